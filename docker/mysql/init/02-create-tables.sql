@@ -13,11 +13,12 @@ CREATE TABLE users (
     gender ENUM('Male', 'Female', 'Other') NOT NULL,
     password_hash VARCHAR(255) NOT NULL,
     email VARCHAR(255) NOT NULL UNIQUE,
-    role ENUM('Administrator', 'Regular') NOT NULL DEFAULT 'Regular',
+    role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular') NOT NULL DEFAULT 'Regular',
     country VARCHAR(100),
     city VARCHAR(100),
     is_active BOOLEAN NOT NULL DEFAULT TRUE,
     birth_date DATE,
+    email_verified_at TIMESTAMP NULL COMMENT 'NULL until the email address is verified',
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
     INDEX idx_email (email),
@@ -195,6 +196,57 @@ CREATE TABLE completed_recipes (
     INDEX idx_rating (rating)
 ) ENGINE=InnoDB;
 
+-- Shopping lists table
+CREATE TABLE shopping_lists (
+    shopping_list_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    user_id INT UNSIGNED NOT NULL,
+    name VARCHAR(150) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(user_id) ON DELETE CASCADE,
+    INDEX idx_user (user_id)
+) ENGINE=InnoDB;
+
+-- Recipes selected for a shopping list
+CREATE TABLE shopping_list_recipes (
+    shopping_list_id INT UNSIGNED NOT NULL,
+    recipe_id INT UNSIGNED NOT NULL,
+    servings INT UNSIGNED NOT NULL,
+    PRIMARY KEY (shopping_list_id, recipe_id),
+    FOREIGN KEY (shopping_list_id) REFERENCES shopping_lists(shopping_list_id) ON DELETE CASCADE,
+    FOREIGN KEY (recipe_id) REFERENCES recipes(recipe_id) ON DELETE CASCADE
+) ENGINE=InnoDB;
+
+-- Shopping list items table
+CREATE TABLE shopping_list_items (
+    shopping_list_item_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    shopping_list_id INT UNSIGNED NOT NULL,
+    ingredient_id INT UNSIGNED NOT NULL,
+    quantity DECIMAL(10,2) NOT NULL COMMENT 'In the ingredient measurement unit',
+    estimated_cost DECIMAL(10,2) NOT NULL DEFAULT 0,
+    is_checked BOOLEAN DEFAULT FALSE,
+    checked_at TIMESTAMP NULL,
+    FOREIGN KEY (shopping_list_id) REFERENCES shopping_lists(shopping_list_id) ON DELETE CASCADE,
+    FOREIGN KEY (ingredient_id) REFERENCES ingredients(ingredient_id) ON DELETE CASCADE,
+    UNIQUE KEY uk_list_ingredient (shopping_list_id, ingredient_id)
+) ENGINE=InnoDB;
+
+-- Meal plans table (one recipe per user, day and meal slot)
+CREATE TABLE meal_plans (
+    meal_plan_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    user_id INT UNSIGNED NOT NULL,
+    plan_date DATE NOT NULL,
+    meal_slot ENUM('breakfast', 'lunch', 'dinner', 'snack') NOT NULL,
+    recipe_id INT UNSIGNED NOT NULL,
+    servings INT UNSIGNED NOT NULL DEFAULT 1,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(user_id) ON DELETE CASCADE,
+    FOREIGN KEY (recipe_id) REFERENCES recipes(recipe_id) ON DELETE CASCADE,
+    UNIQUE KEY uk_user_date_slot (user_id, plan_date, meal_slot),
+    INDEX idx_user_date (user_id, plan_date)
+) ENGINE=InnoDB;
+
 -- =====================================================
 -- AUDIT/HISTORY TABLES
 -- =====================================================
@@ -214,6 +266,21 @@ CREATE TABLE audit_deletions (
     INDEX idx_deleted_by (deleted_by_user_id)
 ) ENGINE=InnoDB;
 
+-- Audit log of requests made with an impersonation token (administrator acting as a user)
+CREATE TABLE audit_impersonations (
+    log_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    impersonator_user_id INT UNSIGNED NOT NULL,
+    impersonated_user_id INT UNSIGNED NOT NULL,
+    session_id INT UNSIGNED,
+    http_method VARCHAR(10) NOT NULL,
+    request_path VARCHAR(500) NOT NULL,
+    ip_address VARCHAR(45),
+    requested_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_impersonator (impersonator_user_id),
+    INDEX idx_impersonated (impersonated_user_id),
+    INDEX idx_requested_at (requested_at)
+) ENGINE=InnoDB;
+
 -- History table for user modifications
 CREATE TABLE history_users (
     history_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
@@ -222,7 +289,7 @@ CREATE TABLE history_users (
     last_name VARCHAR(100),
     gender ENUM('Male', 'Female', 'Other'),
     email VARCHAR(255),
-    role ENUM('Administrator', 'Regular'),
+    role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular'),
     country VARCHAR(100),
     city VARCHAR(100),
     is_active BOOLEAN,
@@ -297,15 +364,72 @@ CREATE TABLE error_logs (
 CREATE TABLE user_sessions (
     session_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
     user_id INT UNSIGNED NOT NULL,
-    session_token VARCHAR(255) NOT NULL UNIQUE,
+    session_token VARCHAR(255) NOT NULL UNIQUE COMMENT 'SHA2(refresh token, 256)',
     ip_address VARCHAR(45),
     user_agent VARCHAR(255),
     login_time TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
     logout_time TIMESTAMP NULL,
     last_activity TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NULL COMMENT 'Refresh token expiration',
     is_active BOOLEAN DEFAULT TRUE,
     INDEX idx_session_token (session_token),
-    INDEX idx_user_id (user_id)
+    INDEX idx_user_id (user_id),
+    INDEX idx_user_active (user_id, is_active)
+) ENGINE=InnoDB;
+
+-- Password reset tokens (only the SHA-256 hash of the emailed token is stored)
+CREATE TABLE password_reset_tokens (
+    token_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    user_id INT UNSIGNED NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE COMMENT 'SHA2(token, 256)',
+    requested_ip VARCHAR(45),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP NULL COMMENT 'Set once the token is consumed or superseded',
+    FOREIGN KEY (user_id) REFERENCES users(user_id) ON DELETE CASCADE,
+    INDEX idx_user_pending (user_id, used_at)
+) ENGINE=InnoDB;
+
+-- Failed login tracking, per account (email) and per client IP
+CREATE TABLE login_failures (
+    scope ENUM('account', 'ip') NOT NULL,
+    identifier VARCHAR(255) NOT NULL COMMENT 'Lower-cased email or client IP',
+    failure_count INT UNSIGNED NOT NULL DEFAULT 0,
+    first_failure_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    last_failure_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    locked_until TIMESTAMP NULL,
+    PRIMARY KEY (scope, identifier),
+    INDEX idx_locked_until (locked_until)
+) ENGINE=InnoDB;
+
+-- Email verification tokens (only the SHA-256 hash of the emailed token is stored)
+CREATE TABLE email_verification_tokens (
+    token_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    user_id INT UNSIGNED NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE COMMENT 'SHA2(token, 256)',
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP NULL COMMENT 'Set once the token is consumed or superseded',
+    FOREIGN KEY (user_id) REFERENCES users(user_id) ON DELETE CASCADE,
+    INDEX idx_user_pending (user_id, used_at)
+) ENGINE=InnoDB;
+
+-- Named permissions checked by the API (e.g. 'recipes.moderate')
+CREATE TABLE permissions (
+    permission_code VARCHAR(50) PRIMARY KEY,
+    description VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB;
+
+-- Role to permission mapping
+CREATE TABLE role_permissions (
+    role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular') NOT NULL,
+    permission_code VARCHAR(50) NOT NULL,
+    granted_by_user_id INT UNSIGNED COMMENT 'NULL for the initial mapping',
+    granted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (role, permission_code),
+    FOREIGN KEY (permission_code) REFERENCES permissions(permission_code) ON DELETE CASCADE,
+    INDEX idx_permission_code (permission_code)
 ) ENGINE=InnoDB;
 
 -- Performance monitoring table
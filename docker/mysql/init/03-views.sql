@@ -107,23 +107,79 @@ INNER JOIN ingredients i ON ri.ingredient_id = i.ingredient_id
 WHERE ri.is_optional = FALSE
 GROUP BY r.recipe_id;
 
+-- View: Recipe cost (price is given for `weight` grams; pieces weigh `weight` grams each)
+CREATE OR REPLACE VIEW v_recipe_cost AS
+SELECT 
+    r.recipe_id,
+    r.title,
+    r.servings,
+    SUM(
+        i.price * (
+            CASE i.measurement_unit
+                WHEN 'pieces' THEN ri.quantity * i.weight
+                WHEN 'kilograms' THEN ri.quantity * 1000
+                WHEN 'liters' THEN ri.quantity * 1000
+                WHEN 'cups' THEN ri.quantity * 240
+                WHEN 'tablespoon' THEN ri.quantity * 15
+                WHEN 'teaspoon' THEN ri.quantity * 5
+                ELSE ri.quantity
+            END
+        ) / NULLIF(i.weight, 0)
+    ) AS total_cost,
+    SUM(
+        i.price * (
+            CASE i.measurement_unit
+                WHEN 'pieces' THEN ri.quantity * i.weight
+                WHEN 'kilograms' THEN ri.quantity * 1000
+                WHEN 'liters' THEN ri.quantity * 1000
+                WHEN 'cups' THEN ri.quantity * 240
+                WHEN 'tablespoon' THEN ri.quantity * 15
+                WHEN 'teaspoon' THEN ri.quantity * 5
+                ELSE ri.quantity
+            END
+        ) / NULLIF(i.weight, 0)
+    ) / GREATEST(r.servings, 1) AS cost_per_serving
+FROM recipes r
+INNER JOIN recipe_ingredients ri ON r.recipe_id = ri.recipe_id
+INNER JOIN ingredients i ON ri.ingredient_id = i.ingredient_id
+WHERE ri.is_optional = FALSE
+GROUP BY r.recipe_id;
+
 -- View: Recipe complexity analysis
 CREATE OR REPLACE VIEW v_recipe_complexity AS
 SELECT 
     r.recipe_id,
     r.title,
     r.difficulty,
-    COUNT(DISTINCT ri.ingredient_id) AS ingredient_count,
-    COUNT(DISTINCT rs.step_order) AS step_count,
-    SUM(rs.duration_minutes) AS total_minutes,
-    COUNT(DISTINCT CASE WHEN ri.is_optional = TRUE THEN ri.ingredient_id END) AS optional_ingredients,
-    COUNT(DISTINCT CASE WHEN rs.step_type = 'cooking' THEN rs.step_order END) AS cooking_steps,
-    COUNT(DISTINCT CASE WHEN rs.step_type = 'action' THEN rs.step_order END) AS action_steps,
-    MAX(rs.duration_minutes) AS longest_step_minutes
+    COALESCE(ing.ingredient_count, 0) AS ingredient_count,
+    COALESCE(st.step_count, 0) AS step_count,
+    COALESCE(st.total_minutes, 0) AS total_minutes,
+    COALESCE(ing.optional_ingredients, 0) AS optional_ingredients,
+    COALESCE(st.cooking_steps, 0) AS cooking_steps,
+    COALESCE(st.action_steps, 0) AS action_steps,
+    st.longest_step_minutes
 FROM recipes r
-LEFT JOIN recipe_ingredients ri ON r.recipe_id = ri.recipe_id
-LEFT JOIN recipe_steps rs ON r.recipe_id = rs.recipe_id
-GROUP BY r.recipe_id;
+-- Ingredients and steps are aggregated separately so that joining both
+-- does not multiply step durations by the number of ingredients
+LEFT JOIN (
+    SELECT
+        recipe_id,
+        COUNT(DISTINCT ingredient_id) AS ingredient_count,
+        COUNT(DISTINCT CASE WHEN is_optional = TRUE THEN ingredient_id END) AS optional_ingredients
+    FROM recipe_ingredients
+    GROUP BY recipe_id
+) ing ON r.recipe_id = ing.recipe_id
+LEFT JOIN (
+    SELECT
+        recipe_id,
+        COUNT(DISTINCT step_order) AS step_count,
+        SUM(duration_minutes) AS total_minutes,
+        COUNT(DISTINCT CASE WHEN step_type = 'cooking' THEN step_order END) AS cooking_steps,
+        COUNT(DISTINCT CASE WHEN step_type = 'action' THEN step_order END) AS action_steps,
+        MAX(duration_minutes) AS longest_step_minutes
+    FROM recipe_steps
+    GROUP BY recipe_id
+) st ON r.recipe_id = st.recipe_id;
 
 -- View: Popular recipes (based on completions and ratings)
 CREATE OR REPLACE VIEW v_popular_recipes AS
@@ -289,6 +345,15 @@ SELECT DISTINCT
             AND uip.preference_type = 'excluded'
             AND ri3.is_optional = FALSE
         ) THEN FALSE
+        WHEN EXISTS (
+            SELECT 1 FROM recipe_ingredients ri4
+            INNER JOIN ingredient_category_assignments ica ON ri4.ingredient_id = ica.ingredient_id
+            INNER JOIN user_ingredient_category_preferences uicp ON ica.category_id = uicp.category_id
+            WHERE ri4.recipe_id = r.recipe_id 
+            AND uicp.user_id = u.user_id 
+            AND uicp.preference_type = 'excluded'
+            AND ri4.is_optional = FALSE
+        ) THEN FALSE
         ELSE TRUE
     END AS respects_preferences
 FROM users u
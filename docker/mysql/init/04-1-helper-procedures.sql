@@ -42,5 +42,25 @@ BEGIN
     );
 END$$
 
-DELIMITER ;
+-- =====================================================
+-- PERMISSIONS
+-- =====================================================
 
+-- Helper function to check whether a role holds a permission (used by ownership checks)
+DROP FUNCTION IF EXISTS fn_role_has_permission$$
+CREATE FUNCTION fn_role_has_permission(
+    p_role VARCHAR(20),
+    p_permission_code VARCHAR(50)
+)
+RETURNS BOOLEAN
+READS SQL DATA
+BEGIN
+    RETURN EXISTS (
+        SELECT 1
+        FROM role_permissions
+        WHERE role = p_role
+        AND permission_code = p_permission_code
+    );
+END$$
+
+DELIMITER ;
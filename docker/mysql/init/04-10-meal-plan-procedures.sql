@@ -0,0 +1,405 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- GESTION DES PLANNINGS DE REPAS
+-- =====================================================
+
+-- Récupérer les repas planifiés d'un utilisateur sur une période
+DROP PROCEDURE IF EXISTS sp_get_user_meal_plans$$
+CREATE PROCEDURE sp_get_user_meal_plans(
+    IN p_user_id INT,
+    IN p_start_date DATE,
+    IN p_end_date DATE,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'start_date', p_start_date,
+                'end_date', p_end_date,
+                'operation', 'GET_USER_MEAL_PLANS'
+            ),
+            'sp_get_user_meal_plans',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        mp.meal_plan_id,
+        mp.user_id,
+        mp.plan_date,
+        mp.meal_slot,
+        mp.recipe_id,
+        r.title as recipe_title,
+        mp.servings,
+        mp.created_at,
+        mp.updated_at
+    FROM meal_plans mp
+    INNER JOIN recipes r ON mp.recipe_id = r.recipe_id
+    WHERE mp.user_id = p_user_id
+    AND mp.plan_date BETWEEN p_start_date AND p_end_date
+    ORDER BY mp.plan_date, FIELD(mp.meal_slot, 'breakfast', 'lunch', 'dinner', 'snack');
+END$$
+
+-- Récupérer un repas planifié par ID
+DROP PROCEDURE IF EXISTS sp_get_meal_plan_by_id$$
+CREATE PROCEDURE sp_get_meal_plan_by_id(
+    IN p_meal_plan_id INT,
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'meal_plan_id', p_meal_plan_id,
+                'operation', 'GET_MEAL_PLAN_BY_ID'
+            ),
+            'sp_get_meal_plan_by_id',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        mp.meal_plan_id,
+        mp.user_id,
+        mp.plan_date,
+        mp.meal_slot,
+        mp.recipe_id,
+        r.title as recipe_title,
+        mp.servings,
+        mp.created_at,
+        mp.updated_at
+    FROM meal_plans mp
+    INNER JOIN recipes r ON mp.recipe_id = r.recipe_id
+    WHERE mp.meal_plan_id = p_meal_plan_id
+    AND mp.user_id = p_user_id;
+END$$
+
+-- Planifier un repas
+DROP PROCEDURE IF EXISTS sp_create_meal_plan$$
+CREATE PROCEDURE sp_create_meal_plan(
+    IN p_user_id INT,
+    IN p_plan_date DATE,
+    IN p_meal_slot VARCHAR(20),
+    IN p_recipe_id INT,
+    IN p_servings INT,
+    OUT p_meal_plan_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_meal_plan_id = NULL;
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'plan_date', p_plan_date,
+                'meal_slot', COALESCE(p_meal_slot, 'NULL'),
+                'recipe_id', p_recipe_id,
+                'operation', 'CREATE_MEAL_PLAN'
+            ),
+            'sp_create_meal_plan',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_meal_plan_id = NULL;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    -- La recette doit être publiée ou appartenir à l'utilisateur
+    IF NOT EXISTS (
+        SELECT 1 FROM recipes
+        WHERE recipe_id = p_recipe_id
+        AND (is_published = TRUE OR author_user_id = p_user_id)
+    ) THEN
+        SET p_error_message = 'Recipe not found';
+        ROLLBACK;
+    ELSEIF EXISTS (
+        SELECT 1 FROM meal_plans
+        WHERE user_id = p_user_id
+        AND plan_date = p_plan_date
+        AND meal_slot = p_meal_slot
+    ) THEN
+        SET p_error_message = 'A meal is already planned for this slot';
+        ROLLBACK;
+    ELSE
+        INSERT INTO meal_plans (user_id, plan_date, meal_slot, recipe_id, servings)
+        VALUES (p_user_id, p_plan_date, p_meal_slot, p_recipe_id, p_servings);
+
+        SET p_meal_plan_id = LAST_INSERT_ID();
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Modifier un repas planifié (les paramètres NULL sont ignorés)
+DROP PROCEDURE IF EXISTS sp_update_meal_plan$$
+CREATE PROCEDURE sp_update_meal_plan(
+    IN p_meal_plan_id INT,
+    IN p_user_id INT,
+    IN p_plan_date DATE,
+    IN p_meal_slot VARCHAR(20),
+    IN p_recipe_id INT,
+    IN p_servings INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_existing_id INT;
+    DECLARE v_plan_date DATE;
+    DECLARE v_meal_slot VARCHAR(20);
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'meal_plan_id', p_meal_plan_id,
+                'operation', 'UPDATE_MEAL_PLAN'
+            ),
+            'sp_update_meal_plan',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT meal_plan_id, plan_date, meal_slot INTO v_existing_id, v_plan_date, v_meal_slot
+    FROM meal_plans
+    WHERE meal_plan_id = p_meal_plan_id AND user_id = p_user_id
+    FOR UPDATE;
+
+    SET v_plan_date = COALESCE(p_plan_date, v_plan_date);
+    SET v_meal_slot = COALESCE(p_meal_slot, v_meal_slot);
+
+    IF v_existing_id IS NULL THEN
+        SET p_error_message = 'Meal plan not found';
+        ROLLBACK;
+    ELSEIF p_recipe_id IS NOT NULL AND NOT EXISTS (
+        SELECT 1 FROM recipes
+        WHERE recipe_id = p_recipe_id
+        AND (is_published = TRUE OR author_user_id = p_user_id)
+    ) THEN
+        SET p_error_message = 'Recipe not found';
+        ROLLBACK;
+    ELSEIF EXISTS (
+        SELECT 1 FROM meal_plans
+        WHERE user_id = p_user_id
+        AND plan_date = v_plan_date
+        AND meal_slot = v_meal_slot
+        AND meal_plan_id != p_meal_plan_id
+    ) THEN
+        SET p_error_message = 'A meal is already planned for this slot';
+        ROLLBACK;
+    ELSE
+        UPDATE meal_plans SET
+            plan_date = v_plan_date,
+            meal_slot = v_meal_slot,
+            recipe_id = COALESCE(p_recipe_id, recipe_id),
+            servings = COALESCE(p_servings, servings)
+        WHERE meal_plan_id = p_meal_plan_id;
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Supprimer un repas planifié
+DROP PROCEDURE IF EXISTS sp_delete_meal_plan$$
+CREATE PROCEDURE sp_delete_meal_plan(
+    IN p_meal_plan_id INT,
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'meal_plan_id', p_meal_plan_id,
+                'operation', 'DELETE_MEAL_PLAN'
+            ),
+            'sp_delete_meal_plan',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    START TRANSACTION;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM meal_plans
+        WHERE meal_plan_id = p_meal_plan_id AND user_id = p_user_id
+    ) THEN
+        SET p_error_message = 'Meal plan not found';
+        ROLLBACK;
+    ELSE
+        DELETE FROM meal_plans WHERE meal_plan_id = p_meal_plan_id;
+
+        SET p_error_message = NULL;
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Enregistrer un planning généré (remplace les repas des créneaux concernés)
+-- p_meals : [{"plan_date": "2024-01-01", "meal_slot": "lunch", "recipe_id": 1, "servings": 2}, ...]
+DROP PROCEDURE IF EXISTS sp_save_meal_plans$$
+CREATE PROCEDURE sp_save_meal_plans(
+    IN p_user_id INT,
+    IN p_meals JSON,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'meal_count', JSON_LENGTH(p_meals),
+                'operation', 'SAVE_MEAL_PLANS'
+            ),
+            'sp_save_meal_plans',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    INSERT INTO meal_plans (user_id, plan_date, meal_slot, recipe_id, servings)
+    SELECT p_user_id, jm.plan_date, jm.meal_slot, jm.recipe_id, jm.servings
+    FROM JSON_TABLE(
+        p_meals, '$[*]' COLUMNS (
+            plan_date DATE PATH '$.plan_date',
+            meal_slot VARCHAR(20) PATH '$.meal_slot',
+            recipe_id INT UNSIGNED PATH '$.recipe_id',
+            servings INT UNSIGNED PATH '$.servings'
+        )
+    ) jm
+    ON DUPLICATE KEY UPDATE
+        recipe_id = VALUES(recipe_id),
+        servings = VALUES(servings);
+
+    COMMIT;
+END$$
+
+DELIMITER ;
@@ -0,0 +1,391 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- GESTION DES SESSIONS ET DES REFRESH TOKENS
+-- =====================================================
+
+-- L'ancien trigger désactivait les autres sessions à chaque connexion (une seule
+-- session par utilisateur) en modifiant la table qu'il surveillait, ce que MySQL refuse
+DROP TRIGGER IF EXISTS trg_after_session_insert$$
+
+-- Ouvrir une session à la connexion (seule l'empreinte du refresh token est stockée)
+DROP PROCEDURE IF EXISTS sp_create_session$$
+CREATE PROCEDURE sp_create_session(
+    IN p_user_id INT,
+    IN p_session_token VARCHAR(255),
+    IN p_ip_address VARCHAR(45),
+    IN p_user_agent VARCHAR(255),
+    IN p_expires_in_seconds INT,
+    OUT p_session_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_session_id = NULL;
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'ip_address', COALESCE(p_ip_address, 'NULL'),
+                'operation', 'CREATE_SESSION'
+            ),
+            'sp_create_session',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_session_id = NULL;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    IF NOT EXISTS (SELECT 1 FROM users WHERE user_id = p_user_id AND is_active = TRUE) THEN
+        SET p_error_message = 'User not found or inactive';
+        ROLLBACK;
+    ELSE
+        -- Clore les sessions expirées de l'utilisateur
+        UPDATE user_sessions
+        SET is_active = FALSE,
+            logout_time = expires_at
+        WHERE user_id = p_user_id
+        AND is_active = TRUE
+        AND expires_at <= NOW();
+
+        INSERT INTO user_sessions (
+            user_id, session_token, ip_address, user_agent, expires_at
+        ) VALUES (
+            p_user_id, SHA2(p_session_token, 256), p_ip_address, p_user_agent,
+            DATE_ADD(NOW(), INTERVAL p_expires_in_seconds SECOND)
+        );
+
+        SET p_session_id = LAST_INSERT_ID();
+        COMMIT;
+    END IF;
+END$$
+
+-- Échanger un refresh token contre un nouveau (rotation) et prolonger la session
+DROP PROCEDURE IF EXISTS sp_refresh_session$$
+CREATE PROCEDURE sp_refresh_session(
+    IN p_session_token VARCHAR(255),
+    IN p_new_session_token VARCHAR(255),
+    IN p_expires_in_seconds INT,
+    OUT p_session_id INT,
+    OUT p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_session_id = NULL;
+        SET p_user_id = NULL;
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'REFRESH_SESSION'
+            ),
+            'sp_refresh_session',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_session_id = NULL;
+    SET p_user_id = NULL;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT s.session_id, s.user_id
+    INTO p_session_id, p_user_id
+    FROM user_sessions s
+    INNER JOIN users u ON s.user_id = u.user_id
+    WHERE s.session_token = SHA2(p_session_token, 256)
+    AND s.is_active = TRUE
+    AND s.expires_at > NOW()
+    AND u.is_active = TRUE
+    FOR UPDATE;
+
+    IF p_session_id IS NULL THEN
+        SET p_user_id = NULL;
+        SET p_error_message = 'Invalid or expired refresh token';
+        ROLLBACK;
+    ELSE
+        UPDATE user_sessions
+        SET session_token = SHA2(p_new_session_token, 256),
+            expires_at = DATE_ADD(NOW(), INTERVAL p_expires_in_seconds SECOND),
+            last_activity = NOW()
+        WHERE session_id = p_session_id;
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Vérifier qu'une session est toujours valide, noter l'activité et renvoyer le rôle courant
+DROP PROCEDURE IF EXISTS sp_touch_session$$
+CREATE PROCEDURE sp_touch_session(
+    IN p_session_id INT,
+    IN p_user_id INT
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_role VARCHAR(20) DEFAULT NULL;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        BEGIN
+            DECLARE CONTINUE HANDLER FOR SQLEXCEPTION BEGIN END;
+            CALL sp_log_error(
+                'SQL_EXCEPTION',
+                COALESCE(v_sql_error, 'Unknown error in sp_touch_session'),
+                JSON_OBJECT(
+                    'sql_state', v_sql_state,
+                    'mysql_errno', v_mysql_errno,
+                    'session_id', p_session_id,
+                    'operation', 'TOUCH_SESSION'
+                ),
+                'sp_touch_session',
+                p_user_id
+            );
+        END;
+
+        RESIGNAL;
+    END;
+
+    -- Le rôle courant est renvoyé : celui du JWT peut dater d'avant un changement de rôle
+    SET v_role = (
+        SELECT u.role
+        FROM user_sessions s
+        INNER JOIN users u ON s.user_id = u.user_id
+        WHERE s.session_id = p_session_id
+        AND s.user_id = p_user_id
+        AND s.is_active = TRUE
+        AND s.expires_at > NOW()
+        AND u.is_active = TRUE
+    );
+
+    -- Limiter les écritures : l'activité est notée au plus une fois par minute
+    IF v_role IS NOT NULL THEN
+        UPDATE user_sessions
+        SET last_activity = NOW()
+        WHERE session_id = p_session_id
+        AND last_activity < DATE_SUB(NOW(), INTERVAL 1 MINUTE);
+    END IF;
+
+    SELECT v_role IS NOT NULL as is_valid, v_role as role;
+END$$
+
+-- Récupérer les sessions actives d'un utilisateur
+DROP PROCEDURE IF EXISTS sp_get_user_sessions$$
+CREATE PROCEDURE sp_get_user_sessions(
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_USER_SESSIONS'
+            ),
+            'sp_get_user_sessions',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        session_id,
+        ip_address,
+        user_agent,
+        login_time,
+        last_activity,
+        expires_at
+    FROM user_sessions
+    WHERE user_id = p_user_id
+    AND is_active = TRUE
+    AND expires_at > NOW()
+    ORDER BY last_activity DESC, session_id DESC;
+END$$
+
+-- Révoquer une session de l'utilisateur
+DROP PROCEDURE IF EXISTS sp_revoke_session$$
+CREATE PROCEDURE sp_revoke_session(
+    IN p_session_id INT,
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'session_id', p_session_id,
+                'operation', 'REVOKE_SESSION'
+            ),
+            'sp_revoke_session',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM user_sessions
+        WHERE session_id = p_session_id
+        AND user_id = p_user_id
+        AND is_active = TRUE
+    ) THEN
+        SET p_error_message = 'Session not found';
+        ROLLBACK;
+    ELSE
+        UPDATE user_sessions
+        SET is_active = FALSE,
+            logout_time = NOW()
+        WHERE session_id = p_session_id;
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Révoquer toutes les sessions d'un utilisateur, sauf éventuellement la session courante
+DROP PROCEDURE IF EXISTS sp_revoke_user_sessions$$
+CREATE PROCEDURE sp_revoke_user_sessions(
+    IN p_user_id INT,
+    IN p_except_session_id INT,
+    OUT p_revoked_count INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_revoked_count = NULL;
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'except_session_id', p_except_session_id,
+                'operation', 'REVOKE_USER_SESSIONS'
+            ),
+            'sp_revoke_user_sessions',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_revoked_count = 0;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    UPDATE user_sessions
+    SET is_active = FALSE,
+        logout_time = NOW()
+    WHERE user_id = p_user_id
+    AND is_active = TRUE
+    AND (p_except_session_id IS NULL OR session_id != p_except_session_id);
+
+    SET p_revoked_count = ROW_COUNT();
+
+    COMMIT;
+END$$
+
+DELIMITER ;
@@ -0,0 +1,618 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- CONSULTATION DE L'AUDIT (ADMINISTRATION)
+-- =====================================================
+
+-- Lister les suppressions archivées
+DROP PROCEDURE IF EXISTS sp_get_audit_deletions$$
+CREATE PROCEDURE sp_get_audit_deletions(
+    IN p_table_name VARCHAR(100),
+    IN p_record_id INT,
+    IN p_deleted_by_user_id INT,
+    IN p_from DATE,
+    IN p_to DATE,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'table_name', COALESCE(p_table_name, 'NULL'),
+                'operation', 'GET_AUDIT_DELETIONS'
+            ),
+            'sp_get_audit_deletions',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    SELECT COUNT(*) as total_count
+    FROM audit_deletions
+    WHERE (p_table_name IS NULL OR table_name = p_table_name)
+    AND (p_record_id IS NULL OR record_id = p_record_id)
+    AND (p_deleted_by_user_id IS NULL OR deleted_by_user_id = p_deleted_by_user_id)
+    AND (p_from IS NULL OR deleted_at >= p_from)
+    AND (p_to IS NULL OR deleted_at < DATE_ADD(p_to, INTERVAL 1 DAY));
+
+    SELECT
+        audit_id,
+        table_name,
+        record_id,
+        deleted_data,
+        deleted_by_user_id,
+        deleted_at,
+        ip_address,
+        user_agent
+    FROM audit_deletions
+    WHERE (p_table_name IS NULL OR table_name = p_table_name)
+    AND (p_record_id IS NULL OR record_id = p_record_id)
+    AND (p_deleted_by_user_id IS NULL OR deleted_by_user_id = p_deleted_by_user_id)
+    AND (p_from IS NULL OR deleted_at >= p_from)
+    AND (p_to IS NULL OR deleted_at < DATE_ADD(p_to, INTERVAL 1 DAY))
+    ORDER BY deleted_at DESC, audit_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+-- Historique des modifications d'utilisateurs
+DROP PROCEDURE IF EXISTS sp_get_user_history$$
+CREATE PROCEDURE sp_get_user_history(
+    IN p_user_id INT,
+    IN p_changed_by_user_id INT,
+    IN p_change_type VARCHAR(10),
+    IN p_from DATE,
+    IN p_to DATE,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'GET_USER_HISTORY'
+            ),
+            'sp_get_user_history',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    SELECT COUNT(*) as total_count
+    FROM history_users
+    WHERE (p_user_id IS NULL OR user_id = p_user_id)
+    AND (p_changed_by_user_id IS NULL OR changed_by_user_id = p_changed_by_user_id)
+    AND (p_change_type IS NULL OR change_type = p_change_type)
+    AND (p_from IS NULL OR changed_at >= p_from)
+    AND (p_to IS NULL OR changed_at < DATE_ADD(p_to, INTERVAL 1 DAY));
+
+    SELECT
+        history_id,
+        user_id,
+        first_name,
+        last_name,
+        gender,
+        email,
+        role,
+        country,
+        city,
+        is_active,
+        birth_date,
+        change_type,
+        changed_by_user_id,
+        changed_at,
+        change_details
+    FROM history_users
+    WHERE (p_user_id IS NULL OR user_id = p_user_id)
+    AND (p_changed_by_user_id IS NULL OR changed_by_user_id = p_changed_by_user_id)
+    AND (p_change_type IS NULL OR change_type = p_change_type)
+    AND (p_from IS NULL OR changed_at >= p_from)
+    AND (p_to IS NULL OR changed_at < DATE_ADD(p_to, INTERVAL 1 DAY))
+    ORDER BY changed_at DESC, history_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+-- Historique des modifications de recettes
+DROP PROCEDURE IF EXISTS sp_get_recipe_history$$
+CREATE PROCEDURE sp_get_recipe_history(
+    IN p_recipe_id INT,
+    IN p_changed_by_user_id INT,
+    IN p_change_type VARCHAR(10),
+    IN p_from DATE,
+    IN p_to DATE,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'recipe_id', p_recipe_id,
+                'operation', 'GET_RECIPE_HISTORY'
+            ),
+            'sp_get_recipe_history',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    SELECT COUNT(*) as total_count
+    FROM history_recipes
+    WHERE (p_recipe_id IS NULL OR recipe_id = p_recipe_id)
+    AND (p_changed_by_user_id IS NULL OR changed_by_user_id = p_changed_by_user_id)
+    AND (p_change_type IS NULL OR change_type = p_change_type)
+    AND (p_from IS NULL OR changed_at >= p_from)
+    AND (p_to IS NULL OR changed_at < DATE_ADD(p_to, INTERVAL 1 DAY));
+
+    SELECT
+        history_id,
+        recipe_id,
+        title,
+        description,
+        servings,
+        is_published,
+        difficulty,
+        author_user_id,
+        change_type,
+        changed_by_user_id,
+        changed_at,
+        change_details
+    FROM history_recipes
+    WHERE (p_recipe_id IS NULL OR recipe_id = p_recipe_id)
+    AND (p_changed_by_user_id IS NULL OR changed_by_user_id = p_changed_by_user_id)
+    AND (p_change_type IS NULL OR change_type = p_change_type)
+    AND (p_from IS NULL OR changed_at >= p_from)
+    AND (p_to IS NULL OR changed_at < DATE_ADD(p_to, INTERVAL 1 DAY))
+    ORDER BY changed_at DESC, history_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+-- Historique des modifications d'ingrédients
+DROP PROCEDURE IF EXISTS sp_get_ingredient_history$$
+CREATE PROCEDURE sp_get_ingredient_history(
+    IN p_ingredient_id INT,
+    IN p_changed_by_user_id INT,
+    IN p_change_type VARCHAR(10),
+    IN p_from DATE,
+    IN p_to DATE,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'ingredient_id', p_ingredient_id,
+                'operation', 'GET_INGREDIENT_HISTORY'
+            ),
+            'sp_get_ingredient_history',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    SELECT COUNT(*) as total_count
+    FROM history_ingredients
+    WHERE (p_ingredient_id IS NULL OR ingredient_id = p_ingredient_id)
+    AND (p_changed_by_user_id IS NULL OR changed_by_user_id = p_changed_by_user_id)
+    AND (p_change_type IS NULL OR change_type = p_change_type)
+    AND (p_from IS NULL OR changed_at >= p_from)
+    AND (p_to IS NULL OR changed_at < DATE_ADD(p_to, INTERVAL 1 DAY));
+
+    SELECT
+        history_id,
+        ingredient_id,
+        name,
+        carbohydrates,
+        proteins,
+        fats,
+        fibers,
+        calories,
+        price,
+        change_type,
+        changed_by_user_id,
+        changed_at,
+        change_details
+    FROM history_ingredients
+    WHERE (p_ingredient_id IS NULL OR ingredient_id = p_ingredient_id)
+    AND (p_changed_by_user_id IS NULL OR changed_by_user_id = p_changed_by_user_id)
+    AND (p_change_type IS NULL OR change_type = p_change_type)
+    AND (p_from IS NULL OR changed_at >= p_from)
+    AND (p_to IS NULL OR changed_at < DATE_ADD(p_to, INTERVAL 1 DAY))
+    ORDER BY changed_at DESC, history_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+-- Restaurer une recette ou un ingrédient supprimé à partir de son instantané
+DROP PROCEDURE IF EXISTS sp_restore_deletion$$
+CREATE PROCEDURE sp_restore_deletion(
+    IN p_audit_id INT,
+    IN p_restored_by_user_id INT,
+    OUT p_table_name VARCHAR(100),
+    OUT p_record_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_data JSON;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_table_name = NULL;
+        SET p_record_id = NULL;
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'audit_id', p_audit_id,
+                'operation', 'RESTORE_DELETION'
+            ),
+            'sp_restore_deletion',
+            p_restored_by_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_table_name = NULL;
+    SET p_record_id = NULL;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT table_name, record_id, deleted_data
+    INTO p_table_name, p_record_id, v_data
+    FROM audit_deletions
+    WHERE audit_id = p_audit_id;
+
+    IF p_table_name IS NULL THEN
+        SET p_error_message = 'Audit entry not found';
+        ROLLBACK;
+
+    ELSEIF p_table_name = 'ingredients' THEN
+        IF EXISTS (SELECT 1 FROM ingredients WHERE ingredient_id = p_record_id) THEN
+            SET p_error_message = 'Ingredient id already exists';
+            ROLLBACK;
+        ELSEIF EXISTS (
+            SELECT 1 FROM ingredients
+            WHERE name = JSON_UNQUOTE(JSON_EXTRACT(v_data, '$.name'))
+        ) THEN
+            SET p_error_message = 'Ingredient name already exists';
+            ROLLBACK;
+        ELSE
+            INSERT INTO ingredients (
+                ingredient_id, name, carbohydrates, proteins, fats, fibers,
+                calories, price, weight, measurement_unit, created_at
+            )
+            SELECT
+                p_record_id, jt.name,
+                COALESCE(jt.carbohydrates, 0), COALESCE(jt.proteins, 0),
+                COALESCE(jt.fats, 0), COALESCE(jt.fibers, 0),
+                COALESCE(jt.calories, 0), COALESCE(jt.price, 0),
+                COALESCE(jt.weight, 100), COALESCE(jt.measurement_unit, 'grams'),
+                COALESCE(jt.created_at, NOW())
+            FROM JSON_TABLE(v_data, '$' COLUMNS (
+                name VARCHAR(200) PATH '$.name',
+                carbohydrates DECIMAL(8,2) PATH '$.carbohydrates',
+                proteins DECIMAL(8,2) PATH '$.proteins',
+                fats DECIMAL(8,2) PATH '$.fats',
+                fibers DECIMAL(8,2) PATH '$.fibers',
+                calories DECIMAL(8,2) PATH '$.calories',
+                price DECIMAL(10,2) PATH '$.price',
+                weight DECIMAL(10,2) PATH '$.weight',
+                measurement_unit VARCHAR(20) PATH '$.measurement_unit',
+                created_at DATETIME PATH '$.created_at'
+            )) AS jt;
+
+            INSERT INTO history_ingredients (
+                ingredient_id, name, carbohydrates, proteins, fats, fibers,
+                calories, price, change_type, changed_by_user_id, changed_at, change_details
+            )
+            SELECT
+                ingredient_id, name, carbohydrates, proteins, fats, fibers,
+                calories, price, 'INSERT', p_restored_by_user_id, NOW(),
+                JSON_OBJECT('restored_from_audit_id', p_audit_id)
+            FROM ingredients
+            WHERE ingredient_id = p_record_id;
+
+            COMMIT;
+        END IF;
+
+    ELSEIF p_table_name = 'recipes' THEN
+        IF EXISTS (SELECT 1 FROM recipes WHERE recipe_id = p_record_id) THEN
+            SET p_error_message = 'Recipe id already exists';
+            ROLLBACK;
+        ELSEIF NOT EXISTS (
+            SELECT 1 FROM users
+            WHERE user_id = JSON_EXTRACT(v_data, '$.author_user_id')
+        ) THEN
+            SET p_error_message = 'Recipe author no longer exists';
+            ROLLBACK;
+        ELSE
+            INSERT INTO recipes (
+                recipe_id, title, description, servings, is_published,
+                difficulty, author_user_id, created_at
+            )
+            SELECT
+                p_record_id, jt.title, jt.description, COALESCE(jt.servings, 4),
+                COALESCE(jt.is_published, FALSE), COALESCE(jt.difficulty, 'Medium'),
+                jt.author_user_id, COALESCE(jt.created_at, NOW())
+            FROM JSON_TABLE(v_data, '$' COLUMNS (
+                title VARCHAR(255) PATH '$.title',
+                description TEXT PATH '$.description',
+                servings INT PATH '$.servings',
+                is_published BOOLEAN PATH '$.is_published',
+                difficulty VARCHAR(20) PATH '$.difficulty',
+                author_user_id INT PATH '$.author_user_id',
+                created_at DATETIME PATH '$.created_at'
+            )) AS jt;
+
+            -- Ingrédients capturés, s'ils existent encore
+            INSERT INTO recipe_ingredients (recipe_id, ingredient_id, quantity, is_optional)
+            SELECT p_record_id, jt.ingredient_id, jt.quantity, COALESCE(jt.is_optional, FALSE)
+            FROM JSON_TABLE(v_data, '$.ingredients[*]' COLUMNS (
+                ingredient_id INT PATH '$.ingredient_id',
+                quantity DECIMAL(10,2) PATH '$.quantity',
+                is_optional BOOLEAN PATH '$.is_optional'
+            )) AS jt
+            INNER JOIN ingredients i ON i.ingredient_id = jt.ingredient_id;
+
+            -- Étapes capturées
+            INSERT INTO recipe_steps (recipe_id, step_order, description, duration_minutes, step_type)
+            SELECT
+                p_record_id, jt.step_order, jt.description,
+                COALESCE(jt.duration_minutes, 0), COALESCE(jt.step_type, 'action')
+            FROM JSON_TABLE(v_data, '$.steps[*]' COLUMNS (
+                step_order INT PATH '$.step_order',
+                description TEXT PATH '$.description',
+                duration_minutes INT PATH '$.duration_minutes',
+                step_type VARCHAR(20) PATH '$.step_type'
+            )) AS jt
+            WHERE jt.description IS NOT NULL;
+
+            INSERT INTO history_recipes (
+                recipe_id, title, description, servings, difficulty, is_published,
+                author_user_id, change_type, changed_by_user_id, changed_at, change_details
+            )
+            SELECT
+                recipe_id, title, description, servings, difficulty, is_published,
+                author_user_id, 'INSERT', p_restored_by_user_id, NOW(),
+                JSON_OBJECT('restored_from_audit_id', p_audit_id)
+            FROM recipes
+            WHERE recipe_id = p_record_id;
+
+            COMMIT;
+        END IF;
+
+    ELSE
+        SET p_error_message = 'Only recipes and ingredients can be restored';
+        ROLLBACK;
+    END IF;
+END$$
+
+-- Journaliser une requête effectuée avec un jeton d'usurpation
+DROP PROCEDURE IF EXISTS sp_record_impersonation$$
+CREATE PROCEDURE sp_record_impersonation(
+    IN p_impersonator_user_id INT,
+    IN p_impersonated_user_id INT,
+    IN p_session_id INT,
+    IN p_http_method VARCHAR(10),
+    IN p_request_path VARCHAR(500),
+    IN p_ip_address VARCHAR(45),
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'impersonated_user_id', p_impersonated_user_id,
+                'operation', 'RECORD_IMPERSONATION'
+            ),
+            'sp_record_impersonation',
+            p_impersonator_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    INSERT INTO audit_impersonations (
+        impersonator_user_id, impersonated_user_id, session_id,
+        http_method, request_path, ip_address
+    ) VALUES (
+        p_impersonator_user_id, p_impersonated_user_id, p_session_id,
+        p_http_method, LEFT(p_request_path, 500), p_ip_address
+    );
+END$$
+
+-- Lister les requêtes effectuées par usurpation
+DROP PROCEDURE IF EXISTS sp_get_audit_impersonations$$
+CREATE PROCEDURE sp_get_audit_impersonations(
+    IN p_impersonated_user_id INT,
+    IN p_impersonator_user_id INT,
+    IN p_from DATE,
+    IN p_to DATE,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_AUDIT_IMPERSONATIONS'
+            ),
+            'sp_get_audit_impersonations',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    SELECT COUNT(*) as total_count
+    FROM audit_impersonations
+    WHERE (p_impersonated_user_id IS NULL OR impersonated_user_id = p_impersonated_user_id)
+    AND (p_impersonator_user_id IS NULL OR impersonator_user_id = p_impersonator_user_id)
+    AND (p_from IS NULL OR requested_at >= p_from)
+    AND (p_to IS NULL OR requested_at < DATE_ADD(p_to, INTERVAL 1 DAY));
+
+    SELECT
+        log_id,
+        impersonator_user_id,
+        impersonated_user_id,
+        session_id,
+        http_method,
+        request_path,
+        ip_address,
+        requested_at
+    FROM audit_impersonations
+    WHERE (p_impersonated_user_id IS NULL OR impersonated_user_id = p_impersonated_user_id)
+    AND (p_impersonator_user_id IS NULL OR impersonator_user_id = p_impersonator_user_id)
+    AND (p_from IS NULL OR requested_at >= p_from)
+    AND (p_to IS NULL OR requested_at < DATE_ADD(p_to, INTERVAL 1 DAY))
+    ORDER BY requested_at DESC, log_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+DELIMITER ;
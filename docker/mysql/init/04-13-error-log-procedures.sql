@@ -0,0 +1,265 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- SUIVI DU JOURNAL DES ERREURS (ADMINISTRATION)
+-- =====================================================
+
+-- Lister les erreurs journalisées avec filtres
+DROP PROCEDURE IF EXISTS sp_get_error_logs$$
+CREATE PROCEDURE sp_get_error_logs(
+    IN p_error_type VARCHAR(100),
+    IN p_procedure_name VARCHAR(100),
+    IN p_user_id INT,
+    IN p_resolved BOOLEAN,
+    IN p_from DATE,
+    IN p_to DATE,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'error_type', COALESCE(p_error_type, 'NULL'),
+                'operation', 'GET_ERROR_LOGS'
+            ),
+            'sp_get_error_logs',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    SELECT COUNT(*) as total_count
+    FROM error_logs
+    WHERE (p_error_type IS NULL OR error_type = p_error_type)
+    AND (p_procedure_name IS NULL OR procedure_name = p_procedure_name)
+    AND (p_user_id IS NULL OR user_id = p_user_id)
+    AND (p_resolved IS NULL OR resolved = p_resolved)
+    AND (p_from IS NULL OR occurred_at >= p_from)
+    AND (p_to IS NULL OR occurred_at < DATE_ADD(p_to, INTERVAL 1 DAY));
+
+    SELECT
+        error_id,
+        error_type,
+        error_message,
+        error_details,
+        procedure_name,
+        user_id,
+        occurred_at,
+        ip_address,
+        resolved,
+        resolved_at,
+        resolved_by_user_id,
+        resolution_notes
+    FROM error_logs
+    WHERE (p_error_type IS NULL OR error_type = p_error_type)
+    AND (p_procedure_name IS NULL OR procedure_name = p_procedure_name)
+    AND (p_user_id IS NULL OR user_id = p_user_id)
+    AND (p_resolved IS NULL OR resolved = p_resolved)
+    AND (p_from IS NULL OR occurred_at >= p_from)
+    AND (p_to IS NULL OR occurred_at < DATE_ADD(p_to, INTERVAL 1 DAY))
+    ORDER BY occurred_at DESC, error_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+-- Récupérer une erreur par ID
+DROP PROCEDURE IF EXISTS sp_get_error_log_by_id$$
+CREATE PROCEDURE sp_get_error_log_by_id(
+    IN p_error_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'error_id', p_error_id,
+                'operation', 'GET_ERROR_LOG_BY_ID'
+            ),
+            'sp_get_error_log_by_id',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        error_id,
+        error_type,
+        error_message,
+        error_details,
+        procedure_name,
+        user_id,
+        occurred_at,
+        ip_address,
+        resolved,
+        resolved_at,
+        resolved_by_user_id,
+        resolution_notes
+    FROM error_logs
+    WHERE error_id = p_error_id;
+END$$
+
+-- Résumé des erreurs sur les 7 derniers jours
+DROP PROCEDURE IF EXISTS sp_get_error_summary$$
+CREATE PROCEDURE sp_get_error_summary(
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_ERROR_SUMMARY'
+            ),
+            'sp_get_error_summary',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        error_date,
+        error_type,
+        procedure_name,
+        error_count,
+        affected_users,
+        unresolved_count,
+        first_occurrence,
+        last_occurrence
+    FROM v_error_summary
+    ORDER BY error_date DESC, error_count DESC;
+END$$
+
+-- Marquer une erreur comme résolue
+DROP PROCEDURE IF EXISTS sp_resolve_error$$
+CREATE PROCEDURE sp_resolve_error(
+    IN p_error_id INT,
+    IN p_resolved_by_user_id INT,
+    IN p_resolution_notes TEXT,
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_resolved BOOLEAN DEFAULT NULL;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_success = FALSE;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'error_id', p_error_id,
+                'operation', 'RESOLVE_ERROR'
+            ),
+            'sp_resolve_error',
+            p_resolved_by_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT resolved INTO v_resolved
+    FROM error_logs
+    WHERE error_id = p_error_id
+    FOR UPDATE;
+
+    IF v_resolved IS NULL THEN
+        SET p_error_message = 'Error log not found';
+        ROLLBACK;
+    ELSEIF v_resolved = TRUE THEN
+        SET p_error_message = 'Error log already resolved';
+        ROLLBACK;
+    ELSE
+        UPDATE error_logs
+        SET resolved = TRUE,
+            resolved_at = NOW(),
+            resolved_by_user_id = p_resolved_by_user_id,
+            resolution_notes = p_resolution_notes
+        WHERE error_id = p_error_id;
+
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
+
+DELIMITER ;
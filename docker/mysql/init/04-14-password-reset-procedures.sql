@@ -0,0 +1,155 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- RÉINITIALISATION DU MOT DE PASSE
+-- =====================================================
+
+-- Enregistrer un jeton de réinitialisation (seule son empreinte est stockée)
+DROP PROCEDURE IF EXISTS sp_create_password_reset$$
+CREATE PROCEDURE sp_create_password_reset(
+    IN p_user_id INT,
+    IN p_token VARCHAR(255),
+    IN p_expires_in_seconds INT,
+    IN p_requested_ip VARCHAR(45),
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'CREATE_PASSWORD_RESET'
+            ),
+            'sp_create_password_reset',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    IF NOT EXISTS (SELECT 1 FROM users WHERE user_id = p_user_id AND is_active = TRUE) THEN
+        SET p_error_message = 'User not found or inactive';
+        ROLLBACK;
+    ELSE
+        -- Un seul lien valide à la fois : les demandes précédentes sont périmées
+        UPDATE password_reset_tokens
+        SET used_at = NOW()
+        WHERE user_id = p_user_id
+        AND used_at IS NULL;
+
+        INSERT INTO password_reset_tokens (
+            user_id, token_hash, requested_ip, expires_at
+        ) VALUES (
+            p_user_id, SHA2(p_token, 256), p_requested_ip,
+            DATE_ADD(NOW(), INTERVAL p_expires_in_seconds SECOND)
+        );
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Consommer un jeton : change le mot de passe et ferme toutes les sessions
+DROP PROCEDURE IF EXISTS sp_reset_password$$
+CREATE PROCEDURE sp_reset_password(
+    IN p_token VARCHAR(255),
+    IN p_password_hash VARCHAR(255),
+    OUT p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_token_id INT DEFAULT NULL;
+    DECLARE v_user_id INT DEFAULT NULL;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_user_id = NULL;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'RESET_PASSWORD'
+            ),
+            'sp_reset_password',
+            v_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_user_id = NULL;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT t.token_id, t.user_id INTO v_token_id, v_user_id
+    FROM password_reset_tokens t
+    JOIN users u ON u.user_id = t.user_id
+    WHERE t.token_hash = SHA2(p_token, 256)
+    AND t.used_at IS NULL
+    AND t.expires_at > NOW()
+    AND u.is_active = TRUE
+    FOR UPDATE;
+
+    IF v_token_id IS NULL THEN
+        SET p_error_message = 'Invalid or expired reset token';
+        ROLLBACK;
+    ELSE
+        UPDATE users
+        SET password_hash = p_password_hash
+        WHERE user_id = v_user_id;
+
+        UPDATE password_reset_tokens
+        SET used_at = NOW()
+        WHERE user_id = v_user_id
+        AND used_at IS NULL;
+
+        UPDATE user_sessions
+        SET is_active = FALSE,
+            logout_time = NOW()
+        WHERE user_id = v_user_id
+        AND is_active = TRUE;
+
+        SET p_user_id = v_user_id;
+        COMMIT;
+    END IF;
+END$$
+
+DELIMITER ;
@@ -0,0 +1,226 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- VÉRIFICATION DE L'ADRESSE EMAIL
+-- =====================================================
+
+-- Enregistrer un jeton de vérification (seule son empreinte est stockée)
+DROP PROCEDURE IF EXISTS sp_create_email_verification$$
+CREATE PROCEDURE sp_create_email_verification(
+    IN p_user_id INT,
+    IN p_token VARCHAR(255),
+    IN p_expires_in_seconds INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_user_found BOOLEAN DEFAULT FALSE;
+    DECLARE v_verified_at TIMESTAMP DEFAULT NULL;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'CREATE_EMAIL_VERIFICATION'
+            ),
+            'sp_create_email_verification',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT TRUE, email_verified_at INTO v_user_found, v_verified_at
+    FROM users
+    WHERE user_id = p_user_id
+    AND is_active = TRUE
+    FOR UPDATE;
+
+    IF NOT v_user_found THEN
+        SET p_error_message = 'User not found or inactive';
+        ROLLBACK;
+    ELSEIF v_verified_at IS NOT NULL THEN
+        SET p_error_message = 'Email already verified';
+        ROLLBACK;
+    ELSE
+        -- Seul le dernier lien envoyé reste valide
+        UPDATE email_verification_tokens
+        SET used_at = NOW()
+        WHERE user_id = p_user_id
+        AND used_at IS NULL;
+
+        INSERT INTO email_verification_tokens (
+            user_id, token_hash, expires_at
+        ) VALUES (
+            p_user_id, SHA2(p_token, 256),
+            DATE_ADD(NOW(), INTERVAL p_expires_in_seconds SECOND)
+        );
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Consommer un jeton de vérification
+DROP PROCEDURE IF EXISTS sp_verify_email$$
+CREATE PROCEDURE sp_verify_email(
+    IN p_token VARCHAR(255),
+    OUT p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_token_id INT DEFAULT NULL;
+    DECLARE v_user_id INT DEFAULT NULL;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_user_id = NULL;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'VERIFY_EMAIL'
+            ),
+            'sp_verify_email',
+            v_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_user_id = NULL;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT t.token_id, t.user_id INTO v_token_id, v_user_id
+    FROM email_verification_tokens t
+    JOIN users u ON u.user_id = t.user_id
+    WHERE t.token_hash = SHA2(p_token, 256)
+    AND t.used_at IS NULL
+    AND t.expires_at > NOW()
+    AND u.is_active = TRUE
+    FOR UPDATE;
+
+    IF v_token_id IS NULL THEN
+        SET p_error_message = 'Invalid or expired verification token';
+        ROLLBACK;
+    ELSE
+        UPDATE users
+        SET email_verified_at = COALESCE(email_verified_at, NOW())
+        WHERE user_id = v_user_id;
+
+        UPDATE email_verification_tokens
+        SET used_at = NOW()
+        WHERE user_id = v_user_id
+        AND used_at IS NULL;
+
+        SET p_user_id = v_user_id;
+        COMMIT;
+    END IF;
+END$$
+
+-- Forcer le statut de vérification (administration)
+DROP PROCEDURE IF EXISTS sp_set_email_verified$$
+CREATE PROCEDURE sp_set_email_verified(
+    IN p_user_id INT,
+    IN p_verified BOOLEAN,
+    IN p_changed_by_user_id INT,
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_success = FALSE;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'SET_EMAIL_VERIFIED'
+            ),
+            'sp_set_email_verified',
+            p_changed_by_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    IF NOT EXISTS (SELECT 1 FROM users WHERE user_id = p_user_id) THEN
+        SET p_error_message = 'User not found';
+        ROLLBACK;
+    ELSE
+        UPDATE users
+        SET email_verified_at = IF(p_verified, COALESCE(email_verified_at, NOW()), NULL)
+        WHERE user_id = p_user_id;
+
+        -- Une validation manuelle rend les liens en attente inutiles
+        IF p_verified THEN
+            UPDATE email_verification_tokens
+            SET used_at = NOW()
+            WHERE user_id = p_user_id
+            AND used_at IS NULL;
+        END IF;
+
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
+
+DELIMITER ;
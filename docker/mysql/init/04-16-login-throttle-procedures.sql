@@ -0,0 +1,285 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- PROTECTION CONTRE LA FORCE BRUTE (CONNEXION)
+-- =====================================================
+
+-- Comptabiliser un échec pour une portée (compte ou IP) ; renvoie la durée du verrou posé
+DROP PROCEDURE IF EXISTS sp_register_login_failure$$
+CREATE PROCEDURE sp_register_login_failure(
+    IN p_scope VARCHAR(10),
+    IN p_identifier VARCHAR(255),
+    IN p_threshold INT,
+    IN p_base_lockout_seconds INT,
+    IN p_max_lockout_seconds INT,
+    IN p_window_seconds INT,
+    IN p_user_id INT,
+    IN p_ip_address VARCHAR(45),
+    OUT p_lockout_seconds INT
+)
+BEGIN
+    DECLARE v_failure_count INT DEFAULT 0;
+
+    SET p_lockout_seconds = 0;
+
+    -- Le compteur repart de zéro après une période calme sans verrou actif
+    INSERT INTO login_failures (
+        scope, identifier, failure_count, first_failure_at, last_failure_at
+    ) VALUES (
+        p_scope, p_identifier, 1, NOW(), NOW()
+    )
+    ON DUPLICATE KEY UPDATE
+        failure_count = IF(
+            last_failure_at < DATE_SUB(NOW(), INTERVAL p_window_seconds SECOND)
+            AND (locked_until IS NULL OR locked_until <= NOW()),
+            1,
+            failure_count + 1
+        ),
+        first_failure_at = IF(failure_count = 1, NOW(), first_failure_at),
+        last_failure_at = NOW();
+
+    SELECT failure_count INTO v_failure_count
+    FROM login_failures
+    WHERE scope = p_scope AND identifier = p_identifier;
+
+    -- Au-delà du seuil, chaque échec double la durée du verrou (plafonnée)
+    IF v_failure_count >= p_threshold THEN
+        SET p_lockout_seconds = LEAST(
+            p_max_lockout_seconds,
+            p_base_lockout_seconds * POW(2, LEAST(v_failure_count - p_threshold, 20))
+        );
+
+        UPDATE login_failures
+        SET locked_until = DATE_ADD(NOW(), INTERVAL p_lockout_seconds SECOND)
+        WHERE scope = p_scope AND identifier = p_identifier;
+
+        CALL sp_log_error(
+            IF(p_scope = 'account', 'ACCOUNT_LOCKOUT', 'IP_LOCKOUT'),
+            CONCAT('Login locked for ', p_lockout_seconds, ' seconds after ',
+                   v_failure_count, ' failed attempts'),
+            JSON_OBJECT(
+                'scope', p_scope,
+                'identifier', p_identifier,
+                'ip_address', p_ip_address,
+                'failure_count', v_failure_count,
+                'lockout_seconds', p_lockout_seconds
+            ),
+            'sp_reserve_login_attempt',
+            p_user_id
+        );
+    END IF;
+END$$
+
+-- Réserver une tentative de connexion avant la vérification du mot de passe.
+-- Les lignes du compte et de l'IP sont verrouillées puis la tentative est comptée
+-- comme un échec dans la même transaction : des requêtes parallèles ne peuvent pas
+-- toutes passer sous le seuil. Renvoie le délai restant si un verrou était déjà actif
+-- (la tentative n'est alors pas comptée), 0 si la tentative peut continuer.
+DROP PROCEDURE IF EXISTS sp_reserve_login_attempt$$
+CREATE PROCEDURE sp_reserve_login_attempt(
+    IN p_email VARCHAR(255),
+    IN p_ip_address VARCHAR(45),
+    IN p_user_id INT,
+    IN p_account_threshold INT,
+    IN p_ip_threshold INT,
+    IN p_base_lockout_seconds INT,
+    IN p_max_lockout_seconds INT,
+    IN p_window_seconds INT,
+    OUT p_retry_after INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_email VARCHAR(255);
+    DECLARE v_account_lockout INT DEFAULT 0;
+    DECLARE v_ip_lockout INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_retry_after = NULL;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'RESERVE_LOGIN_ATTEMPT'
+            ),
+            'sp_reserve_login_attempt',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_retry_after = 0;
+    SET p_error_message = NULL;
+    SET v_email = LOWER(TRIM(p_email));
+
+    START TRANSACTION;
+
+    -- Créer ou verrouiller les compteurs (toujours dans le même ordre : compte puis IP)
+    INSERT INTO login_failures (scope, identifier, failure_count, first_failure_at, last_failure_at)
+    VALUES ('account', v_email, 0, NOW(), NOW())
+    ON DUPLICATE KEY UPDATE identifier = identifier;
+
+    IF p_ip_address IS NOT NULL THEN
+        INSERT INTO login_failures (scope, identifier, failure_count, first_failure_at, last_failure_at)
+        VALUES ('ip', p_ip_address, 0, NOW(), NOW())
+        ON DUPLICATE KEY UPDATE identifier = identifier;
+    END IF;
+
+    SELECT COALESCE(MAX(TIMESTAMPDIFF(SECOND, NOW(), locked_until)), 0) INTO p_retry_after
+    FROM login_failures
+    WHERE (
+        (scope = 'account' AND identifier = v_email)
+        OR (scope = 'ip' AND identifier = p_ip_address)
+    )
+    AND locked_until > NOW()
+    FOR UPDATE;
+
+    IF p_retry_after = 0 THEN
+        CALL sp_register_login_failure(
+            'account', v_email, p_account_threshold,
+            p_base_lockout_seconds, p_max_lockout_seconds, p_window_seconds,
+            p_user_id, p_ip_address, v_account_lockout
+        );
+
+        IF p_ip_address IS NOT NULL THEN
+            CALL sp_register_login_failure(
+                'ip', p_ip_address, p_ip_threshold,
+                p_base_lockout_seconds, p_max_lockout_seconds, p_window_seconds,
+                p_user_id, p_ip_address, v_ip_lockout
+            );
+        END IF;
+    END IF;
+
+    COMMIT;
+END$$
+
+-- Connexion réussie : effacer les échecs du compte et rendre la tentative réservée à l'IP
+DROP PROCEDURE IF EXISTS sp_reset_login_failures$$
+CREATE PROCEDURE sp_reset_login_failures(
+    IN p_email VARCHAR(255),
+    IN p_ip_address VARCHAR(45),
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'RESET_LOGIN_FAILURES'
+            ),
+            'sp_reset_login_failures',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    DELETE FROM login_failures
+    WHERE scope = 'account'
+    AND identifier = LOWER(TRIM(p_email));
+
+    UPDATE login_failures
+    SET failure_count = GREATEST(CAST(failure_count AS SIGNED) - 1, 0)
+    WHERE scope = 'ip'
+    AND identifier = p_ip_address;
+END$$
+
+-- Déverrouiller un compte (administration)
+DROP PROCEDURE IF EXISTS sp_unlock_user_login$$
+CREATE PROCEDURE sp_unlock_user_login(
+    IN p_user_id INT,
+    IN p_unlocked_by_user_id INT,
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_email VARCHAR(255) DEFAULT NULL;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_success = FALSE;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'UNLOCK_USER_LOGIN'
+            ),
+            'sp_unlock_user_login',
+            p_unlocked_by_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    SELECT email INTO v_email
+    FROM users
+    WHERE user_id = p_user_id;
+
+    IF v_email IS NULL THEN
+        SET p_error_message = 'User not found';
+        ROLLBACK;
+    ELSE
+        DELETE FROM login_failures
+        WHERE scope = 'account'
+        AND identifier = LOWER(v_email);
+
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
+
+DELIMITER ;
@@ -0,0 +1,269 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- PERMISSIONS DES RÔLES
+-- =====================================================
+
+-- Catalogue des permissions
+DROP PROCEDURE IF EXISTS sp_get_permissions$$
+CREATE PROCEDURE sp_get_permissions(
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_PERMISSIONS'
+            ),
+            'sp_get_permissions',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT permission_code, description
+    FROM permissions
+    ORDER BY permission_code;
+END$$
+
+-- Permissions accordées aux rôles (tous les rôles si p_role est NULL)
+DROP PROCEDURE IF EXISTS sp_get_role_permissions$$
+CREATE PROCEDURE sp_get_role_permissions(
+    IN p_role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular'),
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'role', p_role,
+                'operation', 'GET_ROLE_PERMISSIONS'
+            ),
+            'sp_get_role_permissions',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        rp.role,
+        rp.permission_code,
+        p.description,
+        rp.granted_by_user_id,
+        rp.granted_at
+    FROM role_permissions rp
+    INNER JOIN permissions p ON rp.permission_code = p.permission_code
+    WHERE (p_role IS NULL OR rp.role = p_role)
+    ORDER BY rp.role, rp.permission_code;
+END$$
+
+-- Vérification d'une permission (appelée à chaque requête protégée)
+DROP PROCEDURE IF EXISTS sp_check_permission$$
+CREATE PROCEDURE sp_check_permission(
+    IN p_role VARCHAR(20),
+    IN p_permission_code VARCHAR(50)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        BEGIN
+            DECLARE CONTINUE HANDLER FOR SQLEXCEPTION BEGIN END;
+            CALL sp_log_error(
+                'SQL_EXCEPTION',
+                COALESCE(v_sql_error, 'Unknown error in sp_check_permission'),
+                JSON_OBJECT(
+                    'sql_state', v_sql_state,
+                    'mysql_errno', v_mysql_errno,
+                    'role', p_role,
+                    'permission_code', p_permission_code,
+                    'operation', 'CHECK_PERMISSION'
+                ),
+                'sp_check_permission',
+                NULL
+            );
+        END;
+
+        RESIGNAL;
+    END;
+
+    SELECT fn_role_has_permission(p_role, p_permission_code) AS is_allowed;
+END$$
+
+-- Accorder une permission à un rôle
+DROP PROCEDURE IF EXISTS sp_grant_role_permission$$
+CREATE PROCEDURE sp_grant_role_permission(
+    IN p_role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular'),
+    IN p_permission_code VARCHAR(50),
+    IN p_granted_by_user_id INT,
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_success = FALSE;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'role', p_role,
+                'permission_code', p_permission_code,
+                'operation', 'GRANT_ROLE_PERMISSION'
+            ),
+            'sp_grant_role_permission',
+            p_granted_by_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    IF NOT EXISTS (SELECT 1 FROM permissions WHERE permission_code = p_permission_code) THEN
+        SET p_error_message = 'Permission not found';
+        ROLLBACK;
+    ELSEIF fn_role_has_permission(p_role, p_permission_code) THEN
+        SET p_error_message = 'Permission already granted to this role';
+        ROLLBACK;
+    ELSE
+        INSERT INTO role_permissions (role, permission_code, granted_by_user_id)
+        VALUES (p_role, p_permission_code, p_granted_by_user_id);
+
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
+
+-- Retirer une permission à un rôle
+DROP PROCEDURE IF EXISTS sp_revoke_role_permission$$
+CREATE PROCEDURE sp_revoke_role_permission(
+    IN p_role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular'),
+    IN p_permission_code VARCHAR(50),
+    IN p_revoked_by_user_id INT,
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_success = FALSE;
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'role', p_role,
+                'permission_code', p_permission_code,
+                'operation', 'REVOKE_ROLE_PERMISSION'
+            ),
+            'sp_revoke_role_permission',
+            p_revoked_by_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    -- Les administrateurs doivent toujours pouvoir gérer les permissions
+    IF p_role = 'Administrator' AND p_permission_code = 'permissions.manage' THEN
+        SET p_error_message = 'Cannot revoke permissions.manage from Administrator';
+        ROLLBACK;
+    ELSEIF NOT fn_role_has_permission(p_role, p_permission_code) THEN
+        SET p_error_message = 'Permission not granted to this role';
+        ROLLBACK;
+    ELSE
+        DELETE FROM role_permissions
+        WHERE role = p_role
+        AND permission_code = p_permission_code;
+
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
+
+DELIMITER ;
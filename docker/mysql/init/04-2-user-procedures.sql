@@ -67,7 +67,8 @@ BEGIN
         is_active,
         birth_date,
         created_at,
-        updated_at
+        updated_at,
+        email_verified_at
     FROM users 
     WHERE email = p_email
     LIMIT 1;
@@ -137,7 +138,8 @@ BEGIN
         is_active,
         birth_date,
         created_at,
-        updated_at
+        updated_at,
+        email_verified_at
     FROM users 
     WHERE user_id = p_id
     LIMIT 1;
@@ -152,7 +154,7 @@ CREATE PROCEDURE sp_create_user(
     IN p_gender ENUM('Male', 'Female', 'Other'),
     IN p_password VARCHAR(255),
     IN p_email VARCHAR(255),
-    IN p_role ENUM('Administrator', 'Regular'),
+    IN p_role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular'),
     IN p_country VARCHAR(100),
     IN p_city VARCHAR(100),
     IN p_birth_date DATE,
@@ -318,7 +320,12 @@ END$$
 DROP PROCEDURE IF EXISTS sp_get_all_user$$
 CREATE PROCEDURE sp_get_all_user(
     IN p_page INT,
-    IN p_page_size INT
+    IN p_page_size INT,
+    IN p_query VARCHAR(255),
+    IN p_role VARCHAR(20),
+    IN p_country VARCHAR(100),
+    IN p_city VARCHAR(100),
+    IN p_is_active BOOLEAN
 )
 BEGIN
     DECLARE v_sql_error TEXT;
@@ -345,7 +352,9 @@ BEGIN
                     'mysql_errno', v_mysql_errno,
                     'operation', 'GET_ALL_USER',
                     'page', p_page,
-                    'page_size', p_page_size
+                    'page_size', p_page_size,
+                    'query', COALESCE(p_query, 'NULL'),
+                    'role', COALESCE(p_role, 'NULL')
                 ),
                 'sp_get_all_user',
                 NULL
@@ -359,9 +368,14 @@ BEGIN
     -- Calculer l'offset
     SET v_offset = (p_page - 1) * p_page_size;
     
-    -- Requête pour obtenir le nombre total d'utilisateurs
+    -- Requête pour obtenir le nombre total d'utilisateurs correspondant aux filtres
     SELECT COUNT(*) as total_count
-    FROM users;
+    FROM users
+    WHERE (p_query IS NULL OR CONCAT_WS(' ', first_name, last_name, email) LIKE CONCAT('%', p_query, '%'))
+    AND (p_role IS NULL OR role = p_role)
+    AND (p_country IS NULL OR country = p_country)
+    AND (p_city IS NULL OR city = p_city)
+    AND (p_is_active IS NULL OR is_active = p_is_active);
     
     -- Requête principale avec pagination
     SELECT 
@@ -377,13 +391,351 @@ BEGIN
         is_active,
         birth_date,
         created_at,
-        updated_at
+        updated_at,
+        email_verified_at
     FROM users
-    ORDER BY created_at DESC
+    WHERE (p_query IS NULL OR CONCAT_WS(' ', first_name, last_name, email) LIKE CONCAT('%', p_query, '%'))
+    AND (p_role IS NULL OR role = p_role)
+    AND (p_country IS NULL OR country = p_country)
+    AND (p_city IS NULL OR city = p_city)
+    AND (p_is_active IS NULL OR is_active = p_is_active)
+    ORDER BY created_at DESC, user_id DESC
     LIMIT p_page_size OFFSET v_offset;
 END$$
 
+-- Procedure: Change user password
+DROP PROCEDURE IF EXISTS sp_update_user_password$$
+CREATE PROCEDURE sp_update_user_password(
+    IN p_user_id INT,
+    IN p_password_hash VARCHAR(255),
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+        
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_success = FALSE;
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'UPDATE_USER_PASSWORD'
+            ),
+            'sp_update_user_password',
+            p_user_id
+        );
+
+        -- Re-raise the error to propagate it to the calling code
+        RESIGNAL;
+    END;
+    
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+    
+    START TRANSACTION;
+    
+    IF NOT EXISTS (SELECT 1 FROM users WHERE user_id = p_user_id AND is_active = TRUE) THEN
+        SET p_error_message = 'User not found or inactive';
+        
+        CALL sp_log_error(
+            'USER_NOT_FOUND',
+            p_error_message,
+            JSON_OBJECT('user_id', p_user_id, 'operation', 'UPDATE_USER_PASSWORD'),
+            'sp_update_user_password',
+            p_user_id
+        );
+        
+        ROLLBACK;
+    ELSE
+        UPDATE users
+        SET password_hash = p_password_hash
+        WHERE user_id = p_user_id;
+        
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
 
+-- Procedure: Get a user with statistics (statistics are NULL for inactive users)
+DROP PROCEDURE IF EXISTS sp_get_user_with_stats$$
+CREATE PROCEDURE sp_get_user_with_stats(
+    IN p_user_id INT
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        BEGIN
+            DECLARE CONTINUE HANDLER FOR SQLEXCEPTION BEGIN END;
+            CALL sp_log_error(
+                'SQL_EXCEPTION',
+                COALESCE(v_sql_error, 'Unknown error in sp_get_user_with_stats'),
+                JSON_OBJECT(
+                    'sql_state', v_sql_state,
+                    'mysql_errno', v_mysql_errno,
+                    'user_id', p_user_id,
+                    'operation', 'GET_USER_WITH_STATS'
+                ),
+                'sp_get_user_with_stats',
+                NULL
+            );
+        END;
+        
+        RESIGNAL;
+    END;
+    
+    SELECT 
+        u.user_id,
+        u.first_name,
+        u.last_name,
+        u.gender,
+        u.email,
+        u.role,
+        u.country,
+        u.city,
+        u.is_active,
+        u.birth_date,
+        u.created_at,
+        u.updated_at,
+        TIMESTAMPDIFF(YEAR, u.birth_date, CURDATE()) as age,
+        s.recipes_created,
+        s.recipes_completed,
+        CAST(s.average_rating_given AS DOUBLE) as average_rating_given,
+        s.allergy_count,
+        s.stock_items_count,
+        u.email_verified_at
+    FROM users u
+    LEFT JOIN v_user_statistics s ON u.user_id = s.user_id
+    WHERE u.user_id = p_user_id;
+END$$
 
-DELIMITER ;
+-- Procedure: Change user role (the last administrator cannot be demoted)
+DROP PROCEDURE IF EXISTS sp_update_user_role$$
+CREATE PROCEDURE sp_update_user_role(
+    IN p_user_id INT,
+    IN p_role ENUM('Administrator', 'Moderator', 'Nutritionist', 'Regular'),
+    IN p_changed_by_user_id INT,
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_current_role VARCHAR(20) DEFAULT NULL;
+    DECLARE v_other_admins INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+        
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_success = FALSE;
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'role', COALESCE(p_role, 'NULL'),
+                'changed_by', p_changed_by_user_id,
+                'operation', 'UPDATE_USER_ROLE'
+            ),
+            'sp_update_user_role',
+            p_changed_by_user_id
+        );
+
+        -- Re-raise the error to propagate it to the calling code
+        RESIGNAL;
+    END;
+    
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+    
+    START TRANSACTION;
+    
+    SELECT role INTO v_current_role
+    FROM users
+    WHERE user_id = p_user_id
+    FOR UPDATE;
+    
+    -- Verrouiller les administrateurs actifs pour éviter deux rétrogradations simultanées
+    SELECT COUNT(*) INTO v_other_admins
+    FROM users
+    WHERE role = 'Administrator' AND is_active = TRUE AND user_id != p_user_id
+    FOR UPDATE;
+    
+    IF v_current_role IS NULL THEN
+        SET p_error_message = 'User not found';
+        ROLLBACK;
+    ELSEIF v_current_role = 'Administrator' AND p_role != 'Administrator' AND v_other_admins = 0 THEN
+        SET p_error_message = 'Cannot remove the last administrator';
+        
+        CALL sp_log_error(
+            'LAST_ADMINISTRATOR',
+            p_error_message,
+            JSON_OBJECT('user_id', p_user_id, 'operation', 'UPDATE_USER_ROLE'),
+            'sp_update_user_role',
+            p_changed_by_user_id
+        );
+        
+        ROLLBACK;
+    ELSE
+        UPDATE users
+        SET role = p_role
+        WHERE user_id = p_user_id;
+        
+        -- Attribuer le changement dans l'historique
+        IF v_current_role != p_role THEN
+            UPDATE history_users
+            SET changed_by_user_id = p_changed_by_user_id
+            WHERE history_id = (
+                SELECT history_id FROM (
+                    SELECT MAX(history_id) AS history_id
+                    FROM history_users
+                    WHERE user_id = p_user_id AND change_type = 'UPDATE'
+                ) AS last_change
+            );
+        END IF;
+        
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
+
+-- Procedure: Activate or deactivate a user (soft delete)
+-- trg_before_user_update records the change in history_users and
+-- trg_before_user_soft_delete unpublishes the user's recipes on deactivation
+DROP PROCEDURE IF EXISTS sp_set_user_active$$
+CREATE PROCEDURE sp_set_user_active(
+    IN p_user_id INT,
+    IN p_is_active BOOLEAN,
+    IN p_changed_by_user_id INT,
+    OUT p_success BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_current_role VARCHAR(20) DEFAULT NULL;
+    DECLARE v_current_active BOOLEAN DEFAULT NULL;
+    DECLARE v_other_admins INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+        
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_success = FALSE;
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'is_active', p_is_active,
+                'changed_by', p_changed_by_user_id,
+                'operation', 'SET_USER_ACTIVE'
+            ),
+            'sp_set_user_active',
+            p_changed_by_user_id
+        );
 
+        -- Re-raise the error to propagate it to the calling code
+        RESIGNAL;
+    END;
+    
+    SET p_success = FALSE;
+    SET p_error_message = NULL;
+    
+    START TRANSACTION;
+    
+    SELECT role, is_active INTO v_current_role, v_current_active
+    FROM users
+    WHERE user_id = p_user_id
+    FOR UPDATE;
+    
+    SELECT COUNT(*) INTO v_other_admins
+    FROM users
+    WHERE role = 'Administrator' AND is_active = TRUE AND user_id != p_user_id
+    FOR UPDATE;
+    
+    IF v_current_role IS NULL THEN
+        SET p_error_message = 'User not found';
+        ROLLBACK;
+    ELSEIF v_current_role = 'Administrator' AND v_current_active = TRUE
+        AND p_is_active = FALSE AND v_other_admins = 0 THEN
+        SET p_error_message = 'Cannot remove the last administrator';
+        
+        CALL sp_log_error(
+            'LAST_ADMINISTRATOR',
+            p_error_message,
+            JSON_OBJECT('user_id', p_user_id, 'operation', 'SET_USER_ACTIVE'),
+            'sp_set_user_active',
+            p_changed_by_user_id
+        );
+        
+        ROLLBACK;
+    ELSE
+        UPDATE users
+        SET is_active = p_is_active
+        WHERE user_id = p_user_id;
+        
+        -- Attribuer le changement dans l'historique
+        IF v_current_active != p_is_active THEN
+            UPDATE history_users
+            SET changed_by_user_id = p_changed_by_user_id
+            WHERE history_id = (
+                SELECT history_id FROM (
+                    SELECT MAX(history_id) AS history_id
+                    FROM history_users
+                    WHERE user_id = p_user_id AND change_type = 'UPDATE'
+                ) AS last_change
+            );
+        END IF;
+        
+        SET p_success = TRUE;
+        COMMIT;
+    END IF;
+END$$
+
+DELIMITER ;
@@ -467,9 +467,9 @@ BEGIN
     
     START TRANSACTION;
     
-    -- Vérifier que l'utilisateur est administrateur
-    IF p_user_role != 'Administrator' THEN
-        SET p_error_msg = 'Only administrators can add ingredient images';
+    -- Vérifier que le rôle de l'utilisateur permet de modifier les ingrédients
+    IF NOT fn_role_has_permission(p_user_role, 'ingredients.edit') THEN
+        SET p_error_msg = 'You are not authorized to add ingredient images';
         SET p_image_id = NULL;
         
         BEGIN
@@ -127,7 +127,7 @@ BEGIN
     IF NOT EXISTS (
         SELECT 1 FROM recipes 
         WHERE recipe_id = p_recipe_id 
-        AND (author_user_id = p_user_id OR p_user_role = 'Administrator')
+        AND (author_user_id = p_user_id OR fn_role_has_permission(p_user_role, 'recipes.moderate'))
     ) THEN
         SET p_error_message = 'Recipe not found or you are not authorized';
         SET p_success = FALSE;
@@ -241,7 +241,8 @@ END$$
 DROP PROCEDURE IF EXISTS sp_get_all_recipes$$
 CREATE PROCEDURE sp_get_all_recipes(
     IN p_page INT,
-    IN p_page_size INT
+    IN p_page_size INT,
+    IN p_max_cost_per_serving DECIMAL(10,2)
 )
 BEGIN
     DECLARE v_sql_error TEXT;
@@ -280,8 +281,10 @@ BEGIN
     
     -- Nombre total de recettes publiées
     SELECT COUNT(*) as total_count
-    FROM recipes
-    WHERE is_published = TRUE;
+    FROM recipes r
+    LEFT JOIN v_recipe_cost c ON r.recipe_id = c.recipe_id
+    WHERE r.is_published = TRUE
+    AND (p_max_cost_per_serving IS NULL OR COALESCE(c.cost_per_serving, 0) <= p_max_cost_per_serving);
     
     -- Requête principale avec pagination
     SELECT 
@@ -298,7 +301,9 @@ BEGIN
         u.last_name as author_last_name
     FROM recipes r
     LEFT JOIN users u ON r.author_user_id = u.user_id
+    LEFT JOIN v_recipe_cost c ON r.recipe_id = c.recipe_id
     WHERE r.is_published = TRUE
+    AND (p_max_cost_per_serving IS NULL OR COALESCE(c.cost_per_serving, 0) <= p_max_cost_per_serving)
     ORDER BY r.created_at DESC
     LIMIT p_page_size OFFSET v_offset;
 END$$
@@ -499,7 +504,7 @@ BEGIN
     END IF;
     
     -- Vérifier les permissions (auteur ou admin)
-    IF p_user_id != v_author_id AND p_user_role != 'Administrator' THEN
+    IF p_user_id != v_author_id AND NOT fn_role_has_permission(p_user_role, 'recipes.moderate') THEN
         SET p_error_message = 'You are not authorized to add steps to this recipe';
         SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'You are not authorized to add steps to this recipe';
     END IF;
@@ -601,7 +606,7 @@ BEGIN
     WHERE recipe_id = v_recipe_id;
     
     -- Vérifier les permissions (auteur ou admin)
-    IF p_user_id != v_author_id AND p_user_role != 'Administrator' THEN
+    IF p_user_id != v_author_id AND NOT fn_role_has_permission(p_user_role, 'recipes.moderate') THEN
         SET p_error_message = 'You are not authorized to update this recipe step';
         SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'You are not authorized to update this recipe step';
     END IF;
@@ -692,7 +697,7 @@ BEGIN
     WHERE recipe_id = v_recipe_id;
     
     -- Vérifier les permissions (auteur ou admin)
-    IF p_user_id != v_author_id AND p_user_role != 'Administrator' THEN
+    IF p_user_id != v_author_id AND NOT fn_role_has_permission(p_user_role, 'recipes.moderate') THEN
         SET p_error_message = 'You are not authorized to delete this recipe step';
         SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'You are not authorized to delete this recipe step';
     END IF;
@@ -834,7 +839,7 @@ BEGIN
     ELSEIF NOT EXISTS (
         SELECT 1 FROM recipes 
         WHERE recipe_id = p_recipe_id 
-        AND (author_user_id = p_user_id OR p_user_role = 'Administrator')
+        AND (author_user_id = p_user_id OR fn_role_has_permission(p_user_role, 'recipes.moderate'))
     ) THEN
         SET p_error_message = 'You are not authorized to update this recipe';
         
@@ -924,7 +929,7 @@ BEGIN
     ELSEIF NOT EXISTS (
         SELECT 1 FROM recipes 
         WHERE recipe_id = p_recipe_id 
-        AND (author_user_id = p_user_id OR p_user_role = 'Administrator')
+        AND (author_user_id = p_user_id OR fn_role_has_permission(p_user_role, 'recipes.moderate'))
     ) THEN
         SET p_error_message = 'You are not authorized to delete this recipe';
         
@@ -938,12 +943,17 @@ BEGIN
         
         ROLLBACK;
     ELSE
-        -- Supprimer d'abord les ingrédients liés
-        DELETE FROM recipe_ingredients WHERE recipe_id = p_recipe_id;
-        
-        -- Supprimer la recette
+        -- Supprimer la recette (ingrédients et étapes suivent par cascade, après
+        -- que trg_before_recipe_delete les a archivés dans audit_deletions)
         DELETE FROM recipes WHERE recipe_id = p_recipe_id;
         
+        -- Attribuer la suppression
+        UPDATE audit_deletions
+        SET deleted_by_user_id = p_user_id
+        WHERE table_name = 'recipes' AND record_id = p_recipe_id
+        ORDER BY audit_id DESC
+        LIMIT 1;
+        
         SET p_error_message = NULL;
         
         COMMIT;
@@ -998,7 +1008,7 @@ BEGIN
     IF NOT EXISTS (
         SELECT 1 FROM recipes 
         WHERE recipe_id = p_recipe_id 
-        AND (author_user_id = p_user_id OR p_user_role = 'Administrator')
+        AND (author_user_id = p_user_id OR fn_role_has_permission(p_user_role, 'recipes.moderate'))
     ) THEN
         SET p_error_message = 'Recipe not found or you are not authorized';
         
@@ -1143,7 +1153,7 @@ BEGIN
     IF NOT EXISTS (
         SELECT 1 FROM recipes 
         WHERE recipe_id = p_recipe_id 
-        AND (author_user_id = p_uploaded_by_user_id OR p_user_role = 'Administrator')
+        AND (author_user_id = p_uploaded_by_user_id OR fn_role_has_permission(p_user_role, 'recipes.moderate'))
     ) THEN
         SET p_error_msg = 'Recipe not found or you are not authorized';
         SET p_image_id = NULL;
@@ -1208,5 +1218,821 @@ BEGIN
     END IF;
 END$$
 
+-- Récupérer toutes les recettes publiées avec leurs ingrédients
+-- (premier jeu de résultats : recettes, second : ingrédients)
+DROP PROCEDURE IF EXISTS sp_get_published_recipes_with_ingredients$$
+CREATE PROCEDURE sp_get_published_recipes_with_ingredients(
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_PUBLISHED_RECIPES_WITH_INGREDIENTS'
+            ),
+            'sp_get_published_recipes_with_ingredients',
+            NULL
+        );
+        
+        RESIGNAL;
+    END;
+    
+    SET p_error_message = NULL;
+    
+    SELECT 
+        r.recipe_id,
+        r.title,
+        r.description,
+        r.servings,
+        r.difficulty,
+        r.author_user_id,
+        r.is_published,
+        r.created_at,
+        r.updated_at,
+        u.first_name as author_first_name,
+        u.last_name as author_last_name
+    FROM recipes r
+    LEFT JOIN users u ON r.author_user_id = u.user_id
+    WHERE r.is_published = TRUE
+    ORDER BY r.recipe_id;
+    
+    SELECT 
+        ri.recipe_id,
+        ri.ingredient_id,
+        i.name as ingredient_name,
+        ri.quantity,
+        i.measurement_unit,
+        ri.is_optional,
+        i.carbohydrates,
+        i.proteins,
+        i.fats,
+        i.fibers,
+        i.calories,
+        i.price,
+        i.weight
+    FROM recipe_ingredients ri
+    INNER JOIN recipes r ON ri.recipe_id = r.recipe_id
+    INNER JOIN ingredients i ON ri.ingredient_id = i.ingredient_id
+    WHERE r.is_published = TRUE
+    ORDER BY ri.recipe_id, ri.ingredient_id;
+END$$
+
+-- Récupérer les recettes publiées compatibles avec les allergies et préférences d'un utilisateur
+DROP PROCEDURE IF EXISTS sp_get_recipes_for_user$$
+CREATE PROCEDURE sp_get_recipes_for_user(
+    IN p_user_id INT,
+    IN p_page INT,
+    IN p_page_size INT,
+    IN p_max_cost_per_serving DECIMAL(10,2),
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_RECIPES_FOR_USER',
+                'page', p_page,
+                'page_size', p_page_size
+            ),
+            'sp_get_recipes_for_user',
+            p_user_id
+        );
+        
+        RESIGNAL;
+    END;
+    
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+    
+    -- Nombre total de recettes compatibles
+    SELECT COUNT(*) as total_count
+    FROM v_user_compatible_recipes v
+    LEFT JOIN v_recipe_cost c ON v.recipe_id = c.recipe_id
+    WHERE v.user_id = p_user_id
+    AND v.is_allergy_safe = TRUE
+    AND v.respects_preferences = TRUE
+    AND (p_max_cost_per_serving IS NULL OR COALESCE(c.cost_per_serving, 0) <= p_max_cost_per_serving);
+    
+    -- Requête principale avec pagination
+    SELECT 
+        r.recipe_id,
+        r.title,
+        r.description,
+        r.servings,
+        r.difficulty,
+        r.author_user_id,
+        r.is_published,
+        r.created_at,
+        r.updated_at,
+        u.first_name as author_first_name,
+        u.last_name as author_last_name
+    FROM v_user_compatible_recipes v
+    INNER JOIN recipes r ON v.recipe_id = r.recipe_id
+    LEFT JOIN users u ON r.author_user_id = u.user_id
+    LEFT JOIN v_recipe_cost c ON r.recipe_id = c.recipe_id
+    WHERE v.user_id = p_user_id
+    AND v.is_allergy_safe = TRUE
+    AND v.respects_preferences = TRUE
+    AND (p_max_cost_per_serving IS NULL OR COALESCE(c.cost_per_serving, 0) <= p_max_cost_per_serving)
+    ORDER BY r.created_at DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+-- Récupérer, pour chaque recette publiée écartée, les ingrédients qui ont déclenché l'exclusion
+DROP PROCEDURE IF EXISTS sp_get_recipe_exclusions_for_user$$
+CREATE PROCEDURE sp_get_recipe_exclusions_for_user(
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_RECIPE_EXCLUSIONS_FOR_USER'
+            ),
+            'sp_get_recipe_exclusions_for_user',
+            p_user_id
+        );
+        
+        RESIGNAL;
+    END;
+    
+    SET p_error_message = NULL;
+    
+    -- Mêmes règles que v_user_compatible_recipes : seuls les ingrédients obligatoires comptent
+    SELECT recipe_id, title, ingredient_id, ingredient_name, reason, detail, severity
+    FROM (
+        -- Ingrédients contenant un allergène déclaré
+        SELECT 
+            r.recipe_id,
+            r.title,
+            i.ingredient_id,
+            i.name as ingredient_name,
+            'allergy' as reason,
+            a.name as detail,
+            CAST(ua.severity AS CHAR) as severity
+        FROM recipes r
+        INNER JOIN recipe_ingredients ri ON r.recipe_id = ri.recipe_id
+        INNER JOIN ingredients i ON ri.ingredient_id = i.ingredient_id
+        INNER JOIN ingredient_allergies ia ON i.ingredient_id = ia.ingredient_id
+        INNER JOIN user_allergies ua ON ia.allergy_id = ua.allergy_id
+        INNER JOIN allergies a ON ua.allergy_id = a.allergy_id
+        WHERE r.is_published = TRUE
+        AND ri.is_optional = FALSE
+        AND ua.user_id = p_user_id
+        
+        UNION ALL
+        
+        -- Ingrédients exclus explicitement
+        SELECT 
+            r.recipe_id,
+            r.title,
+            i.ingredient_id,
+            i.name,
+            'excluded_ingredient',
+            NULL,
+            NULL
+        FROM recipes r
+        INNER JOIN recipe_ingredients ri ON r.recipe_id = ri.recipe_id
+        INNER JOIN ingredients i ON ri.ingredient_id = i.ingredient_id
+        INNER JOIN user_ingredient_preferences uip ON i.ingredient_id = uip.ingredient_id
+        WHERE r.is_published = TRUE
+        AND ri.is_optional = FALSE
+        AND uip.user_id = p_user_id
+        AND uip.preference_type = 'excluded'
+        
+        UNION ALL
+        
+        -- Ingrédients appartenant à une catégorie exclue
+        SELECT 
+            r.recipe_id,
+            r.title,
+            i.ingredient_id,
+            i.name,
+            'excluded_category',
+            ic.name,
+            NULL
+        FROM recipes r
+        INNER JOIN recipe_ingredients ri ON r.recipe_id = ri.recipe_id
+        INNER JOIN ingredients i ON ri.ingredient_id = i.ingredient_id
+        INNER JOIN ingredient_category_assignments ica ON i.ingredient_id = ica.ingredient_id
+        INNER JOIN ingredient_categories ic ON ica.category_id = ic.category_id
+        INNER JOIN user_ingredient_category_preferences uicp ON ic.category_id = uicp.category_id
+        WHERE r.is_published = TRUE
+        AND ri.is_optional = FALSE
+        AND uicp.user_id = p_user_id
+        AND uicp.preference_type = 'excluded'
+    ) exclusions
+    ORDER BY recipe_id, ingredient_name, reason;
+END$$
+
+-- Rechercher des recettes publiées avec filtres, recherche plein texte et tri
+-- p_include_ingredients / p_exclude_ingredients : listes d'IDs séparés par des virgules
+-- p_sort : 'newest', 'rating', 'popularity' ou 'calories'
+DROP PROCEDURE IF EXISTS sp_search_recipes$$
+CREATE PROCEDURE sp_search_recipes(
+    IN p_query VARCHAR(255),
+    IN p_difficulty VARCHAR(20),
+    IN p_author_id INT,
+    IN p_max_total_minutes INT,
+    IN p_include_ingredients VARCHAR(1000),
+    IN p_exclude_ingredients VARCHAR(1000),
+    IN p_min_rating DECIMAL(3,2),
+    IN p_max_cost_per_serving DECIMAL(10,2),
+    IN p_sort VARCHAR(20),
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    DECLARE v_offset INT;
+    DECLARE v_include_count INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        DROP TEMPORARY TABLE IF EXISTS tmp_recipe_search;
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'SEARCH_RECIPES',
+                'query', COALESCE(p_query, 'NULL'),
+                'sort', COALESCE(p_sort, 'NULL')
+            ),
+            'sp_search_recipes',
+            NULL
+        );
+        
+        RESIGNAL;
+    END;
+    
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+    
+    IF p_include_ingredients IS NOT NULL AND p_include_ingredients != '' THEN
+        SET v_include_count = LENGTH(p_include_ingredients)
+            - LENGTH(REPLACE(p_include_ingredients, ',', '')) + 1;
+    END IF;
+    
+    DROP TEMPORARY TABLE IF EXISTS tmp_recipe_search;
+    
+    -- Recettes correspondant aux filtres, avec les statistiques servant au tri
+    CREATE TEMPORARY TABLE tmp_recipe_search AS
+    SELECT 
+        r.recipe_id,
+        COALESCE(stats.average_rating, 0) as average_rating,
+        COALESCE(stats.completion_count, 0) as completion_count,
+        COALESCE(n.calories_per_serving, 0) as calories_per_serving,
+        -- Pertinence : le titre contient la recherche (2), sinon description ou ingrédient (1)
+        CASE
+            WHEN p_query IS NULL THEN 0
+            WHEN r.title LIKE CONCAT('%', p_query, '%') THEN 2
+            ELSE 1
+        END as relevance
+    FROM recipes r
+    LEFT JOIN (
+        SELECT 
+            recipe_id,
+            AVG(rating) as average_rating,
+            COUNT(*) as completion_count
+        FROM completed_recipes
+        GROUP BY recipe_id
+    ) stats ON r.recipe_id = stats.recipe_id
+    LEFT JOIN v_recipe_nutrition n ON r.recipe_id = n.recipe_id
+    LEFT JOIN v_recipe_cost c ON r.recipe_id = c.recipe_id
+    WHERE r.is_published = TRUE
+    AND (
+        p_query IS NULL
+        OR MATCH(r.title, r.description) AGAINST (p_query IN NATURAL LANGUAGE MODE)
+        OR r.title LIKE CONCAT('%', p_query, '%')
+        OR EXISTS (
+            SELECT 1 FROM recipe_ingredients ri
+            INNER JOIN ingredients i ON ri.ingredient_id = i.ingredient_id
+            WHERE ri.recipe_id = r.recipe_id
+            AND i.name LIKE CONCAT('%', p_query, '%')
+        )
+    )
+    AND (p_difficulty IS NULL OR r.difficulty = p_difficulty)
+    AND (p_author_id IS NULL OR r.author_user_id = p_author_id)
+    AND (
+        p_max_total_minutes IS NULL
+        OR (
+            SELECT COALESCE(SUM(rs.duration_minutes), 0)
+            FROM recipe_steps rs
+            WHERE rs.recipe_id = r.recipe_id
+        ) <= p_max_total_minutes
+    )
+    AND (
+        v_include_count = 0
+        OR (
+            SELECT COUNT(DISTINCT ri.ingredient_id)
+            FROM recipe_ingredients ri
+            WHERE ri.recipe_id = r.recipe_id
+            AND FIND_IN_SET(ri.ingredient_id, p_include_ingredients) > 0
+        ) = v_include_count
+    )
+    AND (
+        p_exclude_ingredients IS NULL
+        OR NOT EXISTS (
+            SELECT 1 FROM recipe_ingredients ri
+            WHERE ri.recipe_id = r.recipe_id
+            AND FIND_IN_SET(ri.ingredient_id, p_exclude_ingredients) > 0
+        )
+    )
+    AND (p_min_rating IS NULL OR stats.average_rating >= p_min_rating)
+    AND (p_max_cost_per_serving IS NULL OR COALESCE(c.cost_per_serving, 0) <= p_max_cost_per_serving);
+    
+    -- Nombre total de résultats
+    SELECT COUNT(*) as total_count
+    FROM tmp_recipe_search;
+    
+    -- Requête principale avec tri et pagination
+    SELECT 
+        r.recipe_id,
+        r.title,
+        r.description,
+        r.servings,
+        r.difficulty,
+        r.author_user_id,
+        r.is_published,
+        r.created_at,
+        r.updated_at,
+        u.first_name as author_first_name,
+        u.last_name as author_last_name
+    FROM tmp_recipe_search t
+    INNER JOIN recipes r ON t.recipe_id = r.recipe_id
+    LEFT JOIN users u ON r.author_user_id = u.user_id
+    ORDER BY
+        CASE WHEN p_sort = 'relevance' THEN t.relevance END DESC,
+        CASE WHEN p_sort = 'rating' THEN t.average_rating END DESC,
+        CASE WHEN p_sort = 'popularity' THEN t.completion_count END DESC,
+        CASE WHEN p_sort = 'calories' THEN t.calories_per_serving END ASC,
+        r.created_at DESC,
+        r.recipe_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+    
+    DROP TEMPORARY TABLE IF EXISTS tmp_recipe_search;
+END$$
+
+-- =====================================================
+-- NOTES ET AVIS
+-- =====================================================
+
+-- Agréger les notes de plusieurs recettes (p_recipe_ids : "1,4,7")
+DROP PROCEDURE IF EXISTS sp_get_recipe_ratings$$
+CREATE PROCEDURE sp_get_recipe_ratings(
+    IN p_recipe_ids TEXT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'recipe_ids', COALESCE(p_recipe_ids, 'NULL'),
+                'operation', 'GET_RECIPE_RATINGS'
+            ),
+            'sp_get_recipe_ratings',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        cr.recipe_id,
+        COUNT(*) as completion_count,
+        COUNT(cr.rating) as rating_count,
+        CAST(AVG(cr.rating) AS DECIMAL(3,2)) as average_rating,
+        CAST(COALESCE(SUM(cr.rating = 1), 0) AS SIGNED) as one_star,
+        CAST(COALESCE(SUM(cr.rating = 2), 0) AS SIGNED) as two_stars,
+        CAST(COALESCE(SUM(cr.rating = 3), 0) AS SIGNED) as three_stars,
+        CAST(COALESCE(SUM(cr.rating = 4), 0) AS SIGNED) as four_stars,
+        CAST(COALESCE(SUM(cr.rating = 5), 0) AS SIGNED) as five_stars
+    FROM completed_recipes cr
+    WHERE FIND_IN_SET(cr.recipe_id, p_recipe_ids) > 0
+    GROUP BY cr.recipe_id;
+END$$
+
+-- Récupérer les avis d'une recette avec pagination (complétions notées ou commentées)
+DROP PROCEDURE IF EXISTS sp_get_recipe_reviews$$
+CREATE PROCEDURE sp_get_recipe_reviews(
+    IN p_recipe_id INT,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_offset INT;
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'recipe_id', p_recipe_id,
+                'page', p_page,
+                'page_size', p_page_size,
+                'operation', 'GET_RECIPE_REVIEWS'
+            ),
+            'sp_get_recipe_reviews',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    IF NOT EXISTS (SELECT 1 FROM recipes WHERE recipe_id = p_recipe_id) THEN
+        SET p_error_message = 'Recipe not found';
+    ELSE
+        -- Nombre total d'avis
+        SELECT COUNT(*) as total_count
+        FROM completed_recipes
+        WHERE recipe_id = p_recipe_id
+        AND (rating IS NOT NULL OR NULLIF(TRIM(comment), '') IS NOT NULL);
+
+        -- Avis, du plus récent au plus ancien ; seul l'initiale du nom est exposée
+        SELECT
+            cr.completion_id,
+            cr.user_id,
+            CONCAT(u.first_name, ' ', LEFT(u.last_name, 1), '.') as reviewer_name,
+            cr.rating,
+            NULLIF(TRIM(cr.comment), '') as comment,
+            cr.completion_date
+        FROM completed_recipes cr
+        INNER JOIN users u ON cr.user_id = u.user_id
+        WHERE cr.recipe_id = p_recipe_id
+        AND (cr.rating IS NOT NULL OR NULLIF(TRIM(cr.comment), '') IS NOT NULL)
+        ORDER BY cr.completion_date DESC, cr.completion_id DESC
+        LIMIT p_page_size OFFSET v_offset;
+    END IF;
+END$$
+
+-- Historique des recettes réalisées par un utilisateur avec pagination
+DROP PROCEDURE IF EXISTS sp_get_user_completions$$
+CREATE PROCEDURE sp_get_user_completions(
+    IN p_user_id INT,
+    IN p_page INT,
+    IN p_page_size INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_offset INT;
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'page', p_page,
+                'page_size', p_page_size,
+                'operation', 'GET_USER_COMPLETIONS'
+            ),
+            'sp_get_user_completions',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+    SET v_offset = (p_page - 1) * p_page_size;
+
+    -- Nombre total de complétions
+    SELECT COUNT(*) as total_count
+    FROM completed_recipes
+    WHERE user_id = p_user_id;
+
+    SELECT
+        cr.completion_id,
+        cr.recipe_id,
+        r.title as recipe_title,
+        cr.rating,
+        cr.comment,
+        cr.completion_date
+    FROM completed_recipes cr
+    INNER JOIN recipes r ON cr.recipe_id = r.recipe_id
+    WHERE cr.user_id = p_user_id
+    ORDER BY cr.completion_date DESC, cr.completion_id DESC
+    LIMIT p_page_size OFFSET v_offset;
+END$$
+
+-- =====================================================
+-- RECETTES POPULAIRES ET TENDANCES
+-- =====================================================
+
+-- Recettes populaires (v_popular_recipes) avec leurs indicateurs de complexité
+DROP PROCEDURE IF EXISTS sp_get_popular_recipes$$
+CREATE PROCEDURE sp_get_popular_recipes(
+    IN p_limit INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'limit', p_limit,
+                'operation', 'GET_POPULAR_RECIPES'
+            ),
+            'sp_get_popular_recipes',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        r.recipe_id,
+        r.title,
+        r.description,
+        r.servings,
+        r.difficulty,
+        r.author_user_id,
+        r.is_published,
+        r.created_at,
+        r.updated_at,
+        u.first_name as author_first_name,
+        u.last_name as author_last_name,
+        CAST(pr.completion_count AS SIGNED) as completion_count,
+        CAST(pr.average_rating AS DECIMAL(3,2)) as average_rating,
+        CAST(pr.unique_users AS SIGNED) as unique_users,
+        pr.last_completed,
+        CAST(pr.five_star_count AS SIGNED) as five_star_count,
+        CAST(pr.four_plus_star_count AS SIGNED) as four_plus_star_count,
+        CAST(rc.ingredient_count AS SIGNED) as ingredient_count,
+        CAST(rc.step_count AS SIGNED) as step_count,
+        CAST(rc.total_minutes AS SIGNED) as total_minutes,
+        CAST(rc.optional_ingredients AS SIGNED) as optional_ingredients,
+        CAST(rc.cooking_steps AS SIGNED) as cooking_steps,
+        CAST(rc.action_steps AS SIGNED) as action_steps,
+        CAST(rc.longest_step_minutes AS SIGNED) as longest_step_minutes
+    FROM v_popular_recipes pr
+    INNER JOIN recipes r ON pr.recipe_id = r.recipe_id
+    INNER JOIN v_recipe_complexity rc ON pr.recipe_id = rc.recipe_id
+    LEFT JOIN users u ON r.author_user_id = u.user_id
+    ORDER BY pr.average_rating DESC, pr.completion_count DESC, r.recipe_id
+    LIMIT p_limit;
+END$$
+
+-- Recettes tendance : complétions des p_days derniers jours pondérées par leur note
+-- trend_score = somme des notes récentes / 5 (une complétion sans note compte pour 3)
+DROP PROCEDURE IF EXISTS sp_get_trending_recipes$$
+CREATE PROCEDURE sp_get_trending_recipes(
+    IN p_days INT,
+    IN p_limit INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'days', p_days,
+                'limit', p_limit,
+                'operation', 'GET_TRENDING_RECIPES'
+            ),
+            'sp_get_trending_recipes',
+            NULL
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        r.recipe_id,
+        r.title,
+        r.description,
+        r.servings,
+        r.difficulty,
+        r.author_user_id,
+        r.is_published,
+        r.created_at,
+        r.updated_at,
+        u.first_name as author_first_name,
+        u.last_name as author_last_name,
+        CAST(recent.completion_count AS SIGNED) as completion_count,
+        recent.average_rating,
+        CAST(recent.unique_users AS SIGNED) as unique_users,
+        recent.last_completed,
+        recent.trend_score,
+        CAST(rc.ingredient_count AS SIGNED) as ingredient_count,
+        CAST(rc.step_count AS SIGNED) as step_count,
+        CAST(rc.total_minutes AS SIGNED) as total_minutes,
+        CAST(rc.optional_ingredients AS SIGNED) as optional_ingredients,
+        CAST(rc.cooking_steps AS SIGNED) as cooking_steps,
+        CAST(rc.action_steps AS SIGNED) as action_steps,
+        CAST(rc.longest_step_minutes AS SIGNED) as longest_step_minutes
+    FROM (
+        SELECT
+            cr.recipe_id,
+            COUNT(*) as completion_count,
+            CAST(AVG(cr.rating) AS DECIMAL(3,2)) as average_rating,
+            COUNT(DISTINCT cr.user_id) as unique_users,
+            MAX(cr.completion_date) as last_completed,
+            CAST(SUM(COALESCE(cr.rating, 3)) / 5 AS DECIMAL(10,2)) as trend_score
+        FROM completed_recipes cr
+        WHERE cr.completion_date >= NOW() - INTERVAL p_days DAY
+        GROUP BY cr.recipe_id
+    ) recent
+    INNER JOIN recipes r ON recent.recipe_id = r.recipe_id
+    INNER JOIN v_recipe_complexity rc ON recent.recipe_id = rc.recipe_id
+    LEFT JOIN users u ON r.author_user_id = u.user_id
+    WHERE r.is_published = TRUE
+    ORDER BY recent.trend_score DESC, recent.last_completed DESC, r.recipe_id
+    LIMIT p_limit;
+END$$
+
+-- Historique agrégé par recette des complétions d'un utilisateur (pour les recommandations)
+DROP PROCEDURE IF EXISTS sp_get_user_recipe_history$$
+CREATE PROCEDURE sp_get_user_recipe_history(
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_USER_RECIPE_HISTORY'
+            ),
+            'sp_get_user_recipe_history',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        cr.recipe_id,
+        r.title,
+        r.difficulty,
+        COUNT(*) as completion_count,
+        CAST(AVG(cr.rating) AS DECIMAL(3,2)) as average_rating
+    FROM completed_recipes cr
+    INNER JOIN recipes r ON cr.recipe_id = r.recipe_id
+    WHERE cr.user_id = p_user_id
+    GROUP BY cr.recipe_id, r.title, r.difficulty
+    ORDER BY cr.recipe_id;
+END$$
 
-DELIMITER ;
\ No newline at end of file
+DELIMITER ;
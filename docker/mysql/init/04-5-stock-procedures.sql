@@ -54,6 +54,9 @@ BEGIN
         RESIGNAL;
     END;
     
+    SET p_stock_id = NULL;
+    SET p_error_message = NULL;
+    
     START TRANSACTION;
     
     SELECT stock_id, quantity 
@@ -69,7 +72,10 @@ BEGIN
             IF v_existing_stock_id IS NOT NULL THEN
                 UPDATE user_ingredient_stock
                 SET quantity = quantity + p_quantity,
-                    expiration_date = LEAST(COALESCE(expiration_date, p_expiration_date), p_expiration_date)
+                    expiration_date = LEAST(
+                        COALESCE(expiration_date, p_expiration_date),
+                        COALESCE(p_expiration_date, expiration_date)
+                    )
                 WHERE stock_id = v_existing_stock_id;
                 SET p_stock_id = v_existing_stock_id;
             ELSE
@@ -138,9 +144,231 @@ BEGIN
             END IF;
     END CASE;
     
+    -- Les branches "not found" ont déjà fait un ROLLBACK
+    IF p_error_message IS NULL THEN
+        COMMIT;
+    END IF;
+END$$
+
+-- Récupérer le stock complet d'un utilisateur
+DROP PROCEDURE IF EXISTS sp_get_user_stock$$
+CREATE PROCEDURE sp_get_user_stock(
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'GET_USER_STOCK'
+            ),
+            'sp_get_user_stock',
+            p_user_id
+        );
+        
+        RESIGNAL;
+    END;
+    
     SET p_error_message = NULL;
-    COMMIT;
+    
+    SELECT 
+        uis.stock_id,
+        uis.user_id,
+        uis.ingredient_id,
+        i.name as ingredient_name,
+        uis.quantity,
+        i.measurement_unit,
+        uis.expiration_date,
+        uis.storage_location,
+        uis.created_at,
+        uis.updated_at
+    FROM user_ingredient_stock uis
+    INNER JOIN ingredients i ON uis.ingredient_id = i.ingredient_id
+    WHERE uis.user_id = p_user_id
+    ORDER BY i.name, uis.storage_location;
 END$$
 
-DELIMITER ;
+-- Récupérer un élément du stock d'un utilisateur
+DROP PROCEDURE IF EXISTS sp_get_stock_item$$
+CREATE PROCEDURE sp_get_stock_item(
+    IN p_stock_id INT,
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'stock_id', p_stock_id,
+                'user_id', p_user_id,
+                'operation', 'GET_STOCK_ITEM'
+            ),
+            'sp_get_stock_item',
+            p_user_id
+        );
+        
+        RESIGNAL;
+    END;
+    
+    SET p_error_message = NULL;
+    
+    SELECT 
+        uis.stock_id,
+        uis.user_id,
+        uis.ingredient_id,
+        i.name as ingredient_name,
+        uis.quantity,
+        i.measurement_unit,
+        uis.expiration_date,
+        uis.storage_location,
+        uis.created_at,
+        uis.updated_at
+    FROM user_ingredient_stock uis
+    INNER JOIN ingredients i ON uis.ingredient_id = i.ingredient_id
+    WHERE uis.stock_id = p_stock_id
+    AND uis.user_id = p_user_id;
+END$$
 
+-- Récupérer les éléments du stock qui expirent dans les N prochains jours (expirés inclus)
+DROP PROCEDURE IF EXISTS sp_get_expiring_stock$$
+CREATE PROCEDURE sp_get_expiring_stock(
+    IN p_user_id INT,
+    IN p_within_days INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'within_days', p_within_days,
+                'operation', 'GET_EXPIRING_STOCK'
+            ),
+            'sp_get_expiring_stock',
+            p_user_id
+        );
+        
+        RESIGNAL;
+    END;
+    
+    SET p_error_message = NULL;
+    
+    SELECT 
+        v.stock_id,
+        v.ingredient_id,
+        v.ingredient_name,
+        v.quantity,
+        v.expiration_date,
+        v.storage_location,
+        CAST(v.days_until_expiration AS SIGNED) as days_until_expiration,
+        v.expiration_status,
+        i.measurement_unit,
+        i.price,
+        i.weight
+    FROM v_user_stock_expiration v
+    INNER JOIN ingredients i ON v.ingredient_id = i.ingredient_id
+    WHERE v.user_id = p_user_id
+    AND v.days_until_expiration <= p_within_days
+    ORDER BY v.storage_location, v.days_until_expiration ASC;
+END$$
+
+-- Récupérer le résumé du stock d'un utilisateur
+DROP PROCEDURE IF EXISTS sp_get_stock_summary$$
+CREATE PROCEDURE sp_get_stock_summary(
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+    
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+        
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'user_id', p_user_id,
+                'operation', 'GET_STOCK_SUMMARY'
+            ),
+            'sp_get_stock_summary',
+            p_user_id
+        );
+        
+        RESIGNAL;
+    END;
+    
+    SET p_error_message = NULL;
+    
+    SELECT 
+        s.user_id,
+        s.unique_ingredients,
+        s.total_items,
+        s.expired_items,
+        s.expiring_soon,
+        CAST(COALESCE(s.total_stock_value, 0) AS DECIMAL(12,2)) as total_stock_value,
+        s.storage_locations
+    FROM v_user_stock_summary s
+    WHERE s.user_id = p_user_id;
+END$$
+
+DELIMITER ;
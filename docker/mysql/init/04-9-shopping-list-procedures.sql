@@ -0,0 +1,372 @@
+USE food_advisor_db;
+
+DELIMITER $$
+
+-- =====================================================
+-- GESTION DES LISTES DE COURSES
+-- =====================================================
+
+-- Créer une liste de courses avec ses recettes et ses articles
+-- p_recipes : [{"recipe_id": 1, "servings": 4}, ...]
+-- p_items : [{"ingredient_id": 1, "quantity": "250", "estimated_cost": "1.20"}, ...]
+DROP PROCEDURE IF EXISTS sp_create_shopping_list$$
+CREATE PROCEDURE sp_create_shopping_list(
+    IN p_user_id INT,
+    IN p_name VARCHAR(150),
+    IN p_recipes JSON,
+    IN p_items JSON,
+    OUT p_shopping_list_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+        SET p_shopping_list_id = NULL;
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'list_name', COALESCE(p_name, 'NULL'),
+                'operation', 'CREATE_SHOPPING_LIST'
+            ),
+            'sp_create_shopping_list',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_shopping_list_id = NULL;
+    SET p_error_message = NULL;
+
+    START TRANSACTION;
+
+    INSERT INTO shopping_lists (user_id, name)
+    VALUES (p_user_id, p_name);
+
+    SET p_shopping_list_id = LAST_INSERT_ID();
+
+    INSERT INTO shopping_list_recipes (shopping_list_id, recipe_id, servings)
+    SELECT p_shopping_list_id, jr.recipe_id, jr.servings
+    FROM JSON_TABLE(
+        p_recipes, '$[*]' COLUMNS (
+            recipe_id INT UNSIGNED PATH '$.recipe_id',
+            servings INT UNSIGNED PATH '$.servings'
+        )
+    ) jr;
+
+    INSERT INTO shopping_list_items (shopping_list_id, ingredient_id, quantity, estimated_cost)
+    SELECT p_shopping_list_id, ji.ingredient_id, ji.quantity, ji.estimated_cost
+    FROM JSON_TABLE(
+        p_items, '$[*]' COLUMNS (
+            ingredient_id INT UNSIGNED PATH '$.ingredient_id',
+            quantity DECIMAL(10,2) PATH '$.quantity',
+            estimated_cost DECIMAL(10,2) PATH '$.estimated_cost'
+        )
+    ) ji;
+
+    COMMIT;
+END$$
+
+-- Récupérer les listes de courses d'un utilisateur avec leur avancement
+DROP PROCEDURE IF EXISTS sp_get_user_shopping_lists$$
+CREATE PROCEDURE sp_get_user_shopping_lists(
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'operation', 'GET_USER_SHOPPING_LISTS'
+            ),
+            'sp_get_user_shopping_lists',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    SELECT
+        sl.shopping_list_id,
+        sl.name,
+        COUNT(sli.shopping_list_item_id) as item_count,
+        CAST(COALESCE(SUM(sli.is_checked), 0) AS SIGNED) as checked_count,
+        CAST(COALESCE(SUM(sli.estimated_cost), 0) AS DECIMAL(12,2)) as estimated_total,
+        sl.created_at,
+        sl.updated_at
+    FROM shopping_lists sl
+    LEFT JOIN shopping_list_items sli ON sl.shopping_list_id = sli.shopping_list_id
+    WHERE sl.user_id = p_user_id
+    GROUP BY sl.shopping_list_id
+    ORDER BY sl.created_at DESC;
+END$$
+
+-- Récupérer une liste de courses : en-tête, recettes puis articles
+DROP PROCEDURE IF EXISTS sp_get_shopping_list$$
+CREATE PROCEDURE sp_get_shopping_list(
+    IN p_shopping_list_id INT,
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'shopping_list_id', p_shopping_list_id,
+                'operation', 'GET_SHOPPING_LIST'
+            ),
+            'sp_get_shopping_list',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    SET p_error_message = NULL;
+
+    IF EXISTS (
+        SELECT 1 FROM shopping_lists
+        WHERE shopping_list_id = p_shopping_list_id AND user_id = p_user_id
+    ) THEN
+        -- En-tête de la liste
+        SELECT
+            shopping_list_id,
+            user_id,
+            name,
+            created_at,
+            updated_at
+        FROM shopping_lists
+        WHERE shopping_list_id = p_shopping_list_id;
+
+        -- Recettes sélectionnées
+        SELECT
+            slr.recipe_id,
+            r.title,
+            slr.servings
+        FROM shopping_list_recipes slr
+        INNER JOIN recipes r ON slr.recipe_id = r.recipe_id
+        WHERE slr.shopping_list_id = p_shopping_list_id
+        ORDER BY r.title;
+
+        -- Articles, triés par catégorie (la première par ordre alphabétique si plusieurs)
+        SELECT
+            sli.shopping_list_item_id,
+            sli.ingredient_id,
+            i.name as ingredient_name,
+            (
+                SELECT MIN(ic.name)
+                FROM ingredient_category_assignments ica
+                INNER JOIN ingredient_categories ic ON ica.category_id = ic.category_id
+                WHERE ica.ingredient_id = sli.ingredient_id
+            ) as category_name,
+            sli.quantity,
+            i.measurement_unit,
+            sli.estimated_cost,
+            sli.is_checked,
+            sli.checked_at
+        FROM shopping_list_items sli
+        INNER JOIN ingredients i ON sli.ingredient_id = i.ingredient_id
+        WHERE sli.shopping_list_id = p_shopping_list_id
+        ORDER BY category_name IS NULL, category_name, i.name;
+    END IF;
+END$$
+
+-- Cocher ou décocher un article d'une liste de courses
+DROP PROCEDURE IF EXISTS sp_set_shopping_list_item_checked$$
+CREATE PROCEDURE sp_set_shopping_list_item_checked(
+    IN p_shopping_list_id INT,
+    IN p_shopping_list_item_id INT,
+    IN p_user_id INT,
+    IN p_is_checked BOOLEAN,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'shopping_list_id', p_shopping_list_id,
+                'shopping_list_item_id', p_shopping_list_item_id,
+                'operation', 'SET_SHOPPING_LIST_ITEM_CHECKED'
+            ),
+            'sp_set_shopping_list_item_checked',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    START TRANSACTION;
+
+    -- Vérifier que l'article appartient à une liste de l'utilisateur
+    IF NOT EXISTS (
+        SELECT 1 FROM shopping_list_items sli
+        INNER JOIN shopping_lists sl ON sli.shopping_list_id = sl.shopping_list_id
+        WHERE sli.shopping_list_item_id = p_shopping_list_item_id
+        AND sl.shopping_list_id = p_shopping_list_id
+        AND sl.user_id = p_user_id
+    ) THEN
+        SET p_error_message = 'Shopping list item not found';
+
+        CALL sp_log_error(
+            'SHOPPING_LIST_ITEM_NOT_FOUND',
+            p_error_message,
+            JSON_OBJECT(
+                'shopping_list_id', p_shopping_list_id,
+                'shopping_list_item_id', p_shopping_list_item_id,
+                'operation', 'SET_SHOPPING_LIST_ITEM_CHECKED'
+            ),
+            'sp_set_shopping_list_item_checked',
+            p_user_id
+        );
+
+        ROLLBACK;
+    ELSE
+        UPDATE shopping_list_items SET
+            is_checked = p_is_checked,
+            checked_at = IF(p_is_checked, NOW(), NULL)
+        WHERE shopping_list_item_id = p_shopping_list_item_id;
+
+        UPDATE shopping_lists SET updated_at = NOW()
+        WHERE shopping_list_id = p_shopping_list_id;
+
+        SET p_error_message = NULL;
+
+        COMMIT;
+    END IF;
+END$$
+
+-- Supprimer une liste de courses
+DROP PROCEDURE IF EXISTS sp_delete_shopping_list$$
+CREATE PROCEDURE sp_delete_shopping_list(
+    IN p_shopping_list_id INT,
+    IN p_user_id INT,
+    OUT p_error_message VARCHAR(500)
+)
+BEGIN
+    DECLARE v_sql_error TEXT;
+    DECLARE v_sql_state CHAR(5) DEFAULT '00000';
+    DECLARE v_mysql_errno INT DEFAULT 0;
+
+    DECLARE EXIT HANDLER FOR SQLEXCEPTION
+    BEGIN
+        ROLLBACK;
+
+        GET DIAGNOSTICS CONDITION 1
+            v_sql_state = RETURNED_SQLSTATE,
+            v_mysql_errno = MYSQL_ERRNO,
+            v_sql_error = MESSAGE_TEXT;
+
+        SET p_error_message = COALESCE(v_sql_error, 'Unknown SQL error occurred');
+
+        CALL sp_log_error(
+            'SQL_EXCEPTION',
+            p_error_message,
+            JSON_OBJECT(
+                'sql_state', v_sql_state,
+                'mysql_errno', v_mysql_errno,
+                'shopping_list_id', p_shopping_list_id,
+                'operation', 'DELETE_SHOPPING_LIST'
+            ),
+            'sp_delete_shopping_list',
+            p_user_id
+        );
+
+        RESIGNAL;
+    END;
+
+    START TRANSACTION;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM shopping_lists
+        WHERE shopping_list_id = p_shopping_list_id AND user_id = p_user_id
+    ) THEN
+        SET p_error_message = 'Shopping list not found';
+
+        CALL sp_log_error(
+            'SHOPPING_LIST_NOT_FOUND',
+            p_error_message,
+            JSON_OBJECT('shopping_list_id', p_shopping_list_id, 'operation', 'DELETE_SHOPPING_LIST'),
+            'sp_delete_shopping_list',
+            p_user_id
+        );
+
+        ROLLBACK;
+    ELSE
+        -- Les recettes et articles sont supprimés grâce à ON DELETE CASCADE
+        DELETE FROM shopping_lists WHERE shopping_list_id = p_shopping_list_id;
+
+        SET p_error_message = NULL;
+
+        COMMIT;
+    END IF;
+END$$
+
+DELIMITER ;
@@ -144,7 +144,27 @@ BEGIN
             'is_published', OLD.is_published,
             'author_user_id', OLD.author_user_id,
             'created_at', OLD.created_at,
-            'updated_at', OLD.updated_at
+            'updated_at', OLD.updated_at,
+            -- Ingredients and steps are still present (cascade runs after this trigger)
+            'ingredients', (
+                SELECT JSON_ARRAYAGG(JSON_OBJECT(
+                    'ingredient_id', ri.ingredient_id,
+                    'quantity', ri.quantity,
+                    'is_optional', ri.is_optional
+                ))
+                FROM recipe_ingredients ri
+                WHERE ri.recipe_id = OLD.recipe_id
+            ),
+            'steps', (
+                SELECT JSON_ARRAYAGG(JSON_OBJECT(
+                    'step_order', rs.step_order,
+                    'description', rs.description,
+                    'duration_minutes', rs.duration_minutes,
+                    'step_type', rs.step_type
+                ))
+                FROM recipe_steps rs
+                WHERE rs.recipe_id = OLD.recipe_id
+            )
         ),
         NOW()
     );
@@ -260,37 +280,22 @@ END$$
 
 
 
-
--- Trigger: Clean up old sessions on new login
-DROP TRIGGER IF EXISTS trg_after_session_insert$$
-CREATE TRIGGER trg_after_session_insert
-AFTER INSERT ON user_sessions
-FOR EACH ROW
-BEGIN
-    -- Deactivate all other sessions for this user
-    UPDATE user_sessions
-    SET is_active = FALSE,
-        logout_time = NOW()
-    WHERE user_id = NEW.user_id
-    AND session_id != NEW.session_id
-    AND is_active = TRUE;
-END$$
-
-
-
 -- =====================================================
 -- ERROR LOG TRIGGERS
 -- =====================================================
 
 -- Trigger: Auto-escalate critical errors
+-- BEFORE INSERT: an AFTER INSERT trigger cannot update error_logs itself (error 1442),
+-- which made sp_log_error silently drop every error past the threshold
 DROP TRIGGER IF EXISTS trg_after_error_insert$$
-CREATE TRIGGER trg_after_error_insert
-AFTER INSERT ON error_logs
+DROP TRIGGER IF EXISTS trg_before_error_insert$$
+CREATE TRIGGER trg_before_error_insert
+BEFORE INSERT ON error_logs
 FOR EACH ROW
 BEGIN
     DECLARE v_error_count INT;
     
-    -- Count similar unresolved errors in the last hour
+    -- Count similar unresolved errors in the last hour (the new one is not stored yet)
     SELECT COUNT(*)
     INTO v_error_count
     FROM error_logs
@@ -299,11 +304,9 @@ BEGIN
     AND occurred_at >= DATE_SUB(NOW(), INTERVAL 1 HOUR);
     
     -- If more than 10 similar errors in an hour, create escalation
-    IF v_error_count > 10 THEN
-        -- Update the error as critical
-        UPDATE error_logs
-        SET error_message = CONCAT('[CRITICAL - ESCALATED] ', error_message)
-        WHERE error_id = NEW.error_id;
+    IF v_error_count >= 10 THEN
+        -- Flag the error as critical
+        SET NEW.error_message = CONCAT('[CRITICAL - ESCALATED] ', NEW.error_message);
     END IF;
 END$$
 
@@ -7,17 +7,45 @@ SET FOREIGN_KEY_CHECKS = 0;
 -- USERS DATA
 -- =====================================================
 
-INSERT INTO users (user_id, first_name, last_name, gender, password_hash, email, role, country, city, is_active, birth_date) VALUES
-(1, 'John', 'Doe', 'Male', '', 'john.doe@email.com', 'Administrator', 'USA', 'New York', TRUE, '1985-03-15'),
-(2, 'Marie', 'Dubois', 'Female', '', 'marie.dubois@email.com', 'Regular', 'France', 'Paris', TRUE, '1990-07-22'),
-(3, 'Carlos', 'Garcia', 'Male', '', 'carlos.garcia@email.com', 'Regular', 'Spain', 'Madrid', TRUE, '1988-11-30'),
-(4, 'Emma', 'Wilson', 'Female', '', 'emma.wilson@email.com', 'Regular', 'UK', 'London', TRUE, '1992-05-18'),
-(5, 'Luigi', 'Rossi', 'Male', '', 'luigi.rossi@email.com', 'Regular', 'Italy', 'Rome', TRUE, '1987-09-10'),
-(6, 'Sophie', 'Martin', 'Female', '', 'sophie.martin@email.com', 'Administrator', 'France', 'Lyon', TRUE, '1991-12-25'),
-(7, 'James', 'Smith', 'Male', '', 'james.smith@email.com', 'Regular', 'USA', 'Los Angeles', TRUE, '1989-04-08'),
-(8, 'Anna', 'Schmidt', 'Female', '', 'anna.schmidt@email.com', 'Regular', 'Germany', 'Berlin', TRUE, '1993-08-14'),
-(9, 'Pierre', 'Laurent', 'Male', '', 'pierre.laurent@email.com', 'Regular', 'France', 'Marseille', TRUE, '1986-02-28'),
-(10, 'Maria', 'Silva', 'Female', '', 'maria.silva@email.com', 'Regular', 'Brazil', 'São Paulo', TRUE, '1994-06-20');
+-- Seed accounts have no password: sign in through POST /api/auth/dev-login (DEV_MODE)
+INSERT INTO users (user_id, first_name, last_name, gender, password_hash, email, role, country, city, is_active, birth_date, email_verified_at) VALUES
+(1, 'John', 'Doe', 'Male', '', 'john.doe@email.com', 'Administrator', 'USA', 'New York', TRUE, '1985-03-15', NOW()),
+(2, 'Marie', 'Dubois', 'Female', '', 'marie.dubois@email.com', 'Moderator', 'France', 'Paris', TRUE, '1990-07-22', NOW()),
+(3, 'Carlos', 'Garcia', 'Male', '', 'carlos.garcia@email.com', 'Regular', 'Spain', 'Madrid', TRUE, '1988-11-30', NOW()),
+(4, 'Emma', 'Wilson', 'Female', '', 'emma.wilson@email.com', 'Regular', 'UK', 'London', TRUE, '1992-05-18', NOW()),
+(5, 'Luigi', 'Rossi', 'Male', '', 'luigi.rossi@email.com', 'Regular', 'Italy', 'Rome', TRUE, '1987-09-10', NOW()),
+(6, 'Sophie', 'Martin', 'Female', '', 'sophie.martin@email.com', 'Administrator', 'France', 'Lyon', TRUE, '1991-12-25', NOW()),
+(7, 'James', 'Smith', 'Male', '', 'james.smith@email.com', 'Regular', 'USA', 'Los Angeles', TRUE, '1989-04-08', NOW()),
+(8, 'Anna', 'Schmidt', 'Female', '', 'anna.schmidt@email.com', 'Nutritionist', 'Germany', 'Berlin', TRUE, '1993-08-14', NOW()),
+(9, 'Pierre', 'Laurent', 'Male', '', 'pierre.laurent@email.com', 'Regular', 'France', 'Marseille', TRUE, '1986-02-28', NOW()),
+(10, 'Maria', 'Silva', 'Female', '', 'maria.silva@email.com', 'Regular', 'Brazil', 'São Paulo', TRUE, '1994-06-20', NOW());
+
+-- =====================================================
+-- PERMISSIONS DATA
+-- =====================================================
+
+INSERT INTO permissions (permission_code, description) VALUES
+('allergies.manage', 'Create, update and delete allergies and their ingredients'),
+('audit.read', 'Read deletion, history and impersonation audit logs'),
+('audit.restore', 'Restore deleted records from audit snapshots'),
+('categories.manage', 'Create, update and delete ingredient categories'),
+('errors.manage', 'Read and resolve error logs'),
+('ingredients.edit', 'Update and delete ingredients, add ingredient images'),
+('permissions.manage', 'Manage role to permission mappings'),
+('recipes.moderate', 'Edit and delete recipes of other users'),
+('users.impersonate', 'Act as another user with a short-lived token'),
+('users.manage', 'List users, change their role and status, create administrators');
+
+-- Administrators hold every permission
+INSERT INTO role_permissions (role, permission_code)
+SELECT 'Administrator', permission_code FROM permissions;
+
+INSERT INTO role_permissions (role, permission_code) VALUES
+('Moderator', 'recipes.moderate'),
+('Moderator', 'audit.read'),
+('Nutritionist', 'ingredients.edit'),
+('Nutritionist', 'categories.manage'),
+('Nutritionist', 'allergies.manage');
 
 -- =====================================================
 -- ALLERGIES DATA
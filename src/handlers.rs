@@ -1,22 +1,73 @@
 pub mod admin_handler;
+pub mod allergy_handler;
+pub mod audit_handler;
+pub mod email_verification_handler;
+pub mod error_log_handler;
 pub mod image_handler;
 pub mod ingredient_categories_handler;
 pub mod ingredient_handler;
+pub mod meal_plan_handler;
+pub mod password_reset_handler;
+pub mod permission_handler;
 pub mod recipe_handler;
+pub mod recommendation_handler;
+pub mod session_handler;
+pub mod shopping_list_handler;
+pub mod stock_handler;
 pub mod user_handler;
 pub mod user_preferences_handler;
 
 // Ré-exports optionnels pour simplifier les imports
-pub use admin_handler::{create_admin, get_all_users};
+pub use admin_handler::{
+    create_admin, get_all_users, get_user_details, unlock_user_login,
+    update_user_email_verification, update_user_role, update_user_status,
+};
+pub use allergy_handler::{
+    add_ingredient_allergy, create_allergy, delete_allergy, get_all_allergies, get_allergy,
+    get_allergy_ingredients, get_my_allergies, remove_ingredient_allergy, remove_my_allergy,
+    set_my_allergy, update_allergy,
+};
+pub use audit_handler::{
+    get_audit_deletions, get_audit_impersonations, get_ingredient_history, get_recipe_history,
+    get_user_history, restore_deletion,
+};
+pub use email_verification_handler::{resend_verification_email, verify_email};
+pub use error_log_handler::{get_error_log, get_error_logs, get_error_summary, resolve_error_log};
 pub use image_handler::*;
 pub use ingredient_categories_handler::*;
 pub use ingredient_handler::{
     create_ingredient, delete_ingredient, get_all_ingredients, get_ingredient, update_ingredient,
 };
+pub use meal_plan_handler::{
+    create_meal_plan, create_meal_plan_shopping_list, delete_meal_plan, generate_week_meal_plan,
+    get_meal_plan, get_meal_plans, update_meal_plan,
+};
+pub use password_reset_handler::{forgot_password, reset_password};
+pub use permission_handler::{
+    get_all_role_permissions, get_permissions, get_role_permissions, grant_role_permission,
+    revoke_role_permission,
+};
 pub use recipe_handler::{
     add_recipe_ingredient, add_recipe_step, complete_recipe, create_recipe, delete_recipe,
-    delete_recipe_step, get_all_recipes, get_recipe, get_recipe_steps, get_user_recipes,
-    remove_recipe_ingredient, update_recipe, update_recipe_step,
+    delete_recipe_step, get_all_recipes, get_my_completions, get_popular_recipes, get_recipe,
+    get_recipe_cost, get_recipe_nutrition, get_recipe_reviews, get_recipe_steps,
+    get_recipes_for_me, get_trending_recipes, get_user_recipes, remove_recipe_ingredient,
+    search_recipes, update_recipe, update_recipe_step,
+};
+pub use recommendation_handler::{get_cookable_recipes, get_recommendations};
+pub use session_handler::{
+    get_my_sessions, impersonate_user, logout, refresh_token, revoke_my_session,
+    revoke_other_sessions,
+};
+pub use shopping_list_handler::{
+    create_shopping_list, delete_shopping_list, get_shopping_list, get_shopping_lists,
+    update_shopping_list_item,
+};
+pub use stock_handler::{
+    add_stock_item, get_expiring_stock, get_stock_summary, get_user_stock, remove_stock_item,
+    update_stock_item,
+};
+pub use user_handler::{
+    change_password, deactivate_account, dev_login, get_profile, login, register, update_profile,
 };
-pub use user_handler::{login, register};
 pub use user_preferences_handler::*;
@@ -5,8 +5,12 @@ use serde::Deserialize;
 use sqlx::MySqlPool;
 
 use crate::{
-    models::{PaginatedResponse, PaginationInfo, PaginationParams},
-    repositories::UserRepository,
+    models::{
+        PaginatedResponse, PaginationInfo, Role, TokenClaims, UpdateEmailVerificationRequest,
+        UpdateUserRoleRequest, UpdateUserStatusRequest, UserSearchQuery,
+    },
+    repositories::{LoginThrottleRepository, PermissionRepository, UserRepository},
+    utils::auth::extract_user_info,
 };
 
 #[derive(Debug, Deserialize)]
@@ -23,13 +27,13 @@ pub struct CreateAdminRequest {
 
 pub async fn get_all_users(
     pool: web::Data<MySqlPool>,
-    query: web::Query<PaginationParams>,
+    query: web::Query<UserSearchQuery>,
 ) -> HttpResponse {
     let params = query.into_inner();
 
     let user_repo = UserRepository::new(pool.get_ref().clone());
 
-    match user_repo.get_all(params.page, params.page_size).await {
+    match user_repo.get_all(&params).await {
         Ok((users, total_count)) => {
             let total_pages = ((total_count as f64) / (params.page_size as f64)).ceil() as i32;
 
@@ -74,9 +78,19 @@ pub async fn get_all_users(
 pub async fn create_admin(
     pool: web::Data<MySqlPool>,
     req: web::Json<CreateAdminRequest>,
+    claims: web::ReqData<TokenClaims>,
 ) -> HttpResponse {
     let user_repo = UserRepository::new(pool.get_ref().clone());
 
+    let (creator_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = ensure_role_covered(&pool, &claims.role, &Role::Administrator).await {
+        return response;
+    }
+
     // Hasher le mot de passe
     let password_hash = match hash(&req.password, DEFAULT_COST) {
         Ok(h) => h,
@@ -110,17 +124,27 @@ pub async fn create_admin(
             gender,
             &password_hash,
             &req.email,
-            "Administrator", // Role = Administrator
+            Role::Administrator.as_str(),
             req.country.as_deref(),
             req.city.as_deref(),
             birth_date,
         )
         .await
     {
-        Ok(admin_id) => HttpResponse::Created().json(serde_json::json!({
-            "message": "Admin created successfully",
-            "user_id": admin_id
-        })),
+        Ok(admin_id) => {
+            // L'adresse est validée par l'administrateur qui crée le compte
+            if let Err(e) = user_repo
+                .set_email_verified(admin_id, true, creator_id)
+                .await
+            {
+                log::error!("Failed to mark admin email as verified: {:?}", e);
+            }
+
+            HttpResponse::Created().json(serde_json::json!({
+                "message": "Admin created successfully",
+                "user_id": admin_id
+            }))
+        }
         Err(e) => {
             log::error!("Failed to create admin: {:?}", e);
 
@@ -138,3 +162,181 @@ pub async fn create_admin(
         }
     }
 }
+
+pub async fn get_user_details(pool: web::Data<MySqlPool>, user_id: web::Path<u32>) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    match user_repo.find_with_stats(*user_id).await {
+        Ok(Some(user)) => HttpResponse::Ok().json(user),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "User not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to retrieve user: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve user"
+            }))
+        }
+    }
+}
+
+pub async fn update_user_role(
+    pool: web::Data<MySqlPool>,
+    user_id: web::Path<u32>,
+    req: web::Json<UpdateUserRoleRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = ensure_user_covered(&pool, &claims.role, *user_id).await {
+        return response;
+    }
+
+    if let Err(response) = ensure_role_covered(&pool, &claims.role, &req.role).await {
+        return response;
+    }
+
+    match user_repo.update_role(*user_id, &req.role, admin_id).await {
+        Ok(()) => get_user_details(pool, user_id).await,
+        Err(e) => admin_update_error(e, "Failed to update user role"),
+    }
+}
+
+pub async fn update_user_status(
+    pool: web::Data<MySqlPool>,
+    user_id: web::Path<u32>,
+    req: web::Json<UpdateUserStatusRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = ensure_user_covered(&pool, &claims.role, *user_id).await {
+        return response;
+    }
+
+    match user_repo
+        .set_active(*user_id, req.is_active, admin_id)
+        .await
+    {
+        Ok(()) => get_user_details(pool, user_id).await,
+        Err(e) => admin_update_error(e, "Failed to update user status"),
+    }
+}
+
+/// Valide (ou invalide) manuellement l'adresse email d'un utilisateur
+pub async fn update_user_email_verification(
+    pool: web::Data<MySqlPool>,
+    user_id: web::Path<u32>,
+    req: web::Json<UpdateEmailVerificationRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = ensure_user_covered(&pool, &claims.role, *user_id).await {
+        return response;
+    }
+
+    match user_repo
+        .set_email_verified(*user_id, req.verified, admin_id)
+        .await
+    {
+        Ok(()) => get_user_details(pool, user_id).await,
+        Err(e) => admin_update_error(e, "Failed to update email verification"),
+    }
+}
+
+/// Lève le verrouillage de connexion d'un compte (les verrous par IP expirent seuls)
+pub async fn unlock_user_login(
+    pool: web::Data<MySqlPool>,
+    user_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let throttle_repo = LoginThrottleRepository::new(pool.get_ref().clone());
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match throttle_repo.unlock_user(*user_id, admin_id).await {
+        Ok(()) => get_user_details(pool, user_id).await,
+        Err(e) => admin_update_error(e, "Failed to unlock user"),
+    }
+}
+
+// Un rôle ne peut être attribué que par un utilisateur qui en possède toutes les permissions
+async fn ensure_role_covered(
+    pool: &MySqlPool,
+    actor_role: &Role,
+    role: &Role,
+) -> Result<(), HttpResponse> {
+    match PermissionRepository::new(pool.clone())
+        .covers_role(actor_role, role)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Cannot manage a role with permissions you do not have"
+        }))),
+        Err(e) => {
+            log::error!("Failed to compare role permissions: {:?}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to check permissions"
+            })))
+        }
+    }
+}
+
+// Même règle pour agir sur un compte : son rôle actuel doit être couvert
+async fn ensure_user_covered(
+    pool: &MySqlPool,
+    actor_role: &Role,
+    user_id: u32,
+) -> Result<(), HttpResponse> {
+    match UserRepository::new(pool.clone()).find_by_id(user_id).await {
+        Ok(Some(user)) => ensure_role_covered(pool, actor_role, &user.role).await,
+        Ok(None) => Err(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "User not found"
+        }))),
+        Err(e) => {
+            log::error!("Failed to retrieve user: {:?}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve user"
+            })))
+        }
+    }
+}
+
+fn admin_update_error(e: sqlx::Error, message: &str) -> HttpResponse {
+    log::error!("{}: {:?}", message, e);
+
+    let error_msg = format!("{:?}", e);
+    if error_msg.contains("last administrator") {
+        HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Cannot remove the last administrator"
+        }))
+    } else if error_msg.contains("not found") {
+        HttpResponse::NotFound().json(serde_json::json!({
+            "error": "User not found"
+        }))
+    } else {
+        HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": message
+        }))
+    }
+}
@@ -0,0 +1,381 @@
+use crate::models::{
+    AddIngredientAllergyRequest, CreateAllergyRequest, SetUserAllergyRequest, TokenClaims,
+    UpdateAllergyRequest,
+};
+use crate::repositories::AllergyRepository;
+use crate::utils::auth::extract_user_info;
+use actix_web::{HttpResponse, web};
+use sqlx::MySqlPool;
+
+// =====================================================
+// CATALOGUE D'ALLERGIES
+// =====================================================
+
+/// Récupérer toutes les allergies (accessible à tous les utilisateurs authentifiés)
+pub async fn get_all_allergies(pool: web::Data<MySqlPool>) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    match repo.get_all().await {
+        Ok(allergies) => HttpResponse::Ok().json(allergies),
+        Err(e) => {
+            log::error!("Failed to retrieve allergies: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve allergies"
+            }))
+        }
+    }
+}
+
+/// Récupérer une allergie par ID avec ses ingrédients (accessible à tous)
+pub async fn get_allergy(pool: web::Data<MySqlPool>, allergy_id: web::Path<u32>) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    match repo.find_by_id(*allergy_id).await {
+        Ok(Some(allergy)) => HttpResponse::Ok().json(allergy),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Allergy not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to retrieve allergy: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve allergy"
+            }))
+        }
+    }
+}
+
+/// Créer une allergie (permission allergies.manage)
+pub async fn create_allergy(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<CreateAllergyRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo
+        .create(&req.name, req.description.as_deref(), user_id)
+        .await
+    {
+        Ok(allergy_id) => match repo.find_by_id(allergy_id).await {
+            Ok(Some(allergy)) => HttpResponse::Created().json(allergy),
+            Ok(None) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Allergy created but could not be retrieved"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve created allergy: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Allergy created but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to create allergy: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("already exists") {
+                HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "Allergy with this name already exists"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to create allergy"
+                }))
+            }
+        }
+    }
+}
+
+/// Modifier une allergie (permission allergies.manage)
+pub async fn update_allergy(
+    pool: web::Data<MySqlPool>,
+    allergy_id: web::Path<u32>,
+    req: web::Json<UpdateAllergyRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo
+        .update(*allergy_id, &req.name, req.description.as_deref(), user_id)
+        .await
+    {
+        Ok(()) => match repo.find_by_id(*allergy_id).await {
+            Ok(Some(allergy)) => HttpResponse::Ok().json(allergy),
+            Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Allergy not found"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve updated allergy: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Allergy updated but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to update allergy: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Allergy not found"
+                }))
+            } else if error_msg.contains("already exists") {
+                HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "Allergy with this name already exists"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to update allergy"
+                }))
+            }
+        }
+    }
+}
+
+/// Supprimer une allergie (permission allergies.manage)
+pub async fn delete_allergy(
+    pool: web::Data<MySqlPool>,
+    allergy_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo.delete(*allergy_id, user_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to delete allergy: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Allergy not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to delete allergy"
+                }))
+            }
+        }
+    }
+}
+
+// =====================================================
+// ALLERGÈNES DES INGRÉDIENTS
+// =====================================================
+
+/// Récupérer les ingrédients liés à une allergie
+pub async fn get_allergy_ingredients(
+    pool: web::Data<MySqlPool>,
+    allergy_id: web::Path<u32>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    match repo.get_ingredients(*allergy_id).await {
+        Ok(ingredients) => HttpResponse::Ok().json(ingredients),
+        Err(e) => {
+            log::error!("Failed to retrieve allergy ingredients: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve ingredients"
+            }))
+        }
+    }
+}
+
+/// Lier un ingrédient à une allergie (permission allergies.manage)
+pub async fn add_ingredient_allergy(
+    pool: web::Data<MySqlPool>,
+    allergy_id: web::Path<u32>,
+    req: web::Json<AddIngredientAllergyRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo
+        .add_ingredient(*allergy_id, req.ingredient_id, user_id)
+        .await
+    {
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Ingredient linked to allergy successfully"
+        })),
+        Err(e) => {
+            log::error!("Failed to link ingredient to allergy: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("Allergy not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Allergy not found"
+                }))
+            } else if error_msg.contains("Ingredient not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Ingredient not found"
+                }))
+            } else if error_msg.contains("already linked") {
+                HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "Ingredient already linked to this allergy"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to link ingredient to allergy"
+                }))
+            }
+        }
+    }
+}
+
+/// Retirer le lien entre un ingrédient et une allergie (permission allergies.manage)
+pub async fn remove_ingredient_allergy(
+    pool: web::Data<MySqlPool>,
+    path: web::Path<(u32, u32)>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let (allergy_id, ingredient_id) = path.into_inner();
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo
+        .remove_ingredient(allergy_id, ingredient_id, user_id)
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to unlink ingredient from allergy: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Ingredient allergy link not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to unlink ingredient from allergy"
+                }))
+            }
+        }
+    }
+}
+
+// =====================================================
+// ALLERGIES DE L'UTILISATEUR CONNECTÉ
+// =====================================================
+
+/// Récupérer les allergies déclarées par l'utilisateur connecté
+pub async fn get_my_allergies(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo.get_user_allergies(user_id).await {
+        Ok(allergies) => HttpResponse::Ok().json(allergies),
+        Err(e) => {
+            log::error!("Failed to retrieve user allergies: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve allergies"
+            }))
+        }
+    }
+}
+
+/// Déclarer une allergie ou modifier sa sévérité
+pub async fn set_my_allergy(
+    pool: web::Data<MySqlPool>,
+    allergy_id: web::Path<u32>,
+    req: web::Json<SetUserAllergyRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo
+        .set_user_allergy(user_id, *allergy_id, &req.severity)
+        .await
+    {
+        Ok(()) => match repo.get_user_allergies(user_id).await {
+            Ok(allergies) => HttpResponse::Ok().json(allergies),
+            Err(e) => {
+                log::error!("Failed to retrieve user allergies: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Allergy saved but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to set user allergy: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Allergy not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to save allergy"
+                }))
+            }
+        }
+    }
+}
+
+/// Retirer une allergie déclarée par l'utilisateur connecté
+pub async fn remove_my_allergy(
+    pool: web::Data<MySqlPool>,
+    allergy_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let repo = AllergyRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match repo.remove_user_allergy(user_id, *allergy_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to remove user allergy: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "User allergy not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to remove allergy"
+                }))
+            }
+        }
+    }
+}
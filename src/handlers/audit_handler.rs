@@ -0,0 +1,201 @@
+use crate::models::{
+    AuditFilter, PaginatedResponse, PaginationInfo, PaginationParams, TokenClaims,
+};
+use crate::repositories::AuditRepository;
+use crate::utils::auth::extract_user_info;
+use actix_web::{HttpResponse, web};
+use serde::Serialize;
+use sqlx::MySqlPool;
+
+pub async fn get_audit_deletions(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<AuditFilter>,
+) -> HttpResponse {
+    let audit_repo = AuditRepository::new(pool.get_ref().clone());
+
+    if let Err(response) = validate_audit_query(&query, &filter) {
+        return response;
+    }
+
+    let result = audit_repo
+        .get_deletions(&filter, query.page, query.page_size)
+        .await;
+
+    paginated_response(result, &query, "Failed to retrieve deletions")
+}
+
+pub async fn get_user_history(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<AuditFilter>,
+) -> HttpResponse {
+    let audit_repo = AuditRepository::new(pool.get_ref().clone());
+
+    if let Err(response) = validate_audit_query(&query, &filter) {
+        return response;
+    }
+
+    let result = audit_repo
+        .get_user_history(&filter, query.page, query.page_size)
+        .await;
+
+    paginated_response(result, &query, "Failed to retrieve user history")
+}
+
+pub async fn get_recipe_history(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<AuditFilter>,
+) -> HttpResponse {
+    let audit_repo = AuditRepository::new(pool.get_ref().clone());
+
+    if let Err(response) = validate_audit_query(&query, &filter) {
+        return response;
+    }
+
+    let result = audit_repo
+        .get_recipe_history(&filter, query.page, query.page_size)
+        .await;
+
+    paginated_response(result, &query, "Failed to retrieve recipe history")
+}
+
+pub async fn get_ingredient_history(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<AuditFilter>,
+) -> HttpResponse {
+    let audit_repo = AuditRepository::new(pool.get_ref().clone());
+
+    if let Err(response) = validate_audit_query(&query, &filter) {
+        return response;
+    }
+
+    let result = audit_repo
+        .get_ingredient_history(&filter, query.page, query.page_size)
+        .await;
+
+    paginated_response(result, &query, "Failed to retrieve ingredient history")
+}
+
+/// Requêtes effectuées par usurpation (entity_id = utilisateur usurpé, user_id = administrateur)
+pub async fn get_audit_impersonations(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<AuditFilter>,
+) -> HttpResponse {
+    let audit_repo = AuditRepository::new(pool.get_ref().clone());
+
+    if let Err(response) = validate_audit_query(&query, &filter) {
+        return response;
+    }
+
+    let result = audit_repo
+        .get_impersonations(&filter, query.page, query.page_size)
+        .await;
+
+    paginated_response(result, &query, "Failed to retrieve impersonations")
+}
+
+/// Recrée une recette (avec ses ingrédients et étapes archivés) ou un ingrédient supprimé
+pub async fn restore_deletion(
+    pool: web::Data<MySqlPool>,
+    audit_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let audit_repo = AuditRepository::new(pool.get_ref().clone());
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match audit_repo.restore(*audit_id, admin_id).await {
+        Ok((table_name, record_id)) => HttpResponse::Created().json(serde_json::json!({
+            "message": "Record restored successfully",
+            "table_name": table_name,
+            "record_id": record_id
+        })),
+        Err(e) => {
+            log::error!("Failed to restore deletion: {:?}", e);
+
+            // Les refus métier de la procédure sont renvoyés tels quels
+            let error_msg = match &e {
+                sqlx::Error::Protocol(msg) => msg.as_str(),
+                _ => "",
+            };
+
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Audit entry not found"
+                }))
+            } else if error_msg.contains("already exists") {
+                HttpResponse::Conflict().json(serde_json::json!({
+                    "error": error_msg
+                }))
+            } else if error_msg.contains("can be restored")
+                || error_msg.contains("no longer exists")
+            {
+                HttpResponse::UnprocessableEntity().json(serde_json::json!({
+                    "error": error_msg
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to restore record"
+                }))
+            }
+        }
+    }
+}
+
+fn validate_audit_query(
+    query: &PaginationParams,
+    filter: &AuditFilter,
+) -> Result<(), HttpResponse> {
+    if query.page < 1 || query.page_size < 1 || query.page_size > 100 {
+        return Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "page must be at least 1 and page_size between 1 and 100"
+        })));
+    }
+
+    if let (Some(from), Some(to)) = (filter.from, filter.to)
+        && from > to
+    {
+        return Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "from must be before to"
+        })));
+    }
+
+    Ok(())
+}
+
+fn paginated_response<T: Serialize>(
+    result: Result<(Vec<T>, i64), sqlx::Error>,
+    query: &PaginationParams,
+    error_message: &str,
+) -> HttpResponse {
+    match result {
+        Ok((data, total_count)) => {
+            let total_pages = ((total_count as f64) / (query.page_size as f64)).ceil() as i32;
+
+            HttpResponse::Ok().json(PaginatedResponse {
+                data,
+                pagination: PaginationInfo {
+                    current_page: query.page,
+                    page_size: query.page_size,
+                    total_count,
+                    total_pages,
+                    has_next: query.page < total_pages,
+                    has_previous: query.page > 1,
+                },
+            })
+        }
+        Err(e) => {
+            log::error!("{}: {:?}", error_message, e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": error_message
+            }))
+        }
+    }
+}
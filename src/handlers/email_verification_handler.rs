@@ -0,0 +1,118 @@
+use crate::mailer::{Mailer, build_message, send_in_background};
+use crate::models::{TokenClaims, User, VerifyEmailQuery};
+use crate::repositories::{EmailVerificationRepository, UserRepository};
+use crate::utils::auth::{email_verification_expiration, extract_user_info, generate_token};
+use actix_web::{HttpResponse, web};
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+/// Crée un jeton de vérification et envoie le lien à l'utilisateur
+pub async fn send_verification_email(
+    pool: &MySqlPool,
+    mailer: Arc<dyn Mailer>,
+    user: &User,
+) -> Result<(), sqlx::Error> {
+    let verification_repo = EmailVerificationRepository::new(pool.clone());
+
+    let token = generate_token();
+    let expiration = email_verification_expiration();
+
+    verification_repo
+        .create(user.user_id, &token, expiration)
+        .await?;
+
+    let verify_url = std::env::var("EMAIL_VERIFICATION_URL")
+        .unwrap_or_else(|_| "http://localhost:8080/api/auth/verify".to_string());
+    let body = format!(
+        "Hello {},\n\n\
+         Welcome to Food Advisor! Please confirm your email address\n\
+         by opening the link below (valid for {} hours):\n\n\
+         {}?token={}\n\n\
+         Until then, publishing recipes and uploading images are disabled.\n",
+        user.first_name,
+        expiration / 3600,
+        verify_url,
+        token
+    );
+
+    let message = build_message(&user.email, "Verify your Food Advisor email address", body)
+        .map_err(|e| sqlx::Error::Protocol(format!("Failed to build email: {}", e)))?;
+
+    send_in_background(mailer, message);
+
+    Ok(())
+}
+
+pub async fn verify_email(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<VerifyEmailQuery>,
+) -> HttpResponse {
+    let verification_repo = EmailVerificationRepository::new(pool.get_ref().clone());
+
+    match verification_repo.verify(query.token.trim()).await {
+        Ok(_) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Email verified successfully"
+        })),
+        Err(e) => {
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("Invalid or expired") {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "Invalid or expired verification token"
+                }));
+            }
+
+            log::error!("Failed to verify email: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to verify email"
+            }))
+        }
+    }
+}
+
+/// Renvoie un lien de vérification à l'utilisateur connecté (l'ancien lien est invalidé)
+pub async fn resend_verification_email(
+    pool: web::Data<MySqlPool>,
+    mailer: web::Data<dyn Mailer>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    let user = match user_repo.find_by_id(user_id).await {
+        Ok(Some(user)) if user.is_active => user,
+        Ok(_) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "User not found"
+            }));
+        }
+        Err(e) => {
+            log::error!("Failed to retrieve user: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve user"
+            }));
+        }
+    };
+
+    match send_verification_email(&pool, mailer.into_inner(), &user).await {
+        Ok(()) => HttpResponse::Accepted().json(serde_json::json!({
+            "message": "Verification email sent"
+        })),
+        Err(e) => {
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("already verified") {
+                return HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "Email already verified"
+                }));
+            }
+
+            log::error!("Failed to send verification email: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to send verification email"
+            }))
+        }
+    }
+}
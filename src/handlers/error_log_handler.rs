@@ -0,0 +1,142 @@
+use crate::models::{
+    ErrorLogFilter, PaginatedResponse, PaginationInfo, PaginationParams, ResolveErrorRequest,
+    TokenClaims,
+};
+use crate::repositories::ErrorLogRepository;
+use crate::utils::auth::extract_user_info;
+use actix_web::{HttpResponse, web};
+use sqlx::MySqlPool;
+
+pub async fn get_error_logs(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<ErrorLogFilter>,
+) -> HttpResponse {
+    let error_log_repo = ErrorLogRepository::new(pool.get_ref().clone());
+
+    if query.page < 1 || query.page_size < 1 || query.page_size > 100 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "page must be at least 1 and page_size between 1 and 100"
+        }));
+    }
+
+    if let (Some(from), Some(to)) = (filter.from, filter.to)
+        && from > to
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "from must be before to"
+        }));
+    }
+
+    match error_log_repo
+        .get_all(&filter, query.page, query.page_size)
+        .await
+    {
+        Ok((errors, total_count)) => {
+            let total_pages = ((total_count as f64) / (query.page_size as f64)).ceil() as i32;
+
+            HttpResponse::Ok().json(PaginatedResponse {
+                data: errors,
+                pagination: PaginationInfo {
+                    current_page: query.page,
+                    page_size: query.page_size,
+                    total_count,
+                    total_pages,
+                    has_next: query.page < total_pages,
+                    has_previous: query.page > 1,
+                },
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to retrieve error logs: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve error logs"
+            }))
+        }
+    }
+}
+
+/// Résumé par jour, type et procédure des erreurs des 7 derniers jours
+pub async fn get_error_summary(pool: web::Data<MySqlPool>) -> HttpResponse {
+    let error_log_repo = ErrorLogRepository::new(pool.get_ref().clone());
+
+    match error_log_repo.get_summary().await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(e) => {
+            log::error!("Failed to retrieve error summary: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve error summary"
+            }))
+        }
+    }
+}
+
+pub async fn get_error_log(pool: web::Data<MySqlPool>, error_id: web::Path<u32>) -> HttpResponse {
+    let error_log_repo = ErrorLogRepository::new(pool.get_ref().clone());
+
+    match error_log_repo.find_by_id(*error_id).await {
+        Ok(Some(error_log)) => HttpResponse::Ok().json(error_log),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Error log not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to retrieve error log: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve error log"
+            }))
+        }
+    }
+}
+
+pub async fn resolve_error_log(
+    pool: web::Data<MySqlPool>,
+    error_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+    req: web::Json<ResolveErrorRequest>,
+) -> HttpResponse {
+    let error_log_repo = ErrorLogRepository::new(pool.get_ref().clone());
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    // Des notes vides n'apportent rien : on les stocke comme absentes
+    let notes = req
+        .resolution_notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+
+    if let Err(e) = error_log_repo.resolve(*error_id, admin_id, notes).await {
+        log::error!("Failed to resolve error log: {:?}", e);
+
+        let error_msg = format!("{:?}", e);
+        return if error_msg.contains("not found") {
+            HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Error log not found"
+            }))
+        } else if error_msg.contains("already resolved") {
+            HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Error log already resolved"
+            }))
+        } else {
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to resolve error log"
+            }))
+        };
+    }
+
+    match error_log_repo.find_by_id(*error_id).await {
+        Ok(Some(error_log)) => HttpResponse::Ok().json(error_log),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Error log not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to retrieve error log: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve error log"
+            }))
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::models::TokenClaims;
-use crate::{repositories::ImageRepository, utils::auth::extract_user_info};
+use crate::{
+    repositories::ImageRepository,
+    utils::auth::{extract_user_info, require_verified_email},
+};
 use actix_multipart::Multipart;
 use actix_web::{HttpResponse, web};
 use futures_util::stream::StreamExt as _;
@@ -7,7 +10,7 @@ use sqlx::MySqlPool;
 
 const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024; // 10 MB
 const ALLOWED_MIME_TYPES: &[&str] = &["image/jpeg", "image/png", "image/gif", "image/webp"];
-/// Ajouter une image à une recette (auteur ou administrateur)
+/// Ajouter une image à une recette (auteur ou permission recipes.moderate)
 pub async fn add_recipe_image(
     pool: web::Data<MySqlPool>,
     path: web::Path<u32>,
@@ -22,6 +25,10 @@ pub async fn add_recipe_image(
         Err(response) => return response,
     };
 
+    if let Err(response) = require_verified_email(&pool, &claims).await {
+        return response;
+    }
+
     let mut image_data: Vec<u8> = Vec::new();
     let mut image_name = String::new();
     let mut image_type = String::new();
@@ -177,7 +184,7 @@ pub async fn add_recipe_image(
     }
 }
 
-/// Ajouter une image à un ingrédient (seuls les administrateurs)
+/// Ajouter une image à un ingrédient (permission ingredients.edit)
 pub async fn add_ingredient_image(
     pool: web::Data<MySqlPool>,
     path: web::Path<u32>,
@@ -192,6 +199,10 @@ pub async fn add_ingredient_image(
         Err(response) => return response,
     };
 
+    if let Err(response) = require_verified_email(&pool, &claims).await {
+        return response;
+    }
+
     let mut image_data: Vec<u8> = Vec::new();
     let mut image_name = String::new();
     let mut image_type = String::new();
@@ -336,7 +347,7 @@ pub async fn add_ingredient_image(
             let error_msg = format!("{:?}", e);
             if error_msg.contains("Only administrators") || error_msg.contains("not authorized") {
                 HttpResponse::Forbidden().json(serde_json::json!({
-                    "error": "Only administrators can add ingredient images"
+                    "error": "You are not authorized to add ingredient images"
                 }))
             } else if error_msg.contains("not found") {
                 HttpResponse::NotFound().json(serde_json::json!({
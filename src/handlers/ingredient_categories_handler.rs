@@ -55,7 +55,7 @@ pub async fn get_category(pool: web::Data<MySqlPool>, category_id: web::Path<i32
     }
 }
 
-/// Créer une catégorie (réservé aux administrateurs)
+/// Créer une catégorie (permission categories.manage)
 pub async fn create_category(
     pool: web::Data<MySqlPool>,
     req: web::Json<CreateCategoryRequest>,
@@ -101,7 +101,7 @@ pub async fn create_category(
     }
 }
 
-/// Modifier une catégorie (réservé aux administrateurs)
+/// Modifier une catégorie (permission categories.manage)
 pub async fn update_category(
     pool: web::Data<MySqlPool>,
     category_id: web::Path<i32>,
@@ -152,7 +152,7 @@ pub async fn update_category(
     }
 }
 
-/// Supprimer une catégorie (réservé aux administrateurs)
+/// Supprimer une catégorie (permission categories.manage)
 pub async fn delete_category(
     pool: web::Data<MySqlPool>,
     category_id: web::Path<i32>,
@@ -188,7 +188,7 @@ pub async fn delete_category(
 // GESTION DES ASSIGNATIONS
 // =====================================================
 
-/// Ajouter un ingrédient à une catégorie (réservé aux administrateurs)
+/// Ajouter un ingrédient à une catégorie (permission categories.manage)
 pub async fn add_ingredient_to_category(
     pool: web::Data<MySqlPool>,
     category_id: web::Path<i32>,
@@ -234,7 +234,7 @@ pub async fn add_ingredient_to_category(
     }
 }
 
-/// Supprimer un ingrédient d'une catégorie (réservé aux administrateurs)
+/// Supprimer un ingrédient d'une catégorie (permission categories.manage)
 pub async fn remove_ingredient_from_category(
     pool: web::Data<MySqlPool>,
     path: web::Path<(i32, i32)>,
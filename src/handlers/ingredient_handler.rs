@@ -132,7 +132,7 @@ pub async fn create_ingredient(
     }
 }
 
-// Modifier un ingrédient (réservé aux administrateurs)
+// Modifier un ingrédient (permission ingredients.edit)
 pub async fn update_ingredient(
     pool: web::Data<MySqlPool>,
     ingredient_id: web::Path<i32>,
@@ -204,7 +204,7 @@ pub async fn update_ingredient(
     }
 }
 
-// Supprimer un ingrédient (réservé aux administrateurs)
+// Supprimer un ingrédient (permission ingredients.edit)
 pub async fn delete_ingredient(
     pool: web::Data<MySqlPool>,
     ingredient_id: web::Path<i32>,
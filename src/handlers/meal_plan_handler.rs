@@ -0,0 +1,435 @@
+use crate::handlers::shopping_list_handler::save_shopping_list;
+use crate::models::{
+    CreateMealPlanRequest, GenerateMealPlanRequest, MealPlanQuery, MealPlanShoppingListRequest,
+    MealPlanWeek, MealSlot, ShoppingListRecipeRequest, TokenClaims, UpdateMealPlanRequest,
+};
+use crate::recommendations::meal_plan::{MEAL_PLAN_DAYS, MealPlanTarget};
+use crate::recommendations::{calories_per_serving, generate_meal_plan, group_by_day};
+use crate::repositories::{MealPlanRepository, RecipeRepository};
+use crate::utils::auth::extract_user_info;
+use actix_web::{HttpResponse, web};
+use chrono::{Days, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::MySqlPool;
+use std::collections::{BTreeMap, HashSet};
+
+// Période maximale consultable en une requête
+const MAX_MEAL_PLAN_RANGE_DAYS: i64 = 31;
+
+// =====================================================
+// HANDLERS - Planning de repas
+// =====================================================
+
+/// Récupérer les repas planifiés sur une période (par défaut les 7 prochains jours)
+pub async fn get_meal_plans(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<MealPlanQuery>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let meal_plan_repo = MealPlanRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    let start_date = query.start_date.unwrap_or_else(|| Utc::now().date_naive());
+    let end_date = query.end_date.unwrap_or_else(|| week_end(start_date));
+
+    if let Err(response) = validate_range(start_date, end_date) {
+        return response;
+    }
+
+    match meal_plan_repo
+        .get_user_meal_plans(user_id, start_date, end_date)
+        .await
+    {
+        Ok(meal_plans) => HttpResponse::Ok().json(meal_plans),
+        Err(e) => {
+            log::error!("Failed to retrieve meal plans: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve meal plans"
+            }))
+        }
+    }
+}
+
+/// Récupérer un repas planifié
+pub async fn get_meal_plan(
+    pool: web::Data<MySqlPool>,
+    meal_plan_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let meal_plan_repo = MealPlanRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match meal_plan_repo.find_by_id(*meal_plan_id, user_id).await {
+        Ok(Some(meal_plan)) => HttpResponse::Ok().json(meal_plan),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Meal plan not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to retrieve meal plan: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve meal plan"
+            }))
+        }
+    }
+}
+
+/// Planifier un repas
+pub async fn create_meal_plan(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<CreateMealPlanRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let meal_plan_repo = MealPlanRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = validate_servings(Some(req.servings)) {
+        return response;
+    }
+
+    match meal_plan_repo
+        .create(
+            user_id,
+            req.plan_date,
+            &req.meal_slot,
+            req.recipe_id,
+            req.servings,
+        )
+        .await
+    {
+        Ok(meal_plan_id) => match meal_plan_repo.find_by_id(meal_plan_id, user_id).await {
+            Ok(Some(meal_plan)) => HttpResponse::Created().json(meal_plan),
+            Ok(None) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Meal plan created but could not be retrieved"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve created meal plan: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Meal plan created but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to create meal plan: {:?}", e);
+            meal_plan_error_response(&e, "Failed to create meal plan")
+        }
+    }
+}
+
+/// Modifier un repas planifié
+pub async fn update_meal_plan(
+    pool: web::Data<MySqlPool>,
+    meal_plan_id: web::Path<u32>,
+    req: web::Json<UpdateMealPlanRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let meal_plan_repo = MealPlanRepository::new(pool.get_ref().clone());
+    let meal_plan_id = meal_plan_id.into_inner();
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = validate_servings(req.servings) {
+        return response;
+    }
+
+    match meal_plan_repo
+        .update(
+            meal_plan_id,
+            user_id,
+            req.plan_date,
+            req.meal_slot.as_ref(),
+            req.recipe_id,
+            req.servings,
+        )
+        .await
+    {
+        Ok(()) => match meal_plan_repo.find_by_id(meal_plan_id, user_id).await {
+            Ok(Some(meal_plan)) => HttpResponse::Ok().json(meal_plan),
+            Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Meal plan not found"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve updated meal plan: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Meal plan updated but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to update meal plan: {:?}", e);
+            meal_plan_error_response(&e, "Failed to update meal plan")
+        }
+    }
+}
+
+/// Supprimer un repas planifié
+pub async fn delete_meal_plan(
+    pool: web::Data<MySqlPool>,
+    meal_plan_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let meal_plan_repo = MealPlanRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match meal_plan_repo.delete(*meal_plan_id, user_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to delete meal plan: {:?}", e);
+            meal_plan_error_response(&e, "Failed to delete meal plan")
+        }
+    }
+}
+
+/// Générer une semaine de repas compatibles avec les allergies et préférences de l'utilisateur
+pub async fn generate_week_meal_plan(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<GenerateMealPlanRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let meal_plan_repo = MealPlanRepository::new(pool.get_ref().clone());
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = validate_servings(Some(req.servings)) {
+        return response;
+    }
+
+    if req.daily_calories <= Decimal::ZERO || req.daily_calories > Decimal::from(10000) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Daily calories must be between 1 and 10000"
+        }));
+    }
+
+    let slots = req
+        .slots
+        .clone()
+        .unwrap_or_else(|| vec![MealSlot::Breakfast, MealSlot::Lunch, MealSlot::Dinner]);
+
+    if slots.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "At least one meal slot is required"
+        }));
+    }
+
+    let start_date = req.start_date.unwrap_or_else(|| Utc::now().date_naive());
+    let end_date = week_end(start_date);
+
+    let recipes = match recipe_repo.get_published_with_ingredients().await {
+        Ok(recipes) => recipes,
+        Err(e) => {
+            log::error!("Failed to retrieve recipes: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipes"
+            }));
+        }
+    };
+
+    // Recettes écartées par les allergies et les préférences de l'utilisateur
+    let excluded: HashSet<u32> = match recipe_repo.get_exclusions_for_user(user_id).await {
+        Ok(exclusions) => exclusions.into_iter().map(|e| e.recipe_id).collect(),
+        Err(e) => {
+            log::error!("Failed to retrieve recipe exclusions: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipes"
+            }));
+        }
+    };
+
+    let candidates: Vec<u32> = recipes
+        .iter()
+        .map(|r| r.recipe.recipe_id)
+        .filter(|id| !excluded.contains(id))
+        .collect();
+    let calories = calories_per_serving(&recipes);
+
+    let existing = match meal_plan_repo
+        .get_user_meal_plans(user_id, start_date, end_date)
+        .await
+    {
+        Ok(meal_plans) => meal_plans,
+        Err(e) => {
+            log::error!("Failed to retrieve meal plans: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve meal plans"
+            }));
+        }
+    };
+
+    // Les repas des créneaux non demandés restent en place dans tous les cas
+    let kept: Vec<_> = existing
+        .into_iter()
+        .filter(|m| !req.replace_existing || !slots.contains(&m.meal_slot))
+        .collect();
+
+    let target = MealPlanTarget {
+        start_date,
+        slots,
+        daily_calories: req.daily_calories,
+        servings: req.servings,
+    };
+    let planned = generate_meal_plan(&candidates, &calories, &kept, &target);
+
+    if planned.is_empty() && candidates.is_empty() {
+        return HttpResponse::UnprocessableEntity().json(serde_json::json!({
+            "error": "No compatible recipes available to build a meal plan"
+        }));
+    }
+
+    if let Err(e) = meal_plan_repo.save_all(user_id, &planned).await {
+        log::error!("Failed to save generated meal plan: {:?}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to save meal plan"
+        }));
+    }
+
+    match meal_plan_repo
+        .get_user_meal_plans(user_id, start_date, end_date)
+        .await
+    {
+        Ok(meal_plans) => HttpResponse::Created().json(MealPlanWeek {
+            start_date,
+            end_date,
+            daily_calories: req.daily_calories,
+            days: group_by_day(meal_plans, &calories),
+        }),
+        Err(e) => {
+            log::error!("Failed to retrieve generated meal plan: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Meal plan generated but failed to retrieve"
+            }))
+        }
+    }
+}
+
+/// Générer la liste de courses des repas planifiés sur une période
+pub async fn create_meal_plan_shopping_list(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<MealPlanShoppingListRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let meal_plan_repo = MealPlanRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = validate_range(req.start_date, req.end_date) {
+        return response;
+    }
+
+    let meal_plans = match meal_plan_repo
+        .get_user_meal_plans(user_id, req.start_date, req.end_date)
+        .await
+    {
+        Ok(meal_plans) => meal_plans,
+        Err(e) => {
+            log::error!("Failed to retrieve meal plans: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve meal plans"
+            }));
+        }
+    };
+
+    if meal_plans.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "No meals planned in this period"
+        }));
+    }
+
+    // Une recette servie plusieurs fois cumule ses portions
+    let mut servings: BTreeMap<u32, u32> = BTreeMap::new();
+    for meal in &meal_plans {
+        *servings.entry(meal.recipe_id).or_default() += meal.servings;
+    }
+
+    let selections: Vec<ShoppingListRecipeRequest> = servings
+        .into_iter()
+        .map(|(recipe_id, servings)| ShoppingListRecipeRequest {
+            recipe_id,
+            servings: Some(servings),
+        })
+        .collect();
+
+    let name = match req.name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => format!("Meal plan {} - {}", req.start_date, req.end_date),
+    };
+
+    save_shopping_list(pool.get_ref(), user_id, &name, &selections).await
+}
+
+fn week_end(start_date: NaiveDate) -> NaiveDate {
+    start_date
+        .checked_add_days(Days::new(MEAL_PLAN_DAYS - 1))
+        .unwrap_or(start_date)
+}
+
+fn validate_range(start_date: NaiveDate, end_date: NaiveDate) -> Result<(), HttpResponse> {
+    if end_date < start_date {
+        return Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "end_date must not be before start_date"
+        })));
+    }
+
+    if (end_date - start_date).num_days() >= MAX_MEAL_PLAN_RANGE_DAYS {
+        return Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("The period cannot exceed {} days", MAX_MEAL_PLAN_RANGE_DAYS)
+        })));
+    }
+
+    Ok(())
+}
+
+fn validate_servings(servings: Option<u32>) -> Result<(), HttpResponse> {
+    match servings {
+        Some(s) if s == 0 || s > 100 => Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Servings must be between 1 and 100"
+        }))),
+        _ => Ok(()),
+    }
+}
+
+fn meal_plan_error_response(e: &sqlx::Error, default_message: &str) -> HttpResponse {
+    let error_msg = format!("{:?}", e);
+
+    if error_msg.contains("Recipe not found") {
+        HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Recipe not found"
+        }))
+    } else if error_msg.contains("not found") {
+        HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Meal plan not found"
+        }))
+    } else if error_msg.contains("already planned") {
+        HttpResponse::Conflict().json(serde_json::json!({
+            "error": "A meal is already planned for this slot"
+        }))
+    } else {
+        HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": default_message
+        }))
+    }
+}
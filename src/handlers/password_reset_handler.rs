@@ -0,0 +1,120 @@
+use crate::mailer::{Mailer, build_message, send_in_background};
+use crate::models::{ForgotPasswordRequest, ResetPasswordRequest};
+use crate::repositories::{PasswordResetRepository, UserRepository};
+use crate::utils::auth::{client_ip, generate_token, password_reset_expiration};
+use actix_web::{HttpRequest, HttpResponse, web};
+use bcrypt::{DEFAULT_COST, hash};
+use sqlx::MySqlPool;
+
+/// Envoie un lien de réinitialisation ; la réponse est identique que le compte existe ou non
+pub async fn forgot_password(
+    pool: web::Data<MySqlPool>,
+    mailer: web::Data<dyn Mailer>,
+    http_req: HttpRequest,
+    req: web::Json<ForgotPasswordRequest>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+    let reset_repo = PasswordResetRepository::new(pool.get_ref().clone());
+
+    let accepted = HttpResponse::Accepted().json(serde_json::json!({
+        "message": "If an account exists for this email, a reset link has been sent"
+    }));
+
+    let user = match user_repo.find_by_email(req.email.trim()).await {
+        Ok(Some(user)) if user.is_active => user,
+        Ok(_) => return accepted,
+        Err(e) => {
+            log::error!("Database error querying users: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            }));
+        }
+    };
+
+    let token = generate_token();
+    let expiration = password_reset_expiration();
+    let ip_address = client_ip(&http_req);
+
+    if let Err(e) = reset_repo
+        .create(user.user_id, &token, expiration, ip_address.as_deref())
+        .await
+    {
+        log::error!("Failed to create password reset token: {:?}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to request password reset"
+        }));
+    }
+
+    let reset_url = std::env::var("PASSWORD_RESET_URL")
+        .unwrap_or_else(|_| "http://localhost:8080/reset-password".to_string());
+    let body = format!(
+        "Hello {},\n\n\
+         A password reset was requested for your Food Advisor account.\n\
+         Use the link below to choose a new password (valid for {} minutes):\n\n\
+         {}?token={}\n\n\
+         If you did not request this, you can ignore this email.\n",
+        user.first_name,
+        expiration / 60,
+        reset_url,
+        token
+    );
+
+    let message = match build_message(&user.email, "Reset your Food Advisor password", body) {
+        Ok(message) => message,
+        Err(e) => {
+            log::error!("Failed to build password reset email: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to request password reset"
+            }));
+        }
+    };
+
+    // L'envoi se fait en arrière-plan : le temps de réponse ne révèle pas l'existence du compte
+    send_in_background(mailer.into_inner(), message);
+
+    accepted
+}
+
+/// Remplace le mot de passe à partir d'un jeton reçu par email (à usage unique)
+pub async fn reset_password(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<ResetPasswordRequest>,
+) -> HttpResponse {
+    let reset_repo = PasswordResetRepository::new(pool.get_ref().clone());
+
+    if req.new_password.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "new_password cannot be empty"
+        }));
+    }
+
+    let password_hash = match hash(&req.new_password, DEFAULT_COST) {
+        Ok(h) => h,
+        Err(e) => {
+            log::error!("Failed to hash password: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to hash password"
+            }));
+        }
+    };
+
+    match reset_repo
+        .reset_password(req.token.trim(), &password_hash)
+        .await
+    {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("Invalid or expired") {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "Invalid or expired reset token"
+                }));
+            }
+
+            log::error!("Failed to reset password: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to reset password"
+            }))
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::models::{Role, TokenClaims};
+use crate::repositories::PermissionRepository;
+use crate::utils::auth::extract_user_info;
+use actix_web::{HttpResponse, web};
+use sqlx::MySqlPool;
+
+/// Catalogue des permissions disponibles
+pub async fn get_permissions(pool: web::Data<MySqlPool>) -> HttpResponse {
+    let permission_repo = PermissionRepository::new(pool.get_ref().clone());
+
+    match permission_repo.get_all().await {
+        Ok(permissions) => HttpResponse::Ok().json(permissions),
+        Err(e) => {
+            log::error!("Failed to retrieve permissions: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve permissions"
+            }))
+        }
+    }
+}
+
+/// Permissions de tous les rôles
+pub async fn get_all_role_permissions(pool: web::Data<MySqlPool>) -> HttpResponse {
+    role_permissions_response(&pool, None).await
+}
+
+/// Permissions d'un rôle
+pub async fn get_role_permissions(
+    pool: web::Data<MySqlPool>,
+    role: web::Path<Role>,
+) -> HttpResponse {
+    role_permissions_response(&pool, Some(&role)).await
+}
+
+/// Accorde une permission à un rôle (effet immédiat pour tous ses utilisateurs)
+pub async fn grant_role_permission(
+    pool: web::Data<MySqlPool>,
+    path: web::Path<(Role, String)>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let permission_repo = PermissionRepository::new(pool.get_ref().clone());
+    let (role, permission_code) = path.into_inner();
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match permission_repo
+        .grant(&role, &permission_code, admin_id)
+        .await
+    {
+        Ok(()) => role_permissions_response(&pool, Some(&role)).await,
+        Err(e) => mapping_update_error(e, "Failed to grant permission"),
+    }
+}
+
+/// Retire une permission à un rôle
+pub async fn revoke_role_permission(
+    pool: web::Data<MySqlPool>,
+    path: web::Path<(Role, String)>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let permission_repo = PermissionRepository::new(pool.get_ref().clone());
+    let (role, permission_code) = path.into_inner();
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match permission_repo
+        .revoke(&role, &permission_code, admin_id)
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => mapping_update_error(e, "Failed to revoke permission"),
+    }
+}
+
+async fn role_permissions_response(pool: &MySqlPool, role: Option<&Role>) -> HttpResponse {
+    let permission_repo = PermissionRepository::new(pool.clone());
+
+    match permission_repo.get_role_permissions(role).await {
+        Ok(permissions) => HttpResponse::Ok().json(permissions),
+        Err(e) => {
+            log::error!("Failed to retrieve role permissions: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve role permissions"
+            }))
+        }
+    }
+}
+
+// Traduit les erreurs des procédures de mapping rôle → permission
+fn mapping_update_error(e: sqlx::Error, fallback: &str) -> HttpResponse {
+    let error_msg = format!("{:?}", e);
+
+    if error_msg.contains("Permission not found") {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Permission not found"
+        }));
+    }
+    if error_msg.contains("already granted") {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Permission already granted to this role"
+        }));
+    }
+    if error_msg.contains("not granted") {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Permission not granted to this role"
+        }));
+    }
+    if error_msg.contains("Cannot revoke") {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Cannot revoke permissions.manage from Administrator"
+        }));
+    }
+
+    log::error!("{}: {:?}", fallback, e);
+    HttpResponse::InternalServerError().json(serde_json::json!({
+        "error": fallback
+    }))
+}
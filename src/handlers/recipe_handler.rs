@@ -1,11 +1,17 @@
 use crate::models::{
-    AddRecipeIngredientRequest, AddRecipeStepRequest, CompleteRecipeRequest, CreateRecipeRequest,
-    PaginatedResponse, PaginationInfo, PaginationParams, TokenClaims, UpdateRecipeRequest,
+    AddRecipeIngredientRequest, AddRecipeStepRequest, CompleteRecipeRequest, CostQuery,
+    CreateRecipeRequest, NutritionQuery, PaginatedResponse, PaginationInfo, PaginationParams,
+    PopularRecipesQuery, Recipe, RecipeFeedResponse, RecipeListFilter, RecipeQuery,
+    RecipeSearchQuery, ScaledRecipe, TokenClaims, TrendingRecipesQuery, UpdateRecipeRequest,
     UpdateRecipeStepRequest,
 };
 use crate::repositories::RecipeRepository;
-use crate::utils::auth::extract_user_info;
+use crate::utils::auth::{extract_user_info, require_verified_email};
+use crate::utils::cost::compute_recipe_cost;
+use crate::utils::nutrition::compute_recipe_nutrition;
+use crate::utils::scaling::{scale_recipe, validate_servings};
 use actix_web::{HttpResponse, web};
+use rust_decimal::Decimal;
 use sqlx::MySqlPool;
 
 // =====================================================
@@ -16,12 +22,45 @@ use sqlx::MySqlPool;
 pub async fn get_all_recipes(
     pool: web::Data<MySqlPool>,
     query: web::Query<PaginationParams>,
+    filter: web::Query<RecipeListFilter>,
+    claims: web::ReqData<TokenClaims>,
 ) -> HttpResponse {
     let params = query.into_inner();
     let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
 
-    match recipe_repo.get_all(params.page, params.page_size).await {
-        Ok((recipes, total_count)) => {
+    if matches!(filter.max_cost_per_serving, Some(c) if c < Decimal::ZERO) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "max_cost_per_serving cannot be negative"
+        }));
+    }
+
+    // Avec safe_only, ne garder que les recettes compatibles avec l'utilisateur connecté
+    let result = if filter.safe_only {
+        let (user_id, _) = match extract_user_info(&claims) {
+            Ok(info) => info,
+            Err(response) => return response,
+        };
+
+        recipe_repo
+            .get_for_user(
+                user_id,
+                params.page,
+                params.page_size,
+                filter.max_cost_per_serving,
+            )
+            .await
+    } else {
+        recipe_repo
+            .get_all(params.page, params.page_size, filter.max_cost_per_serving)
+            .await
+    };
+
+    match result {
+        Ok((mut recipes, total_count)) => {
+            if let Err(response) = attach_ratings(&recipe_repo, &mut recipes).await {
+                return response;
+            }
+
             let total_pages = ((total_count as f64) / (params.page_size as f64)).ceil() as i32;
 
             let response = PaginatedResponse {
@@ -47,12 +86,281 @@ pub async fn get_all_recipes(
     }
 }
 
-/// Récupérer une recette par ID avec ses ingrédients (accessible à tous)
-pub async fn get_recipe(pool: web::Data<MySqlPool>, recipe_id: web::Path<u32>) -> HttpResponse {
+/// Rechercher des recettes publiées (texte, difficulté, auteur, durée, ingrédients, note)
+pub async fn search_recipes(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<RecipeSearchQuery>,
+) -> HttpResponse {
+    let mut params = query.into_inner();
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    // Une recherche vide équivaut à l'absence de recherche
+    params.q = params
+        .q
+        .map(|q| q.trim().to_string())
+        .filter(|q| !q.is_empty());
+
+    if let Some(difficulty) = &params.difficulty
+        && !["Easy", "Medium", "Hard", "Expert"].contains(&difficulty.as_str())
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid difficulty. Must be 'Easy', 'Medium', 'Hard' or 'Expert'"
+        }));
+    }
+
+    if matches!(params.min_rating, Some(r) if r < Decimal::ONE || r > Decimal::from(5)) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "min_rating must be between 1 and 5"
+        }));
+    }
+
+    if matches!(params.max_cost_per_serving, Some(c) if c < Decimal::ZERO) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "max_cost_per_serving cannot be negative"
+        }));
+    }
+
+    let include_ingredients = match parse_id_list(params.include_ingredients.as_deref()) {
+        Ok(ids) => ids,
+        Err(response) => return response,
+    };
+
+    let exclude_ingredients = match parse_id_list(params.exclude_ingredients.as_deref()) {
+        Ok(ids) => ids,
+        Err(response) => return response,
+    };
+
+    match recipe_repo
+        .search(
+            &params,
+            include_ingredients.as_deref(),
+            exclude_ingredients.as_deref(),
+        )
+        .await
+    {
+        Ok((mut recipes, total_count)) => {
+            if let Err(response) = attach_ratings(&recipe_repo, &mut recipes).await {
+                return response;
+            }
+
+            let total_pages = ((total_count as f64) / (params.page_size as f64)).ceil() as i32;
+
+            HttpResponse::Ok().json(PaginatedResponse {
+                data: recipes,
+                pagination: PaginationInfo {
+                    current_page: params.page,
+                    page_size: params.page_size,
+                    total_count,
+                    total_pages,
+                    has_next: params.page < total_pages,
+                    has_previous: params.page > 1,
+                },
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to search recipes: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to search recipes"
+            }))
+        }
+    }
+}
+
+// Valide une liste d'IDs "1,4,7" et la renvoie dédoublonnée, prête pour FIND_IN_SET
+fn parse_id_list(raw: Option<&str>) -> Result<Option<String>, HttpResponse> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+
+    let mut ids: Vec<u32> = Vec::new();
+    for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part.parse::<u32>() {
+            Ok(id) if !ids.contains(&id) => ids.push(id),
+            Ok(_) => {}
+            Err(_) => {
+                return Err(HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("Invalid ingredient id: {}", part)
+                })));
+            }
+        }
+    }
+
+    if ids.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        ids.iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    ))
+}
+
+// Ajoute les notes agrégées aux recettes renvoyées
+async fn attach_ratings(
+    recipe_repo: &RecipeRepository,
+    recipes: &mut [Recipe],
+) -> Result<(), HttpResponse> {
+    recipe_repo.attach_ratings(recipes).await.map_err(|e| {
+        log::error!("Failed to retrieve recipe ratings: {:?}", e);
+        HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to retrieve recipe ratings"
+        }))
+    })
+}
+
+/// Recettes populaires : les plus réalisées et les mieux notées depuis toujours
+pub async fn get_popular_recipes(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PopularRecipesQuery>,
+) -> HttpResponse {
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    if !(1..=50).contains(&query.limit) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "limit must be between 1 and 50"
+        }));
+    }
+
+    match recipe_repo.get_popular(query.limit).await {
+        Ok(recipes) => HttpResponse::Ok().json(recipes),
+        Err(e) => {
+            log::error!("Failed to retrieve popular recipes: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve popular recipes"
+            }))
+        }
+    }
+}
+
+/// Recettes tendance : complétions récentes pondérées par leur note (`?days=7`)
+pub async fn get_trending_recipes(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<TrendingRecipesQuery>,
+) -> HttpResponse {
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    if !(1..=365).contains(&query.days) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "days must be between 1 and 365"
+        }));
+    }
+
+    if !(1..=50).contains(&query.limit) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "limit must be between 1 and 50"
+        }));
+    }
+
+    match recipe_repo.get_trending(query.days, query.limit).await {
+        Ok(recipes) => HttpResponse::Ok().json(recipes),
+        Err(e) => {
+            log::error!("Failed to retrieve trending recipes: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve trending recipes"
+            }))
+        }
+    }
+}
+
+/// Fil de recettes de l'utilisateur connecté : recettes compatibles avec ses allergies et
+/// exclusions, et recettes écartées avec les ingrédients responsables
+pub async fn get_recipes_for_me(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let params = query.into_inner();
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    let (mut recipes, total_count) = match recipe_repo
+        .get_for_user(user_id, params.page, params.page_size, None)
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            log::error!("Failed to retrieve recipes for user: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipes"
+            }));
+        }
+    };
+
+    if let Err(response) = attach_ratings(&recipe_repo, &mut recipes).await {
+        return response;
+    }
+
+    match recipe_repo.get_exclusions_for_user(user_id).await {
+        Ok(excluded_recipes) => {
+            let total_pages = ((total_count as f64) / (params.page_size as f64)).ceil() as i32;
+
+            HttpResponse::Ok().json(RecipeFeedResponse {
+                recipes: PaginatedResponse {
+                    data: recipes,
+                    pagination: PaginationInfo {
+                        current_page: params.page,
+                        page_size: params.page_size,
+                        total_count,
+                        total_pages,
+                        has_next: params.page < total_pages,
+                        has_previous: params.page > 1,
+                    },
+                },
+                excluded_recipes,
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to retrieve recipe exclusions: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipe exclusions"
+            }))
+        }
+    }
+}
+
+/// Récupérer une recette par ID avec ses ingrédients (accessible à tous).
+/// Avec `?servings=N`, les quantités, apports et coût sont adaptés à N portions.
+pub async fn get_recipe(
+    pool: web::Data<MySqlPool>,
+    recipe_id: web::Path<u32>,
+    query: web::Query<RecipeQuery>,
+) -> HttpResponse {
     let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
 
+    if let Some(Err(message)) = query.servings.map(validate_servings) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": message
+        }));
+    }
+
     match recipe_repo.find_by_id(*recipe_id).await {
-        Ok(Some(recipe_with_ingredients)) => HttpResponse::Ok().json(recipe_with_ingredients),
+        Ok(Some(mut recipe_with_ingredients)) => {
+            let recipe = std::slice::from_mut(&mut recipe_with_ingredients.recipe);
+            if let Err(response) = attach_ratings(&recipe_repo, recipe).await {
+                return response;
+            }
+
+            match query.servings {
+                Some(servings) => {
+                    let original_servings = recipe_with_ingredients.recipe.servings;
+                    let scaled = scale_recipe(recipe_with_ingredients, servings);
+
+                    HttpResponse::Ok().json(ScaledRecipe {
+                        nutrition: compute_recipe_nutrition(&scaled, false),
+                        cost: compute_recipe_cost(&scaled, false),
+                        recipe: scaled,
+                        original_servings,
+                    })
+                }
+                None => HttpResponse::Ok().json(recipe_with_ingredients),
+            }
+        }
         Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
             "error": "Recipe not found"
         })),
@@ -65,6 +373,54 @@ pub async fn get_recipe(pool: web::Data<MySqlPool>, recipe_id: web::Path<u32>) -
     }
 }
 
+/// Calculer les apports nutritionnels d'une recette (totaux, par portion et par ingrédient)
+pub async fn get_recipe_nutrition(
+    pool: web::Data<MySqlPool>,
+    recipe_id: web::Path<u32>,
+    query: web::Query<NutritionQuery>,
+) -> HttpResponse {
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    match recipe_repo.find_by_id(*recipe_id).await {
+        Ok(Some(recipe)) => {
+            HttpResponse::Ok().json(compute_recipe_nutrition(&recipe, query.include_optional))
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Recipe not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to compute recipe nutrition: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to compute recipe nutrition"
+            }))
+        }
+    }
+}
+
+/// Estimer le coût d'une recette (total, par portion et par ingrédient)
+pub async fn get_recipe_cost(
+    pool: web::Data<MySqlPool>,
+    recipe_id: web::Path<u32>,
+    query: web::Query<CostQuery>,
+) -> HttpResponse {
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    match recipe_repo.find_by_id(*recipe_id).await {
+        Ok(Some(recipe)) => {
+            HttpResponse::Ok().json(compute_recipe_cost(&recipe, query.include_optional))
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Recipe not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to compute recipe cost: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to compute recipe cost"
+            }))
+        }
+    }
+}
+
 // =====================================================
 // HANDLERS - Gestion des recettes (utilisateur authentifié)
 // =====================================================
@@ -82,6 +438,13 @@ pub async fn create_recipe(
         Err(response) => return response,
     };
 
+    // Publier une recette nécessite une adresse email vérifiée
+    if req.is_published
+        && let Err(response) = require_verified_email(&pool, &claims).await
+    {
+        return response;
+    }
+
     match recipe_repo
         .create(
             &req.title,
@@ -132,7 +495,11 @@ pub async fn get_user_recipes(
         .get_user_recipes(user_id, params.page, params.page_size)
         .await
     {
-        Ok((recipes, total_count)) => {
+        Ok((mut recipes, total_count)) => {
+            if let Err(response) = attach_ratings(&recipe_repo, &mut recipes).await {
+                return response;
+            }
+
             let total_pages = ((total_count as f64) / (params.page_size as f64)).ceil() as i32;
 
             let response = PaginatedResponse {
@@ -158,7 +525,7 @@ pub async fn get_user_recipes(
     }
 }
 
-/// Modifier une recette (auteur ou administrateur)
+/// Modifier une recette (auteur ou permission recipes.moderate)
 pub async fn update_recipe(
     pool: web::Data<MySqlPool>,
     recipe_id: web::Path<u32>,
@@ -172,6 +539,13 @@ pub async fn update_recipe(
         Err(response) => return response,
     };
 
+    // Publier une recette nécessite une adresse email vérifiée
+    if req.is_published
+        && let Err(response) = require_verified_email(&pool, &claims).await
+    {
+        return response;
+    }
+
     match recipe_repo
         .update(
             *recipe_id,
@@ -218,7 +592,7 @@ pub async fn update_recipe(
     }
 }
 
-/// Supprimer une recette (auteur ou administrateur)
+/// Supprimer une recette (auteur ou permission recipes.moderate)
 pub async fn delete_recipe(
     pool: web::Data<MySqlPool>,
     recipe_id: web::Path<u32>,
@@ -258,7 +632,7 @@ pub async fn delete_recipe(
 // HANDLERS - Gestion des ingrédients de recette
 // =====================================================
 
-/// Ajouter un ingrédient à une recette (auteur ou administrateur)
+/// Ajouter un ingrédient à une recette (auteur ou permission recipes.moderate)
 pub async fn add_recipe_ingredient(
     pool: web::Data<MySqlPool>,
     recipe_id: web::Path<u32>,
@@ -303,7 +677,7 @@ pub async fn add_recipe_ingredient(
     }
 }
 
-/// Supprimer un ingrédient d'une recette (auteur ou administrateur)
+/// Supprimer un ingrédient d'une recette (auteur ou permission recipes.moderate)
 pub async fn remove_recipe_ingredient(
     pool: web::Data<MySqlPool>,
     path: web::Path<(u32, u32)>,
@@ -382,6 +756,93 @@ pub async fn complete_recipe(
     }
 }
 
+/// Récupérer les avis (note et/ou commentaire) laissés sur une recette
+pub async fn get_recipe_reviews(
+    pool: web::Data<MySqlPool>,
+    recipe_id: web::Path<u32>,
+    query: web::Query<PaginationParams>,
+) -> HttpResponse {
+    let params = query.into_inner();
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    match recipe_repo
+        .get_reviews(*recipe_id, params.page, params.page_size)
+        .await
+    {
+        Ok((reviews, total_count)) => {
+            let total_pages = ((total_count as f64) / (params.page_size as f64)).ceil() as i32;
+
+            HttpResponse::Ok().json(PaginatedResponse {
+                data: reviews,
+                pagination: PaginationInfo {
+                    current_page: params.page,
+                    page_size: params.page_size,
+                    total_count,
+                    total_pages,
+                    has_next: params.page < total_pages,
+                    has_previous: params.page > 1,
+                },
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to retrieve recipe reviews: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Recipe not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to retrieve recipe reviews"
+                }))
+            }
+        }
+    }
+}
+
+/// Historique des recettes réalisées par l'utilisateur connecté
+pub async fn get_my_completions(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<PaginationParams>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let params = query.into_inner();
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match recipe_repo
+        .get_user_completions(user_id, params.page, params.page_size)
+        .await
+    {
+        Ok((completions, total_count)) => {
+            let total_pages = ((total_count as f64) / (params.page_size as f64)).ceil() as i32;
+
+            HttpResponse::Ok().json(PaginatedResponse {
+                data: completions,
+                pagination: PaginationInfo {
+                    current_page: params.page,
+                    page_size: params.page_size,
+                    total_count,
+                    total_pages,
+                    has_next: params.page < total_pages,
+                    has_previous: params.page > 1,
+                },
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to retrieve completions: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve completions"
+            }))
+        }
+    }
+}
+
 /// Récupérer les étapes d'une recette (accessible à tous)
 pub async fn get_recipe_steps(
     pool: web::Data<MySqlPool>,
@@ -408,7 +869,7 @@ pub async fn get_recipe_steps(
     }
 }
 
-/// Ajouter une étape à une recette (auteur ou administrateur)
+/// Ajouter une étape à une recette (auteur ou permission recipes.moderate)
 pub async fn add_recipe_step(
     pool: web::Data<MySqlPool>,
     recipe_id: web::Path<u32>,
@@ -463,7 +924,7 @@ pub async fn add_recipe_step(
     }
 }
 
-/// Modifier une étape de recette (auteur ou administrateur)
+/// Modifier une étape de recette (auteur ou permission recipes.moderate)
 pub async fn update_recipe_step(
     pool: web::Data<MySqlPool>,
     path: web::Path<(u32, u32)>,
@@ -518,7 +979,7 @@ pub async fn update_recipe_step(
     }
 }
 
-/// Supprimer une étape de recette (auteur ou administrateur)
+/// Supprimer une étape de recette (auteur ou permission recipes.moderate)
 pub async fn delete_recipe_step(
     pool: web::Data<MySqlPool>,
     path: web::Path<(u32, u32)>,
@@ -0,0 +1,180 @@
+use crate::models::{CookableQuery, RecommendationQuery, TokenClaims};
+use crate::recommendations::{UserProfile, rank_cookable_recipes, recommend_recipes};
+use crate::repositories::{
+    IngredientCategoryRepository, RecipeRepository, StockRepository, UserPreferencesRepository,
+};
+use crate::utils::auth::extract_user_info;
+use actix_web::{HttpResponse, web};
+use sqlx::MySqlPool;
+use std::collections::{HashMap, HashSet};
+
+// =====================================================
+// HANDLERS - Recommandations
+// =====================================================
+
+/// Recettes publiées classées selon ce que l'utilisateur a déjà dans son stock
+pub async fn get_cookable_recipes(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<CookableQuery>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    let stock = match stock_repo.get_user_stock(user_id).await {
+        Ok(items) => items,
+        Err(e) => {
+            log::error!("Failed to retrieve user stock: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve stock"
+            }));
+        }
+    };
+
+    let recipes = match recipe_repo.get_published_with_ingredients().await {
+        Ok(recipes) => recipes,
+        Err(e) => {
+            log::error!("Failed to retrieve recipes: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipes"
+            }));
+        }
+    };
+
+    let cookable: Vec<_> = rank_cookable_recipes(recipes, &stock)
+        .into_iter()
+        .filter(|r| {
+            query
+                .max_missing
+                .is_none_or(|max| r.missing_ingredients.len() <= max)
+        })
+        .take(query.limit)
+        .collect();
+
+    HttpResponse::Ok().json(cookable)
+}
+
+/// Recettes publiées recommandées à l'utilisateur, avec l'explication de chaque score
+pub async fn get_recommendations(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<RecommendationQuery>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let recipe_repo = RecipeRepository::new(pool.get_ref().clone());
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+    let preferences_repo = UserPreferencesRepository::new(pool.get_ref().clone());
+    let category_repo = IngredientCategoryRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if query.limit == 0 || query.limit > 50 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "limit must be between 1 and 50"
+        }));
+    }
+
+    let recipes = match recipe_repo.get_published_with_ingredients().await {
+        Ok(recipes) => recipes,
+        Err(e) => {
+            log::error!("Failed to retrieve recipes: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipes"
+            }));
+        }
+    };
+
+    let excluded_recipes: HashSet<u32> = match recipe_repo.get_exclusions_for_user(user_id).await {
+        Ok(exclusions) => exclusions.into_iter().map(|e| e.recipe_id).collect(),
+        Err(e) => {
+            log::error!("Failed to retrieve recipe exclusions: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipe exclusions"
+            }));
+        }
+    };
+
+    let history = match recipe_repo.get_user_recipe_history(user_id).await {
+        Ok(history) => history,
+        Err(e) => {
+            log::error!("Failed to retrieve recipe history: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve recipe history"
+            }));
+        }
+    };
+
+    let stock = match stock_repo.get_user_stock(user_id).await {
+        Ok(items) => items,
+        Err(e) => {
+            log::error!("Failed to retrieve user stock: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve stock"
+            }));
+        }
+    };
+
+    let preferences = match preferences_repo
+        .get_all_user_preferences(user_id as i32)
+        .await
+    {
+        Ok(preferences) => preferences,
+        Err(e) => {
+            log::error!("Failed to retrieve user preferences: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve preferences"
+            }));
+        }
+    };
+
+    let preferred_ingredients: HashSet<u32> = preferences
+        .ingredient_preferences
+        .iter()
+        .filter(|p| p.preference_type == "preferred")
+        .map(|p| p.ingredient_id)
+        .collect();
+
+    // Les catégories préférées sont ramenées à leurs ingrédients
+    let mut preferred_category_ingredients: HashMap<u32, String> = HashMap::new();
+    for category in preferences
+        .category_preferences
+        .iter()
+        .filter(|p| p.preference_type == "preferred")
+    {
+        match category_repo
+            .get_category_ingredients(category.category_id as i32)
+            .await
+        {
+            Ok(ingredients) => {
+                for ingredient in ingredients {
+                    preferred_category_ingredients
+                        .entry(ingredient.ingredient_id)
+                        .or_insert_with(|| category.category_name.clone());
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to retrieve category ingredients: {:?}", e);
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to retrieve preferences"
+                }));
+            }
+        }
+    }
+
+    let profile = UserProfile {
+        history: &history,
+        preferred_ingredients: &preferred_ingredients,
+        preferred_category_ingredients: &preferred_category_ingredients,
+        stock: &stock,
+        excluded_recipes: &excluded_recipes,
+    };
+
+    HttpResponse::Ok().json(recommend_recipes(recipes, &profile, query.limit))
+}
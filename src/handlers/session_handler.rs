@@ -0,0 +1,323 @@
+use crate::handlers::user_handler::token_response;
+use crate::models::{ActorClaim, ImpersonationResponse, RefreshTokenRequest, Role, TokenClaims};
+use crate::repositories::{
+    AuditRepository, PermissionRepository, SessionRepository, UserRepository,
+};
+use crate::utils::auth::{
+    client_ip, create_impersonation_jwt, extract_user_info, generate_token,
+    impersonation_expiration, refresh_token_expiration, reject_impersonation,
+};
+use actix_web::{HttpRequest, HttpResponse, web};
+use sqlx::MySqlPool;
+
+/// Échange un refresh token valide contre un nouveau token d'accès (le refresh token est renouvelé)
+pub async fn refresh_token(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<RefreshTokenRequest>,
+) -> HttpResponse {
+    let session_repo = SessionRepository::new(pool.get_ref().clone());
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let new_refresh_token = generate_token();
+
+    let (session_id, user_id) = match session_repo
+        .refresh(
+            &req.refresh_token,
+            &new_refresh_token,
+            refresh_token_expiration(),
+        )
+        .await
+    {
+        Ok(session) => session,
+        Err(e) => {
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("Invalid or expired") {
+                return HttpResponse::Unauthorized().json(serde_json::json!({
+                    "error": "Invalid or expired refresh token"
+                }));
+            }
+
+            log::error!("Failed to refresh session: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to refresh session"
+            }));
+        }
+    };
+
+    // Recharger l'utilisateur : le rôle a pu changer depuis la connexion
+    match user_repo.find_by_id(user_id).await {
+        Ok(Some(user)) if user.is_active => token_response(&user, session_id, new_refresh_token),
+        Ok(_) => HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired refresh token"
+        })),
+        Err(e) => {
+            log::error!("Failed to retrieve user: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to refresh session"
+            }))
+        }
+    }
+}
+
+/// Ferme la session courante : son token d'accès et son refresh token sont révoqués
+/// (autorisé sous usurpation : seule la session d'usurpation est alors fermée)
+pub async fn logout(pool: web::Data<MySqlPool>, claims: web::ReqData<TokenClaims>) -> HttpResponse {
+    let session_repo = SessionRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match session_repo.revoke(claims.sid, user_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to revoke session: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Session not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to log out"
+                }))
+            }
+        }
+    }
+}
+
+pub async fn get_my_sessions(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let session_repo = SessionRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = reject_impersonation(&claims) {
+        return response;
+    }
+
+    match session_repo.get_user_sessions(user_id, claims.sid).await {
+        Ok(sessions) => HttpResponse::Ok().json(sessions),
+        Err(e) => {
+            log::error!("Failed to retrieve sessions: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve sessions"
+            }))
+        }
+    }
+}
+
+pub async fn revoke_my_session(
+    pool: web::Data<MySqlPool>,
+    session_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let session_repo = SessionRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = reject_impersonation(&claims) {
+        return response;
+    }
+
+    match session_repo.revoke(*session_id, user_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to revoke session: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Session not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to revoke session"
+                }))
+            }
+        }
+    }
+}
+
+/// Révoque toutes les sessions de l'utilisateur sauf la session courante
+pub async fn revoke_other_sessions(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let session_repo = SessionRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = reject_impersonation(&claims) {
+        return response;
+    }
+
+    match session_repo.revoke_all(user_id, Some(claims.sid)).await {
+        Ok(revoked_count) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Other sessions revoked",
+            "revoked_count": revoked_count
+        })),
+        Err(e) => {
+            log::error!("Failed to revoke sessions: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to revoke sessions"
+            }))
+        }
+    }
+}
+
+/// Ouvre une session de courte durée au nom d'un utilisateur (administration)
+pub async fn impersonate_user(
+    pool: web::Data<MySqlPool>,
+    http_req: HttpRequest,
+    target_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+    let session_repo = SessionRepository::new(pool.get_ref().clone());
+    let audit_repo = AuditRepository::new(pool.get_ref().clone());
+
+    let (admin_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = reject_impersonation(&claims) {
+        return response;
+    }
+
+    if *target_id == admin_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Cannot impersonate yourself"
+        }));
+    }
+
+    let user = match user_repo.find_by_id(*target_id).await {
+        Ok(Some(user)) if user.is_active => user,
+        Ok(_) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "User not found"
+            }));
+        }
+        Err(e) => {
+            log::error!("Failed to retrieve user: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve user"
+            }));
+        }
+    };
+
+    // Un administrateur ne peut pas endosser les droits d'un autre administrateur
+    if user.role == Role::Administrator {
+        return HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Cannot impersonate an administrator"
+        }));
+    }
+
+    // Usurper un compte ne doit jamais donner une permission que l'on n'a pas déjà
+    match PermissionRepository::new(pool.get_ref().clone())
+        .covers_role(&claims.role, &user.role)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Cannot impersonate a user with permissions you do not have"
+            }));
+        }
+        Err(e) => {
+            log::error!("Failed to compare role permissions: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to check permissions"
+            }));
+        }
+    }
+
+    let expiration = impersonation_expiration();
+    let ip_address = client_ip(&http_req);
+    let session_label = format!("Impersonation by user {}", admin_id);
+
+    // Le refresh token de cette session n'est jamais communiqué : elle expire avec le jeton
+    let session_id = match session_repo
+        .create(
+            user.user_id,
+            &generate_token(),
+            ip_address.as_deref(),
+            Some(&session_label),
+            expiration,
+        )
+        .await
+    {
+        Ok(session_id) => session_id,
+        Err(e) => {
+            log::error!("Failed to create impersonation session: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create session"
+            }));
+        }
+    };
+
+    if let Err(e) = audit_repo
+        .record_impersonation(
+            admin_id,
+            user.user_id,
+            Some(session_id),
+            http_req.method().as_str(),
+            http_req.path(),
+            ip_address.as_deref(),
+        )
+        .await
+    {
+        log::error!("Failed to record impersonation: {:?}", e);
+        if let Err(e) = session_repo.revoke(session_id, user.user_id).await {
+            log::error!("Failed to revoke impersonation session: {:?}", e);
+        }
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to start impersonation"
+        }));
+    }
+
+    let actor = ActorClaim {
+        sub: admin_id.to_string(),
+        email: claims.email.clone(),
+    };
+    let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+
+    match create_impersonation_jwt(&user, session_id, actor, &secret, expiration) {
+        Ok(token) => {
+            log::warn!(
+                "Administrator {} started impersonating user {}",
+                admin_id,
+                user.user_id
+            );
+
+            HttpResponse::Ok().json(ImpersonationResponse {
+                token,
+                expires_in: expiration,
+                user_id: user.user_id,
+                email: user.email.clone(),
+                role: user.role.clone(),
+                impersonator_user_id: admin_id,
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to create token: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create token"
+            }))
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::models::{
+    CreateShoppingListRequest, ShoppingListRecipeRequest, TokenClaims,
+    UpdateShoppingListItemRequest,
+};
+use crate::repositories::{RecipeRepository, ShoppingListRepository, StockRepository};
+use crate::utils::auth::extract_user_info;
+use crate::utils::shopping::build_shopping_items;
+use actix_web::{HttpResponse, web};
+use sqlx::MySqlPool;
+
+// =====================================================
+// HANDLERS - Listes de courses
+// =====================================================
+
+/// Récupérer les listes de courses de l'utilisateur connecté
+pub async fn get_shopping_lists(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let list_repo = ShoppingListRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match list_repo.get_user_lists(user_id).await {
+        Ok(lists) => HttpResponse::Ok().json(lists),
+        Err(e) => {
+            log::error!("Failed to retrieve shopping lists: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve shopping lists"
+            }))
+        }
+    }
+}
+
+/// Récupérer une liste de courses, articles regroupés par catégorie
+pub async fn get_shopping_list(
+    pool: web::Data<MySqlPool>,
+    shopping_list_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let list_repo = ShoppingListRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match list_repo.find_by_id(*shopping_list_id, user_id).await {
+        Ok(Some(list)) => HttpResponse::Ok().json(list),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Shopping list not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to retrieve shopping list: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve shopping list"
+            }))
+        }
+    }
+}
+
+/// Générer une liste de courses à partir de recettes, déduction faite du stock
+pub async fn create_shopping_list(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<CreateShoppingListRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if req.recipes.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "At least one recipe is required"
+        }));
+    }
+
+    if req
+        .recipes
+        .iter()
+        .any(|r| matches!(r.servings, Some(s) if s == 0 || s > 100))
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Servings must be between 1 and 100"
+        }));
+    }
+
+    if let Some(duplicate) = req
+        .recipes
+        .iter()
+        .enumerate()
+        .find(|(i, r)| req.recipes[..*i].iter().any(|p| p.recipe_id == r.recipe_id))
+        .map(|(_, r)| r.recipe_id)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Recipe {} is selected more than once", duplicate)
+        }));
+    }
+
+    let name = match req.name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => format!("Shopping list {}", chrono::Utc::now().format("%Y-%m-%d")),
+    };
+
+    save_shopping_list(pool.get_ref(), user_id, &name, &req.recipes).await
+}
+
+/// Charge les recettes, déduit le stock de l'utilisateur et enregistre la liste.
+///
+/// Partagé avec la génération depuis le planning de repas.
+pub async fn save_shopping_list(
+    pool: &MySqlPool,
+    user_id: u32,
+    name: &str,
+    selected: &[ShoppingListRecipeRequest],
+) -> HttpResponse {
+    let list_repo = ShoppingListRepository::new(pool.clone());
+    let recipe_repo = RecipeRepository::new(pool.clone());
+    let stock_repo = StockRepository::new(pool.clone());
+
+    // Charger les recettes demandées et fixer les portions de chacune
+    let mut recipes = Vec::with_capacity(selected.len());
+    let mut selections: Vec<ShoppingListRecipeRequest> = Vec::with_capacity(selected.len());
+
+    for selection in selected {
+        match recipe_repo.find_by_id(selection.recipe_id).await {
+            Ok(Some(recipe)) => {
+                let servings = selection.servings.unwrap_or(recipe.recipe.servings);
+                selections.push(ShoppingListRecipeRequest {
+                    recipe_id: selection.recipe_id,
+                    servings: Some(servings),
+                });
+                recipes.push((recipe, servings));
+            }
+            Ok(None) => {
+                return HttpResponse::NotFound().json(serde_json::json!({
+                    "error": format!("Recipe {} not found", selection.recipe_id)
+                }));
+            }
+            Err(e) => {
+                log::error!("Failed to retrieve recipe for shopping list: {:?}", e);
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to retrieve recipes"
+                }));
+            }
+        }
+    }
+
+    let stock = match stock_repo.get_user_stock(user_id).await {
+        Ok(stock) => stock,
+        Err(e) => {
+            log::error!("Failed to retrieve user stock: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve stock"
+            }));
+        }
+    };
+
+    let items = build_shopping_items(&recipes, &stock);
+
+    match list_repo.create(user_id, name, &selections, &items).await {
+        Ok(shopping_list_id) => match list_repo.find_by_id(shopping_list_id, user_id).await {
+            Ok(Some(list)) => HttpResponse::Created().json(list),
+            Ok(None) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Shopping list created but could not be retrieved"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve created shopping list: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Shopping list created but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to create shopping list: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create shopping list"
+            }))
+        }
+    }
+}
+
+/// Cocher ou décocher un article de la liste
+pub async fn update_shopping_list_item(
+    pool: web::Data<MySqlPool>,
+    path: web::Path<(u32, u32)>,
+    req: web::Json<UpdateShoppingListItemRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let (shopping_list_id, item_id) = path.into_inner();
+    let list_repo = ShoppingListRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match list_repo
+        .set_item_checked(shopping_list_id, item_id, user_id, req.is_checked)
+        .await
+    {
+        Ok(()) => match list_repo.find_by_id(shopping_list_id, user_id).await {
+            Ok(Some(list)) => HttpResponse::Ok().json(list),
+            Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Shopping list not found"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve updated shopping list: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Item updated but failed to retrieve shopping list"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to update shopping list item: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Shopping list item not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to update shopping list item"
+                }))
+            }
+        }
+    }
+}
+
+/// Supprimer une liste de courses
+pub async fn delete_shopping_list(
+    pool: web::Data<MySqlPool>,
+    shopping_list_id: web::Path<u32>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let list_repo = ShoppingListRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match list_repo.delete(*shopping_list_id, user_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to delete shopping list: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Shopping list not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to delete shopping list"
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use crate::models::{
+    AddStockRequest, ExpiringStockItem, ExpiringStockQuery, ExpiringStockResponse,
+    RemoveStockQuery, StockLocationGroup, TokenClaims, UpdateStockRequest,
+};
+use crate::repositories::StockRepository;
+use crate::utils::auth::extract_user_info;
+use actix_web::{HttpResponse, web};
+use rust_decimal::Decimal;
+use sqlx::MySqlPool;
+
+// =====================================================
+// HANDLERS - Stock de l'utilisateur (garde-manger)
+// =====================================================
+
+/// Récupérer le stock de l'utilisateur connecté
+pub async fn get_user_stock(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match stock_repo.get_user_stock(user_id).await {
+        Ok(items) => HttpResponse::Ok().json(items),
+        Err(e) => {
+            log::error!("Failed to retrieve user stock: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve stock"
+            }))
+        }
+    }
+}
+
+/// Récupérer les ingrédients qui expirent bientôt, regroupés par emplacement
+pub async fn get_expiring_stock(
+    pool: web::Data<MySqlPool>,
+    query: web::Query<ExpiringStockQuery>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if query.within_days < 0 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "within_days cannot be negative"
+        }));
+    }
+
+    match stock_repo
+        .get_expiring_stock(user_id, query.within_days)
+        .await
+    {
+        Ok(items) => {
+            let locations = group_by_location(items);
+            let total_value = locations.iter().map(|g| g.total_value).sum();
+
+            HttpResponse::Ok().json(ExpiringStockResponse {
+                within_days: query.within_days,
+                total_value,
+                locations,
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to retrieve expiring stock: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve expiring stock"
+            }))
+        }
+    }
+}
+
+/// Récupérer le résumé du stock (nombre d'éléments, expirations, valeur totale)
+pub async fn get_stock_summary(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match stock_repo.get_summary(user_id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(e) => {
+            log::error!("Failed to retrieve stock summary: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve stock summary"
+            }))
+        }
+    }
+}
+
+// Les éléments arrivent triés par emplacement puis par date d'expiration
+fn group_by_location(items: Vec<ExpiringStockItem>) -> Vec<StockLocationGroup> {
+    let mut groups: Vec<StockLocationGroup> = Vec::new();
+
+    for item in items {
+        match groups.last_mut() {
+            Some(group) if group.storage_location == item.storage_location => {
+                group.total_value += item.estimated_value;
+                group.items.push(item);
+            }
+            _ => groups.push(StockLocationGroup {
+                storage_location: item.storage_location.clone(),
+                total_value: item.estimated_value,
+                items: vec![item],
+            }),
+        }
+    }
+
+    groups
+}
+
+/// Ajouter un ingrédient au stock (cumule la quantité si déjà présent au même emplacement)
+pub async fn add_stock_item(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<AddStockRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if req.quantity <= Decimal::ZERO {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Quantity must be greater than 0"
+        }));
+    }
+
+    match stock_repo
+        .add(
+            user_id,
+            req.ingredient_id,
+            req.quantity,
+            req.expiration_date,
+            req.storage_location.as_deref(),
+        )
+        .await
+    {
+        Ok(stock_id) => match stock_repo.find_by_id(stock_id, user_id).await {
+            Ok(Some(item)) => HttpResponse::Created().json(item),
+            Ok(None) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Stock item created but could not be retrieved"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve created stock item: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Stock item created but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to add stock item: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("foreign key constraint") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Ingredient not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to add stock item"
+                }))
+            }
+        }
+    }
+}
+
+/// Modifier la quantité, la date d'expiration ou l'emplacement d'un ingrédient en stock
+pub async fn update_stock_item(
+    pool: web::Data<MySqlPool>,
+    ingredient_id: web::Path<u32>,
+    req: web::Json<UpdateStockRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if req.quantity <= Decimal::ZERO {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Quantity must be greater than 0"
+        }));
+    }
+
+    match stock_repo
+        .update(
+            user_id,
+            *ingredient_id,
+            req.quantity,
+            req.expiration_date,
+            req.storage_location.as_deref(),
+        )
+        .await
+    {
+        Ok(stock_id) => match stock_repo.find_by_id(stock_id, user_id).await {
+            Ok(Some(item)) => HttpResponse::Ok().json(item),
+            Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Stock item not found"
+            })),
+            Err(e) => {
+                log::error!("Failed to retrieve updated stock item: {:?}", e);
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Stock item updated but failed to retrieve"
+                }))
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to update stock item: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Stock item not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to update stock item"
+                }))
+            }
+        }
+    }
+}
+
+/// Retirer un ingrédient du stock (totalement, ou partiellement avec `?quantity=`)
+pub async fn remove_stock_item(
+    pool: web::Data<MySqlPool>,
+    ingredient_id: web::Path<u32>,
+    query: web::Query<RemoveStockQuery>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let stock_repo = StockRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if matches!(query.quantity, Some(q) if q <= Decimal::ZERO) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Quantity must be greater than 0"
+        }));
+    }
+
+    match stock_repo
+        .remove(
+            user_id,
+            *ingredient_id,
+            query.quantity,
+            query.storage_location.as_deref(),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to remove stock item: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Stock item not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to remove stock item"
+                }))
+            }
+        }
+    }
+}
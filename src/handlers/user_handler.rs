@@ -1,7 +1,15 @@
-use crate::models::{AuthResponse, LoginRequest, RegisterRequest, User};
-use crate::repositories::UserRepository;
-use crate::utils::auth::create_jwt;
-use actix_web::{HttpResponse, web};
+use crate::handlers::email_verification_handler::send_verification_email;
+use crate::mailer::Mailer;
+use crate::models::{
+    AuthResponse, ChangePasswordRequest, DevLoginRequest, LoginRequest, ProfileResponse,
+    RegisterRequest, Role, TokenClaims, UpdateProfileRequest, User,
+};
+use crate::repositories::{LoginThrottleRepository, SessionRepository, UserRepository};
+use crate::utils::auth::{
+    LoginThrottlePolicy, client_ip, create_jwt, dev_mode_enabled, extract_user_info,
+    generate_token, refresh_token_expiration, reject_impersonation,
+};
+use actix_web::{HttpRequest, HttpResponse, http::header, web};
 use bcrypt::{DEFAULT_COST, hash, verify};
 use chrono::NaiveDate;
 use sqlx::MySqlPool;
@@ -10,28 +18,59 @@ use sqlx::MySqlPool;
 // HANDLERS
 // =====================================================
 
-pub async fn login(pool: web::Data<MySqlPool>, req: web::Json<LoginRequest>) -> HttpResponse {
+pub async fn login(
+    pool: web::Data<MySqlPool>,
+    http_req: HttpRequest,
+    req: web::Json<LoginRequest>,
+) -> HttpResponse {
     let user_repo = UserRepository::new(pool.get_ref().clone());
+    let throttle_repo = LoginThrottleRepository::new(pool.get_ref().clone());
+    let ip_address = client_ip(&http_req);
 
-    match user_repo.find_by_email(&req.email).await {
-        Ok(Some(user)) => {
-            return handle_login(&user, &req.password).await;
-        }
+    let user = match user_repo.find_by_email(&req.email).await {
+        Ok(user) => user,
         Err(e) => {
             log::error!("Database error querying users: {:?}", e);
             return HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": "Internal server error"
             }));
         }
-        Ok(None) => {}
+    };
+
+    // La tentative est réservée (comptée comme un échec) avant de vérifier le mot de passe,
+    // en une seule procédure : des requêtes parallèles ne peuvent pas dépasser le seuil.
+    // Les emails inconnus comptent aussi : l'IP finit par être verrouillée
+    match throttle_repo
+        .reserve_attempt(
+            &req.email,
+            ip_address.as_deref(),
+            user.as_ref().map(|u| u.user_id),
+            &LoginThrottlePolicy::from_env(),
+        )
+        .await
+    {
+        Ok(0) => {}
+        Ok(retry_after) => return too_many_attempts(retry_after),
+        Err(e) => {
+            log::error!("Failed to reserve login attempt: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            }));
+        }
     }
 
-    HttpResponse::Unauthorized().json(serde_json::json!({
-        "error": "Invalid credentials"
-    }))
+    match user {
+        Some(user) => handle_login(&pool, &http_req, &user, &req.password).await,
+        None => invalid_credentials(),
+    }
 }
 
-async fn handle_login(user: &User, password: &str) -> HttpResponse {
+async fn handle_login(
+    pool: &MySqlPool,
+    http_req: &HttpRequest,
+    user: &User,
+    password: &str,
+) -> HttpResponse {
     // Vérifier si l'utilisateur est actif
     if !user.is_active {
         return HttpResponse::Forbidden().json(serde_json::json!({
@@ -39,24 +78,28 @@ async fn handle_login(user: &User, password: &str) -> HttpResponse {
         }));
     }
 
-    // Cas spécial : mot de passe vide (pour développement uniquement)
+    // Un compte sans mot de passe (données de démonstration) ne peut pas se connecter ici :
+    // utiliser /auth/dev-login en mode développeur, ou la réinitialisation du mot de passe
     if user.password_hash.is_empty() {
-        log::warn!(
-            "User {} logged in without password verification (empty hash)",
-            user.email
-        );
-        return generate_token_response(user);
+        return invalid_credentials();
     }
 
     // Vérification normale du mot de passe
     match verify(password, &user.password_hash) {
         Ok(valid) => {
             if valid {
-                generate_token_response(user)
+                let throttle_repo = LoginThrottleRepository::new(pool.clone());
+                let ip_address = client_ip(http_req);
+                if let Err(e) = throttle_repo
+                    .reset(&user.email, ip_address.as_deref())
+                    .await
+                {
+                    log::error!("Failed to reset login failures: {:?}", e);
+                }
+
+                start_session(pool, http_req, user).await
             } else {
-                HttpResponse::Unauthorized().json(serde_json::json!({
-                    "error": "Invalid credentials"
-                }))
+                invalid_credentials()
             }
         }
         Err(e) => {
@@ -68,16 +111,99 @@ async fn handle_login(user: &User, password: &str) -> HttpResponse {
     }
 }
 
-fn generate_token_response(user: &User) -> HttpResponse {
+/// Connexion sans mot de passe ; la route n'existe que si DEV_MODE est activé
+pub async fn dev_login(
+    pool: web::Data<MySqlPool>,
+    http_req: HttpRequest,
+    req: web::Json<DevLoginRequest>,
+) -> HttpResponse {
+    if !dev_mode_enabled() {
+        return HttpResponse::NotFound().finish();
+    }
+
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    match user_repo.find_by_email(&req.email).await {
+        Ok(Some(user)) if user.is_active => {
+            log::warn!(
+                "DEV_MODE: user {} logged in without password verification",
+                user.email
+            );
+            start_session(&pool, &http_req, &user).await
+        }
+        Ok(_) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "User not found"
+        })),
+        Err(e) => {
+            log::error!("Database error querying users: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            }))
+        }
+    }
+}
+
+// L'échec a déjà été comptabilisé par la réservation de la tentative
+fn invalid_credentials() -> HttpResponse {
+    HttpResponse::Unauthorized().json(serde_json::json!({
+        "error": "Invalid credentials"
+    }))
+}
+
+fn too_many_attempts(retry_after: i64) -> HttpResponse {
+    HttpResponse::TooManyRequests()
+        .insert_header((header::RETRY_AFTER, retry_after.to_string()))
+        .json(serde_json::json!({
+            "error": "Too many failed login attempts, try again later",
+            "retry_after": retry_after
+        }))
+}
+
+/// Ouvre une session pour l'utilisateur et renvoie le token d'accès et le refresh token
+async fn start_session(pool: &MySqlPool, http_req: &HttpRequest, user: &User) -> HttpResponse {
+    let session_repo = SessionRepository::new(pool.clone());
+
+    let refresh_token = generate_token();
+    let ip_address = client_ip(http_req);
+    let user_agent = http_req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|ua| ua.to_str().ok())
+        .map(|ua| ua.chars().take(255).collect::<String>());
+
+    match session_repo
+        .create(
+            user.user_id,
+            &refresh_token,
+            ip_address.as_deref(),
+            user_agent.as_deref(),
+            refresh_token_expiration(),
+        )
+        .await
+    {
+        Ok(session_id) => token_response(user, session_id, refresh_token),
+        Err(e) => {
+            log::error!("Failed to create session: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create session"
+            }))
+        }
+    }
+}
+
+/// Construit la réponse d'authentification pour une session existante
+pub fn token_response(user: &User, session_id: u32, refresh_token: String) -> HttpResponse {
     let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
     let expiration = std::env::var("JWT_EXPIRATION")
         .unwrap_or_else(|_| "3600".to_string())
         .parse::<i64>()
         .expect("JWT_EXPIRATION must be a valid number");
 
-    match create_jwt(user, &secret, expiration) {
+    match create_jwt(user, session_id, &secret, expiration) {
         Ok(token) => HttpResponse::Ok().json(AuthResponse {
             token,
+            refresh_token,
+            expires_in: expiration,
             user_id: user.user_id,
             email: user.email.clone(),
             role: user.role.clone(),
@@ -91,9 +217,20 @@ fn generate_token_response(user: &User) -> HttpResponse {
     }
 }
 
-pub async fn register(pool: web::Data<MySqlPool>, req: web::Json<RegisterRequest>) -> HttpResponse {
+pub async fn register(
+    pool: web::Data<MySqlPool>,
+    mailer: web::Data<dyn Mailer>,
+    http_req: HttpRequest,
+    req: web::Json<RegisterRequest>,
+) -> HttpResponse {
     let user_repo = UserRepository::new(pool.get_ref().clone());
 
+    if req.email.parse::<lettre::Address>().is_err() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid email address"
+        }));
+    }
+
     // Hasher le mot de passe
     let password_hash = match hash(&req.password, DEFAULT_COST) {
         Ok(h) => h,
@@ -127,7 +264,7 @@ pub async fn register(pool: web::Data<MySqlPool>, req: web::Json<RegisterRequest
             gender,
             &password_hash,
             &req.email,
-            "Regular", // Role par défaut
+            Role::Regular.as_str(), // Role par défaut
             req.country.as_deref(),
             req.city.as_deref(),
             birth_date,
@@ -137,7 +274,15 @@ pub async fn register(pool: web::Data<MySqlPool>, req: web::Json<RegisterRequest
         Ok(user_id) => {
             // Récupérer l'utilisateur créé pour générer le token
             match user_repo.find_by_id(user_id).await {
-                Ok(Some(user)) => generate_token_response(&user),
+                Ok(Some(user)) => {
+                    // Le compte reste limité tant que l'adresse n'est pas vérifiée
+                    if let Err(e) = send_verification_email(&pool, mailer.into_inner(), &user).await
+                    {
+                        log::error!("Failed to send verification email: {:?}", e);
+                    }
+
+                    start_session(&pool, &http_req, &user).await
+                }
                 Ok(None) => HttpResponse::InternalServerError().json(serde_json::json!({
                     "error": "User created but could not be retrieved"
                 })),
@@ -166,3 +311,222 @@ pub async fn register(pool: web::Data<MySqlPool>, req: web::Json<RegisterRequest
         }
     }
 }
+
+fn to_profile_response(user: &User) -> ProfileResponse {
+    ProfileResponse {
+        user_id: user.user_id,
+        first_name: user.first_name.clone(),
+        last_name: user.last_name.clone(),
+        email: user.email.clone(),
+        role: user.role.clone(),
+        gender: format!("{:?}", user.gender),
+        birth_date: user.birth_date,
+        country: user.country.clone(),
+        city: user.city.clone(),
+        email_verified: user.email_verified_at.is_some(),
+        created_at: user.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+// Charge le profil de l'utilisateur connecté (un compte désactivé n'a plus de profil)
+async fn load_active_user(user_repo: &UserRepository, user_id: u32) -> Result<User, HttpResponse> {
+    match user_repo.find_by_id(user_id).await {
+        Ok(Some(user)) if user.is_active => Ok(user),
+        Ok(_) => Err(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "User not found"
+        }))),
+        Err(e) => {
+            log::error!("Failed to retrieve user: {:?}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve profile"
+            })))
+        }
+    }
+}
+
+pub async fn get_profile(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    match load_active_user(&user_repo, user_id).await {
+        Ok(user) => HttpResponse::Ok().json(to_profile_response(&user)),
+        Err(response) => response,
+    }
+}
+
+pub async fn update_profile(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<UpdateProfileRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    // Le nom et le prénom sont obligatoires : on refuse de les vider
+    let blank = |value: &Option<String>| value.as_ref().is_some_and(|v| v.trim().is_empty());
+    if blank(&req.first_name) || blank(&req.last_name) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "first_name and last_name cannot be empty"
+        }));
+    }
+
+    if let Err(e) = user_repo
+        .update(
+            user_id,
+            req.first_name.as_deref().map(str::trim),
+            req.last_name.as_deref().map(str::trim),
+            req.country.as_deref(),
+            req.city.as_deref(),
+            user_id,
+        )
+        .await
+    {
+        log::error!("Failed to update profile: {:?}", e);
+
+        let error_msg = format!("{:?}", e);
+        return if error_msg.contains("not found") {
+            HttpResponse::NotFound().json(serde_json::json!({
+                "error": "User not found"
+            }))
+        } else {
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update profile"
+            }))
+        };
+    }
+
+    match load_active_user(&user_repo, user_id).await {
+        Ok(user) => HttpResponse::Ok().json(to_profile_response(&user)),
+        Err(response) => response,
+    }
+}
+
+pub async fn change_password(
+    pool: web::Data<MySqlPool>,
+    req: web::Json<ChangePasswordRequest>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = reject_impersonation(&claims) {
+        return response;
+    }
+
+    if req.new_password.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "new_password cannot be empty"
+        }));
+    }
+
+    let user = match load_active_user(&user_repo, user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    // Vérifier l'ancien mot de passe avant d'accepter le nouveau
+    match verify(&req.current_password, &user.password_hash) {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Current password is incorrect"
+            }));
+        }
+        Err(e) => {
+            log::error!("Failed to verify password: {:?}", e);
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Current password is incorrect"
+            }));
+        }
+    }
+
+    let password_hash = match hash(&req.new_password, DEFAULT_COST) {
+        Ok(h) => h,
+        Err(e) => {
+            log::error!("Failed to hash password: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to hash password"
+            }));
+        }
+    };
+
+    match user_repo.update_password(user_id, &password_hash).await {
+        Ok(()) => {
+            // Les autres appareils doivent se reconnecter avec le nouveau mot de passe
+            let session_repo = SessionRepository::new(pool.get_ref().clone());
+            if let Err(e) = session_repo.revoke_all(user_id, Some(claims.sid)).await {
+                log::error!("Failed to revoke sessions after password change: {:?}", e);
+            }
+
+            HttpResponse::NoContent().finish()
+        }
+        Err(e) => {
+            log::error!("Failed to update password: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "User not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to update password"
+                }))
+            }
+        }
+    }
+}
+
+/// Désactive le compte de l'utilisateur connecté (suppression logique)
+pub async fn deactivate_account(
+    pool: web::Data<MySqlPool>,
+    claims: web::ReqData<TokenClaims>,
+) -> HttpResponse {
+    let user_repo = UserRepository::new(pool.get_ref().clone());
+
+    let (user_id, _) = match extract_user_info(&claims) {
+        Ok(info) => info,
+        Err(response) => return response,
+    };
+
+    if let Err(response) = reject_impersonation(&claims) {
+        return response;
+    }
+
+    match user_repo.set_active(user_id, false, user_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            log::error!("Failed to deactivate account: {:?}", e);
+
+            let error_msg = format!("{:?}", e);
+            if error_msg.contains("last administrator") {
+                HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "Cannot deactivate the last administrator"
+                }))
+            } else if error_msg.contains("not found") {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "User not found"
+                }))
+            } else {
+                HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to deactivate account"
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+pub mod outbox;
+pub mod smtp;
+
+use lettre::Message;
+use lettre::message::header::ContentType;
+use std::sync::Arc;
+
+pub use outbox::FileOutbox;
+pub use smtp::SmtpMailer;
+
+pub type MailError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Envoi d'emails ; les implémentations sont bloquantes (appeler via spawn_blocking)
+pub trait Mailer: Send + Sync {
+    fn send(&self, message: &Message) -> Result<(), MailError>;
+}
+
+/// Construit un email texte depuis l'expéditeur configuré (MAIL_FROM)
+pub fn build_message(to: &str, subject: &str, body: String) -> Result<Message, MailError> {
+    let from = std::env::var("MAIL_FROM")
+        .unwrap_or_else(|_| "Food Advisor <no-reply@foodadvisor.local>".to_string());
+
+    let message = Message::builder()
+        .from(from.parse()?)
+        .to(to.parse()?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)?;
+
+    Ok(message)
+}
+
+/// Envoie l'email sans bloquer la requête ; un échec est seulement journalisé
+pub fn send_in_background(mailer: Arc<dyn Mailer>, message: Message) {
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = mailer.send(&message) {
+            log::error!("Failed to send email: {:?}", e);
+        }
+    });
+}
+
+/// Choisit le transport selon MAIL_TRANSPORT : "smtp" ou "file" (par défaut)
+pub fn from_env() -> Arc<dyn Mailer> {
+    let transport = std::env::var("MAIL_TRANSPORT").unwrap_or_else(|_| "file".to_string());
+
+    match transport.as_str() {
+        "smtp" => Arc::new(SmtpMailer::from_env()),
+        "file" => Arc::new(FileOutbox::from_env()),
+        other => panic!(
+            "MAIL_TRANSPORT must be \"smtp\" or \"file\", got \"{}\"",
+            other
+        ),
+    }
+}
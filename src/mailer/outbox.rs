@@ -0,0 +1,39 @@
+use super::{MailError, Mailer};
+use chrono::Utc;
+use lettre::Message;
+use std::path::PathBuf;
+
+/// Écrit chaque email dans un fichier .eml au lieu de l'envoyer (développement)
+pub struct FileOutbox {
+    directory: PathBuf,
+}
+
+impl FileOutbox {
+    /// MAIL_OUTBOX_DIR, "./mail_outbox" par défaut
+    pub fn from_env() -> Self {
+        let directory =
+            std::env::var("MAIL_OUTBOX_DIR").unwrap_or_else(|_| "./mail_outbox".to_string());
+
+        Self {
+            directory: PathBuf::from(directory),
+        }
+    }
+}
+
+impl Mailer for FileOutbox {
+    fn send(&self, message: &Message) -> Result<(), MailError> {
+        std::fs::create_dir_all(&self.directory)?;
+
+        let file_name = format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S"),
+            uuid::Uuid::new_v4().simple()
+        );
+        let path = self.directory.join(file_name);
+
+        std::fs::write(&path, message.formatted())?;
+        log::info!("Email written to outbox: {}", path.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use super::{MailError, Mailer};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+
+/// Envoi via un serveur SMTP (relais de production ou capteur local type MailHog)
+pub struct SmtpMailer {
+    transport: SmtpTransport,
+}
+
+impl SmtpMailer {
+    /// SMTP_HOST, SMTP_PORT, SMTP_TLS ("none", "starttls", "tls"), SMTP_USERNAME, SMTP_PASSWORD
+    pub fn from_env() -> Self {
+        let host = std::env::var("SMTP_HOST").expect("SMTP_HOST must be set");
+        let tls = std::env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string());
+
+        let builder = match tls.as_str() {
+            // Sans chiffrement : réservé aux capteurs SMTP locaux
+            "none" => SmtpTransport::builder_dangerous(&host),
+            "starttls" => SmtpTransport::starttls_relay(&host).expect("Invalid SMTP_HOST"),
+            "tls" => SmtpTransport::relay(&host).expect("Invalid SMTP_HOST"),
+            other => panic!(
+                "SMTP_TLS must be \"none\", \"starttls\" or \"tls\", got \"{}\"",
+                other
+            ),
+        };
+
+        let builder = match std::env::var("SMTP_PORT") {
+            Ok(port) => {
+                let port = port.parse().expect("SMTP_PORT must be a valid port number");
+                builder.port(port)
+            }
+            Err(_) => builder,
+        };
+
+        let builder = match (
+            std::env::var("SMTP_USERNAME"),
+            std::env::var("SMTP_PASSWORD"),
+        ) {
+            (Ok(username), Ok(password)) => {
+                builder.credentials(Credentials::new(username, password))
+            }
+            _ => builder,
+        };
+
+        Self {
+            transport: builder.build(),
+        }
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, message: &Message) -> Result<(), MailError> {
+        self.transport.send(message)?;
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 mod handlers;
+mod mailer;
 mod middlewares;
 mod models;
+mod recommendations;
 mod repositories;
 mod utils;
 
@@ -11,7 +13,7 @@ use dotenv::dotenv;
 use sqlx::mysql::MySqlPoolOptions;
 use std::time::Duration;
 
-use crate::middlewares::AdminOnly;
+use crate::middlewares::RequirePermission;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -77,6 +79,17 @@ async fn main() -> std::io::Result<()> {
         }
     }
 
+    let dev_mode = utils::auth::dev_mode_enabled();
+    if dev_mode {
+        println!("⚠️  DEV_MODE enabled: /api/auth/dev-login accepts logins without password");
+    }
+
+    let mailer = mailer::from_env();
+    println!(
+        "✅ Mail transport: {}",
+        std::env::var("MAIL_TRANSPORT").unwrap_or_else(|_| "file".to_string())
+    );
+
     let bind_address = format!("{}:{}", host, port);
     println!("\n🚀 Server starting on http://{}", bind_address);
     println!("📝 API: http://{}/api", bind_address);
@@ -100,6 +113,7 @@ async fn main() -> std::io::Result<()> {
 
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::from(mailer.clone()))
             .wrap(middleware::Logger::default())
             .wrap(cors)
             .route("/health", web::get().to(health_check))
@@ -108,19 +122,123 @@ async fn main() -> std::io::Result<()> {
                     .service(
                         web::scope("/auth")
                             .route("/register", web::post().to(handlers::register))
-                            .route("/login", web::post().to(handlers::login)),
+                            .route("/login", web::post().to(handlers::login))
+                            .route("/refresh", web::post().to(handlers::refresh_token))
+                            .route(
+                                "/forgot-password",
+                                web::post().to(handlers::forgot_password),
+                            )
+                            .route("/reset-password", web::post().to(handlers::reset_password))
+                            .route("/verify", web::get().to(handlers::verify_email))
+                            .configure(|cfg| {
+                                // Connexion sans mot de passe : mode développeur uniquement
+                                if dev_mode {
+                                    cfg.route("/dev-login", web::post().to(handlers::dev_login));
+                                }
+                            })
+                            .service(
+                                web::resource("/verify/resend")
+                                    .wrap(auth.clone())
+                                    .route(web::post().to(handlers::resend_verification_email)),
+                            )
+                            .service(
+                                web::resource("/logout")
+                                    .wrap(auth.clone())
+                                    .route(web::post().to(handlers::logout)),
+                            ),
                     )
                     .service(
                         web::scope("/users")
-                            .wrap(AdminOnly)
+                            .wrap(RequirePermission("users.manage"))
                             .wrap(auth.clone())
-                            .route("/all", web::get().to(handlers::get_all_users)),
+                            .route("/all", web::get().to(handlers::get_all_users))
+                            .route("/{id}", web::get().to(handlers::get_user_details))
+                            .route("/{id}/role", web::put().to(handlers::update_user_role))
+                            .route("/{id}/status", web::put().to(handlers::update_user_status))
+                            .route(
+                                "/{id}/email-verification",
+                                web::put().to(handlers::update_user_email_verification),
+                            )
+                            .route("/{id}/unlock", web::post().to(handlers::unlock_user_login)),
                     )
                     .service(
                         web::scope("/admin")
-                            .wrap(AdminOnly)
                             .wrap(auth.clone())
-                            .route("/create", web::post().to(handlers::create_admin)),
+                            .service(
+                                web::resource("/create")
+                                    .wrap(RequirePermission("users.manage"))
+                                    .route(web::post().to(handlers::create_admin)),
+                            )
+                            .service(
+                                web::resource("/impersonate/{user_id}")
+                                    .wrap(RequirePermission("users.impersonate"))
+                                    .route(web::post().to(handlers::impersonate_user)),
+                            )
+                            .service(
+                                web::resource("/audit/{audit_id}/restore")
+                                    .wrap(RequirePermission("audit.restore"))
+                                    .route(web::post().to(handlers::restore_deletion)),
+                            )
+                            .service(
+                                web::scope("/audit")
+                                    .wrap(RequirePermission("audit.read"))
+                                    .route(
+                                        "/deletions",
+                                        web::get().to(handlers::get_audit_deletions),
+                                    )
+                                    .route(
+                                        "/impersonations",
+                                        web::get().to(handlers::get_audit_impersonations),
+                                    )
+                                    .route(
+                                        "/history/users",
+                                        web::get().to(handlers::get_user_history),
+                                    )
+                                    .route(
+                                        "/history/recipes",
+                                        web::get().to(handlers::get_recipe_history),
+                                    )
+                                    .route(
+                                        "/history/ingredients",
+                                        web::get().to(handlers::get_ingredient_history),
+                                    ),
+                            )
+                            .service(
+                                web::scope("/errors")
+                                    .wrap(RequirePermission("errors.manage"))
+                                    .route("", web::get().to(handlers::get_error_logs))
+                                    .route("/summary", web::get().to(handlers::get_error_summary))
+                                    .route("/{id}", web::get().to(handlers::get_error_log))
+                                    .route(
+                                        "/{id}/resolve",
+                                        web::put().to(handlers::resolve_error_log),
+                                    ),
+                            )
+                            .service(
+                                web::resource("/permissions")
+                                    .wrap(RequirePermission("permissions.manage"))
+                                    .route(web::get().to(handlers::get_permissions)),
+                            )
+                            .service(
+                                web::scope("/roles")
+                                    .wrap(RequirePermission("permissions.manage"))
+                                    .route(
+                                        "/permissions",
+                                        web::get().to(handlers::get_all_role_permissions),
+                                    )
+                                    .route(
+                                        "/{role}/permissions",
+                                        web::get().to(handlers::get_role_permissions),
+                                    )
+                                    .route(
+                                        "/{role}/permissions/{permission_code}",
+                                        web::put().to(handlers::grant_role_permission),
+                                    )
+                                    .route(
+                                        "/{role}/permissions/{permission_code}",
+                                        web::delete().to(handlers::revoke_role_permission),
+                                    ),
+                            ),
                     )
                     .service(
                         web::scope("/ingredients")
@@ -134,10 +252,10 @@ async fn main() -> std::io::Result<()> {
                             )
                             .route("/{id}", web::get().to(handlers::get_ingredient))
                             .route("", web::post().to(handlers::create_ingredient))
-                            // Routes réservées aux administrateurs
+                            // Routes réservées aux rôles autorisés à modifier les ingrédients
                             .service(
                                 web::scope("")
-                                    .wrap(AdminOnly)
+                                    .wrap(RequirePermission("ingredients.edit"))
                                     .route("/{id}", web::put().to(handlers::update_ingredient))
                                     .route("/{id}", web::delete().to(handlers::delete_ingredient)),
                             ),
@@ -146,9 +264,20 @@ async fn main() -> std::io::Result<()> {
                         web::scope("/recipes")
                             .wrap(auth.clone())
                             .route("/my-recipes", web::get().to(handlers::get_user_recipes))
+                            .route("/cookable", web::get().to(handlers::get_cookable_recipes))
+                            .route("/for-me", web::get().to(handlers::get_recipes_for_me))
+                            .route("/search", web::get().to(handlers::search_recipes))
+                            .route("/popular", web::get().to(handlers::get_popular_recipes))
+                            .route("/trending", web::get().to(handlers::get_trending_recipes))
                             .route("", web::get().to(handlers::get_all_recipes))
                             .route("/{id}/image", web::get().to(handlers::get_recipe_image))
                             .route("/{id}/image", web::post().to(handlers::add_recipe_image))
+                            .route("/{id}/cost", web::get().to(handlers::get_recipe_cost))
+                            .route("/{id}/reviews", web::get().to(handlers::get_recipe_reviews))
+                            .route(
+                                "/{id}/nutrition",
+                                web::get().to(handlers::get_recipe_nutrition),
+                            )
                             .route("/{id}/steps", web::get().to(handlers::get_recipe_steps))
                             .route("/{id}/steps", web::post().to(handlers::add_recipe_step))
                             .route(
@@ -173,6 +302,83 @@ async fn main() -> std::io::Result<()> {
                             )
                             .route("/{id}/complete", web::post().to(handlers::complete_recipe)),
                     )
+                    .service(
+                        web::scope("/stock")
+                            .wrap(auth.clone())
+                            .route("", web::get().to(handlers::get_user_stock))
+                            .route("/expiring", web::get().to(handlers::get_expiring_stock))
+                            .route("/summary", web::get().to(handlers::get_stock_summary))
+                            .route("", web::post().to(handlers::add_stock_item))
+                            .route(
+                                "/{ingredient_id}",
+                                web::put().to(handlers::update_stock_item),
+                            )
+                            .route(
+                                "/{ingredient_id}",
+                                web::delete().to(handlers::remove_stock_item),
+                            ),
+                    )
+                    .service(
+                        web::scope("/shopping-lists")
+                            .wrap(auth.clone())
+                            .route("", web::get().to(handlers::get_shopping_lists))
+                            .route("", web::post().to(handlers::create_shopping_list))
+                            .route("/{id}", web::get().to(handlers::get_shopping_list))
+                            .route("/{id}", web::delete().to(handlers::delete_shopping_list))
+                            .route(
+                                "/{id}/items/{item_id}",
+                                web::put().to(handlers::update_shopping_list_item),
+                            ),
+                    )
+                    .service(
+                        web::scope("/recommendations")
+                            .wrap(auth.clone())
+                            .route("", web::get().to(handlers::get_recommendations)),
+                    )
+                    .service(
+                        web::scope("/meal-plans")
+                            .wrap(auth.clone())
+                            .route("", web::get().to(handlers::get_meal_plans))
+                            .route("", web::post().to(handlers::create_meal_plan))
+                            .route(
+                                "/generate",
+                                web::post().to(handlers::generate_week_meal_plan),
+                            )
+                            .route(
+                                "/shopping-list",
+                                web::post().to(handlers::create_meal_plan_shopping_list),
+                            )
+                            .route("/{id}", web::get().to(handlers::get_meal_plan))
+                            .route("/{id}", web::put().to(handlers::update_meal_plan))
+                            .route("/{id}", web::delete().to(handlers::delete_meal_plan)),
+                    )
+                    .service(
+                        web::scope("/me")
+                            .wrap(auth.clone())
+                            .route("", web::get().to(handlers::get_profile))
+                            .route("", web::put().to(handlers::update_profile))
+                            .route("", web::delete().to(handlers::deactivate_account))
+                            .route("/password", web::post().to(handlers::change_password))
+                            .route("/sessions", web::get().to(handlers::get_my_sessions))
+                            .route(
+                                "/sessions",
+                                web::delete().to(handlers::revoke_other_sessions),
+                            )
+                            .route(
+                                "/sessions/{session_id}",
+                                web::delete().to(handlers::revoke_my_session),
+                            )
+                            .route("/allergies", web::get().to(handlers::get_my_allergies))
+                            .route("/completions", web::get().to(handlers::get_my_completions))
+                            .route(
+                                "/allergies/{allergy_id}",
+                                web::put().to(handlers::set_my_allergy),
+                            )
+                            .route(
+                                "/allergies/{allergy_id}",
+                                web::delete().to(handlers::remove_my_allergy),
+                            ),
+                    )
                     .service(
                         web::scope("/preferences")
                             .wrap(auth.clone())
@@ -215,10 +421,10 @@ async fn main() -> std::io::Result<()> {
                                 "/{id}/ingredients",
                                 web::get().to(handlers::get_category_ingredients),
                             )
-                            // Routes réservées aux administrateurs
+                            // Routes réservées aux rôles autorisés à gérer les catégories
                             .service(
                                 web::scope("")
-                                    .wrap(AdminOnly)
+                                    .wrap(RequirePermission("categories.manage"))
                                     .route("", web::post().to(handlers::create_category))
                                     .route("/{id}", web::put().to(handlers::update_category))
                                     .route("/{id}", web::delete().to(handlers::delete_category))
@@ -231,6 +437,33 @@ async fn main() -> std::io::Result<()> {
                                         web::delete().to(handlers::remove_ingredient_from_category),
                                     ),
                             ),
+                    )
+                    .service(
+                        web::scope("/allergies")
+                            .wrap(auth.clone())
+                            // Routes accessibles à tous les utilisateurs authentifiés
+                            .route("", web::get().to(handlers::get_all_allergies))
+                            .route("/{id}", web::get().to(handlers::get_allergy))
+                            .route(
+                                "/{id}/ingredients",
+                                web::get().to(handlers::get_allergy_ingredients),
+                            )
+                            // Routes réservées aux rôles autorisés à gérer les allergies
+                            .service(
+                                web::scope("")
+                                    .wrap(RequirePermission("allergies.manage"))
+                                    .route("", web::post().to(handlers::create_allergy))
+                                    .route("/{id}", web::put().to(handlers::update_allergy))
+                                    .route("/{id}", web::delete().to(handlers::delete_allergy))
+                                    .route(
+                                        "/{id}/ingredients",
+                                        web::post().to(handlers::add_ingredient_allergy),
+                                    )
+                                    .route(
+                                        "/{allergy_id}/ingredients/{ingredient_id}",
+                                        web::delete().to(handlers::remove_ingredient_allergy),
+                                    ),
+                            ),
                     ),
             )
     })
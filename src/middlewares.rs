@@ -1,3 +1,3 @@
-pub mod admin_only_middleware;
+pub mod require_permission_middleware;
 
-pub use admin_only_middleware::AdminOnly;
+pub use require_permission_middleware::RequirePermission;
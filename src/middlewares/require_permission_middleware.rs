@@ -0,0 +1,102 @@
+use crate::repositories::PermissionRepository;
+use actix_web::HttpMessage;
+use actix_web::http::header::ContentType;
+use actix_web::{
+    Error, HttpResponse,
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    web,
+};
+use futures_util::future::LocalBoxFuture;
+use sqlx::MySqlPool;
+use std::future::{Ready, ready};
+use std::rc::Rc;
+
+// Middleware struct : le rôle de l'utilisateur doit posséder la permission nommée
+pub struct RequirePermission(pub &'static str);
+
+impl<S, B> Transform<S, ServiceRequest> for RequirePermission
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequirePermissionMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequirePermissionMiddleware {
+            service: Rc::new(service),
+            permission: self.0,
+        }))
+    }
+}
+
+pub struct RequirePermissionMiddleware<S> {
+    service: Rc<S>,
+    permission: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RequirePermissionMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let permission = self.permission;
+
+        // Récupérer les TokenClaims depuis les extensions (ajoutés par le middleware auth)
+        let claims = req
+            .extensions()
+            .get::<crate::models::TokenClaims>()
+            .cloned();
+        let pool = req.app_data::<web::Data<MySqlPool>>().cloned();
+
+        Box::pin(async move {
+            // Lu à chaque requête : un changement de mapping s'applique sans reconnexion
+            let checked = match (claims, pool) {
+                (Some(claims), Some(pool)) => {
+                    PermissionRepository::new(pool.get_ref().clone())
+                        .has_permission(&claims.role, permission)
+                        .await
+                }
+                _ => Ok(false),
+            };
+
+            let response = match checked {
+                Ok(true) => {
+                    let res = service.call(req).await?;
+                    return Ok(res.map_into_left_body());
+                }
+                Ok(false) => HttpResponse::Forbidden()
+                    .content_type(ContentType::json())
+                    .json(serde_json::json!({
+                        "error": "Insufficient permissions",
+                        "required_permission": permission
+                    })),
+                Err(e) => {
+                    log::error!("Failed to check permission {}: {:?}", permission, e);
+                    HttpResponse::InternalServerError()
+                        .content_type(ContentType::json())
+                        .json(serde_json::json!({
+                            "error": "Failed to check permissions"
+                        }))
+                }
+            };
+
+            let (request, _) = req.into_parts();
+            Ok(ServiceResponse::new(request, response).map_into_right_body())
+        })
+    }
+}
@@ -1,19 +1,35 @@
 pub mod allergy_models;
 pub mod audit_models;
+pub mod cost_models;
 pub mod image_models;
 pub mod ingredient_categories_models;
 pub mod ingredient_models;
+pub mod meal_plan_models;
+pub mod nutrition_models;
 pub mod pagination_models;
+pub mod permission_models;
 pub mod recipe_models;
+pub mod recommendation_models;
+pub mod session_models;
+pub mod shopping_list_models;
+pub mod stock_models;
 pub mod user_models;
 pub mod user_preferences_models;
 
 pub use allergy_models::*;
 pub use audit_models::*;
+pub use cost_models::*;
 pub use image_models::*;
 pub use ingredient_categories_models::*;
 pub use ingredient_models::*;
+pub use meal_plan_models::*;
+pub use nutrition_models::*;
 pub use pagination_models::*;
+pub use permission_models::*;
 pub use recipe_models::*;
+pub use recommendation_models::*;
+pub use session_models::*;
+pub use shopping_list_models::*;
+pub use stock_models::*;
 pub use user_models::*;
 pub use user_preferences_models::*;
@@ -2,6 +2,8 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::models::Ingredient;
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "PascalCase")]
 pub enum Severity {
@@ -9,6 +11,7 @@ pub enum Severity {
     Moderate,
     Severe,
     #[sqlx(rename = "Life-threatening")]
+    #[serde(rename = "Life-threatening")]
     LifeThreatening,
 }
 
@@ -43,3 +46,32 @@ pub struct IngredientAllergy {
     pub ingredient_id: u32,
     pub allergy_id: u32,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllergyWithIngredients {
+    #[serde(flatten)]
+    pub allergy: Allergy,
+    pub ingredients: Vec<Ingredient>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAllergyRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAllergyRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddIngredientAllergyRequest {
+    pub ingredient_id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetUserAllergyRequest {
+    pub severity: Severity,
+}
@@ -1,4 +1,5 @@
 use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
@@ -35,6 +36,39 @@ pub struct HistoryUser {
     pub change_details: Option<serde_json::Value>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct HistoryRecipe {
+    pub history_id: u32,
+    pub recipe_id: u32,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub servings: Option<u32>,
+    pub is_published: Option<bool>,
+    pub difficulty: Option<String>,
+    pub author_user_id: Option<u32>,
+    pub change_type: String,
+    pub changed_by_user_id: Option<u32>,
+    pub changed_at: NaiveDateTime,
+    pub change_details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct HistoryIngredient {
+    pub history_id: u32,
+    pub ingredient_id: u32,
+    pub name: Option<String>,
+    pub carbohydrates: Option<Decimal>,
+    pub proteins: Option<Decimal>,
+    pub fats: Option<Decimal>,
+    pub fibers: Option<Decimal>,
+    pub calories: Option<Decimal>,
+    pub price: Option<Decimal>,
+    pub change_type: String,
+    pub changed_by_user_id: Option<u32>,
+    pub changed_at: NaiveDateTime,
+    pub change_details: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ErrorLog {
     pub error_id: u32,
@@ -51,13 +85,87 @@ pub struct ErrorLog {
     pub resolution_notes: Option<String>,
 }
 
+/// Requête effectuée par un administrateur avec un jeton d'usurpation
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditImpersonation {
+    pub log_id: u32,
+    pub impersonator_user_id: u32,
+    pub impersonated_user_id: u32,
+    pub session_id: Option<u32>,
+    pub http_method: String,
+    pub request_path: String,
+    pub ip_address: Option<String>,
+    pub requested_at: NaiveDateTime,
+}
+
+/// Ligne de la vue v_error_summary (7 derniers jours)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ErrorSummary {
+    pub error_date: NaiveDate,
+    pub error_type: String,
+    pub procedure_name: Option<String>,
+    pub error_count: i64,
+    pub affected_users: i64,
+    pub unresolved_count: i64,
+    pub first_occurrence: NaiveDateTime,
+    pub last_occurrence: NaiveDateTime,
+}
+
 // =====================================================
 // REQUEST/RESPONSE MODELS
 // =====================================================
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ChangeType {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// Filtres communs aux routes d'audit (les dates sont incluses)
+#[derive(Debug, Deserialize)]
+pub struct AuditFilter {
+    pub table: Option<String>,  // Suppressions uniquement : table d'origine
+    pub entity_id: Option<u32>, // Enregistrement concerné
+    pub user_id: Option<u32>,   // Auteur de la modification ou de la suppression
+    pub change_type: Option<ChangeType>, // Historiques uniquement
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Filtres du journal des erreurs (les dates sont incluses)
+#[derive(Debug, Deserialize)]
+pub struct ErrorLogFilter {
+    pub error_type: Option<String>,
+    pub procedure_name: Option<String>,
+    pub user_id: Option<u32>,
+    pub resolved: Option<bool>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveErrorRequest {
+    pub resolution_notes: Option<String>,
+}
+
+/// Jeton d'usurpation : courte durée de vie, sans refresh token
+#[derive(Debug, Serialize)]
+pub struct ImpersonationResponse {
+    pub token: String,
+    pub expires_in: i64,
+    pub user_id: u32,
+    pub email: String,
+    pub role: Role,
+    pub impersonator_user_id: u32,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
     pub user_id: u32,
     pub email: String,
     pub role: Role,
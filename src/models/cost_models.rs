@@ -0,0 +1,31 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngredientCost {
+    pub ingredient_id: u32,
+    pub ingredient_name: String,
+    pub quantity: Decimal,
+    pub measurement_unit: String,
+    pub is_optional: bool,
+    pub price: Decimal,  // Prix pour `weight` grammes (ou pour une pièce)
+    pub weight: Decimal, // Poids de référence du prix, en grammes
+    pub cost: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecipeCost {
+    pub recipe_id: u32,
+    pub title: String,
+    pub servings: u32,
+    pub include_optional: bool,
+    pub total_cost: Decimal,
+    pub cost_per_serving: Decimal,
+    pub ingredients: Vec<IngredientCost>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CostQuery {
+    #[serde(default)]
+    pub include_optional: bool,
+}
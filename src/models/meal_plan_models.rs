@@ -0,0 +1,99 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MealSlot {
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MealPlan {
+    pub meal_plan_id: u32,
+    pub user_id: u32,
+    pub plan_date: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub recipe_id: u32,
+    pub recipe_title: String,
+    pub servings: u32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Repas proposé par le générateur, avant enregistrement
+#[derive(Debug, Serialize)]
+pub struct NewMealPlan {
+    pub plan_date: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub recipe_id: u32,
+    pub servings: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MealPlanDay {
+    pub plan_date: NaiveDate,
+    pub calories: Decimal, // Par personne (une portion de chaque repas)
+    pub meals: Vec<MealPlan>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MealPlanWeek {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub daily_calories: Decimal,
+    pub days: Vec<MealPlanDay>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MealPlanQuery {
+    pub start_date: Option<NaiveDate>, // Absent = aujourd'hui
+    pub end_date: Option<NaiveDate>,   // Absent = 6 jours après le début
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMealPlanRequest {
+    pub plan_date: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub recipe_id: u32,
+    #[serde(default = "default_meal_servings")]
+    pub servings: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMealPlanRequest {
+    pub plan_date: Option<NaiveDate>,
+    pub meal_slot: Option<MealSlot>,
+    pub recipe_id: Option<u32>,
+    pub servings: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenerateMealPlanRequest {
+    pub start_date: Option<NaiveDate>, // Absent = aujourd'hui
+    pub slots: Option<Vec<MealSlot>>,  // Absent = petit-déjeuner, déjeuner et dîner
+    #[serde(default = "default_daily_calories")]
+    pub daily_calories: Decimal,
+    #[serde(default = "default_meal_servings")]
+    pub servings: u32,
+    #[serde(default)]
+    pub replace_existing: bool, // Faux = les créneaux déjà planifiés sont conservés
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MealPlanShoppingListRequest {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub name: Option<String>,
+}
+
+fn default_meal_servings() -> u32 {
+    1
+}
+
+fn default_daily_calories() -> Decimal {
+    Decimal::from(2000)
+}
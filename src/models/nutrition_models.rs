@@ -0,0 +1,39 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct NutritionValues {
+    pub calories: Decimal,
+    pub proteins: Decimal,
+    pub carbohydrates: Decimal,
+    pub fats: Decimal,
+    pub fibers: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngredientNutrition {
+    pub ingredient_id: u32,
+    pub ingredient_name: String,
+    pub quantity: Decimal,
+    pub measurement_unit: String,
+    pub grams: Decimal,
+    pub is_optional: bool,
+    pub nutrition: NutritionValues,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecipeNutrition {
+    pub recipe_id: u32,
+    pub title: String,
+    pub servings: u32,
+    pub include_optional: bool,
+    pub total: NutritionValues,
+    pub per_serving: NutritionValues,
+    pub ingredients: Vec<IngredientNutrition>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NutritionQuery {
+    #[serde(default)]
+    pub include_optional: bool,
+}
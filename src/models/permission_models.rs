@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::models::Role;
+
+/// Permission nommée vérifiée par le middleware RequirePermission (ex. "recipes.moderate")
+#[derive(Debug, Clone, Serialize)]
+pub struct Permission {
+    pub permission_code: String,
+    pub description: String,
+}
+
+/// Permission accordée à un rôle
+#[derive(Debug, Clone, Serialize)]
+pub struct RolePermission {
+    pub role: Role,
+    pub permission_code: String,
+    pub description: String,
+    pub granted_by_user_id: Option<u32>, // NULL pour les permissions initiales
+    pub granted_at: NaiveDateTime,
+}
@@ -1,8 +1,11 @@
 use chrono::NaiveDateTime;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use validator::{Validate, ValidationError};
 
+use crate::models::{PaginatedResponse, RecipeCost, RecipeNutrition, Severity};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RecipeStep {
     pub recipe_step_id: u32,
@@ -30,6 +33,97 @@ pub struct Recipe {
     pub author_first_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author_last_name: Option<String>,
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<RecipeRating>, // Renseigné par RecipeRepository::attach_ratings
+}
+
+/// Notes agrégées d'une recette à partir de ses complétions
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecipeRating {
+    pub completion_count: i64,
+    pub rating_count: i64,
+    pub average_rating: Option<Decimal>,
+    pub histogram: BTreeMap<u8, i64>, // Nombre de notes pour chaque valeur de 1 à 5
+}
+
+/// Indicateurs de v_recipe_complexity
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecipeComplexity {
+    pub ingredient_count: i64,
+    pub step_count: i64,
+    pub total_minutes: i64,
+    pub optional_ingredients: i64,
+    pub cooking_steps: i64,
+    pub action_steps: i64,
+    pub longest_step_minutes: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PopularRecipe {
+    #[serde(flatten)]
+    pub recipe: Recipe,
+    pub completion_count: i64,
+    pub average_rating: Option<Decimal>,
+    pub unique_users: i64,
+    pub last_completed: Option<NaiveDateTime>,
+    pub five_star_count: i64,
+    pub four_plus_star_count: i64,
+    pub complexity: RecipeComplexity,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrendingRecipe {
+    #[serde(flatten)]
+    pub recipe: Recipe,
+    pub completion_count: i64, // Sur la période demandée
+    pub average_rating: Option<Decimal>,
+    pub unique_users: i64,
+    pub last_completed: Option<NaiveDateTime>,
+    pub trend_score: Decimal,
+    pub complexity: RecipeComplexity,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PopularRecipesQuery {
+    #[serde(default = "default_ranking_limit")]
+    pub limit: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrendingRecipesQuery {
+    #[serde(default = "default_trending_days")]
+    pub days: i32,
+    #[serde(default = "default_ranking_limit")]
+    pub limit: i32,
+}
+
+fn default_ranking_limit() -> i32 {
+    10
+}
+
+fn default_trending_days() -> i32 {
+    7
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecipeReview {
+    pub completion_id: u32,
+    pub user_id: u32,
+    pub reviewer_name: String,
+    pub rating: Option<u32>,
+    pub comment: Option<String>,
+    pub completion_date: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecipeCompletion {
+    pub completion_id: u32,
+    pub recipe_id: u32,
+    pub recipe_title: String,
+    pub rating: Option<u32>,
+    pub comment: Option<String>,
+    pub completion_date: NaiveDateTime,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +133,20 @@ pub struct RecipeWithIngredients {
     pub ingredients: Vec<RecipeIngredientDetail>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ScaledRecipe {
+    #[serde(flatten)]
+    pub recipe: RecipeWithIngredients,
+    pub original_servings: u32,
+    pub nutrition: RecipeNutrition,
+    pub cost: RecipeCost,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecipeQuery {
+    pub servings: Option<u32>, // Adapter les quantités à ce nombre de portions
+}
+
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct RecipeIngredientDetail {
     pub recipe_id: u32,
@@ -125,6 +233,105 @@ pub struct UpdateRecipeStepRequest {
     pub step_type: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RecipeListFilter {
+    #[serde(default)]
+    pub safe_only: bool, // Exclure les recettes incompatibles avec l'utilisateur connecté
+    pub max_cost_per_serving: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecipeSort {
+    Relevance, // Titre avant description ou ingrédients, puis les plus récentes
+    Newest,
+    Rating,
+    Popularity,
+    Calories,
+}
+
+impl RecipeSort {
+    /// Valeur attendue par `sp_search_recipes`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecipeSort::Relevance => "relevance",
+            RecipeSort::Newest => "newest",
+            RecipeSort::Rating => "rating",
+            RecipeSort::Popularity => "popularity",
+            RecipeSort::Calories => "calories",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecipeSearchQuery {
+    pub q: Option<String>,
+    pub difficulty: Option<String>,
+    pub author_id: Option<u32>,
+    pub max_total_minutes: Option<u32>,
+    pub include_ingredients: Option<String>, // IDs séparés par des virgules : "1,4,7"
+    pub exclude_ingredients: Option<String>,
+    pub min_rating: Option<Decimal>,
+    pub max_cost_per_serving: Option<Decimal>,
+    pub sort: Option<RecipeSort>, // Par défaut : pertinence si `q` est fourni, sinon newest
+    #[serde(default = "default_search_page")]
+    pub page: i32,
+    #[serde(default = "default_search_page_size")]
+    pub page_size: i32,
+}
+
+impl RecipeSearchQuery {
+    /// Tri demandé, ou tri par défaut selon la présence d'un texte recherché
+    pub fn effective_sort(&self) -> RecipeSort {
+        match self.sort {
+            Some(sort) => sort,
+            None if self.q.is_some() => RecipeSort::Relevance,
+            None => RecipeSort::Newest,
+        }
+    }
+}
+
+fn default_search_page() -> i32 {
+    1
+}
+
+fn default_search_page_size() -> i32 {
+    10
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExclusionReason {
+    Allergy,
+    ExcludedIngredient,
+    ExcludedCategory,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExclusionTrigger {
+    pub ingredient_id: u32,
+    pub ingredient_name: String,
+    pub reason: ExclusionReason,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>, // Nom de l'allergie ou de la catégorie exclue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExcludedRecipe {
+    pub recipe_id: u32,
+    pub title: String,
+    pub triggers: Vec<ExclusionTrigger>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecipeFeedResponse {
+    #[serde(flatten)]
+    pub recipes: PaginatedResponse<Recipe>,
+    pub excluded_recipes: Vec<ExcludedRecipe>,
+}
+
 // Fonction de validation personnalisée pour step_type
 fn validate_step_type(step_type: &str) -> Result<(), validator::ValidationError> {
     if step_type == "cooking" || step_type == "action" {
@@ -135,3 +342,34 @@ fn validate_step_type(step_type: &str) -> Result<(), validator::ValidationError>
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_query(params: serde_json::Value) -> RecipeSearchQuery {
+        serde_json::from_value(params).unwrap()
+    }
+
+    #[test]
+    fn text_search_defaults_to_relevance() {
+        let query = search_query(serde_json::json!({ "q": "tarte" }));
+
+        assert_eq!(query.effective_sort(), RecipeSort::Relevance);
+        assert_eq!(query.effective_sort().as_str(), "relevance");
+    }
+
+    #[test]
+    fn search_without_text_defaults_to_newest() {
+        let query = search_query(serde_json::json!({ "difficulty": "Easy" }));
+
+        assert_eq!(query.effective_sort(), RecipeSort::Newest);
+    }
+
+    #[test]
+    fn explicit_sort_overrides_relevance() {
+        let query = search_query(serde_json::json!({ "q": "tarte", "sort": "rating" }));
+
+        assert_eq!(query.effective_sort(), RecipeSort::Rating);
+    }
+}
@@ -0,0 +1,63 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::Recipe;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MissingIngredient {
+    pub ingredient_id: u32,
+    pub ingredient_name: String,
+    pub measurement_unit: String,
+    pub required_quantity: Decimal,
+    pub available_quantity: Decimal,
+    pub shortfall: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CookableRecipe {
+    #[serde(flatten)]
+    pub recipe: Recipe,
+    pub required_ingredients: usize,
+    pub covered_ingredients: usize,
+    pub coverage: f64, // Entre 0.0 et 1.0
+    pub missing_ingredients: Vec<MissingIngredient>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CookableQuery {
+    pub max_missing: Option<usize>,
+    #[serde(default = "default_cookable_limit")]
+    pub limit: usize,
+}
+
+fn default_cookable_limit() -> usize {
+    20
+}
+
+/// Complétions d'un utilisateur agrégées par recette
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserRecipeHistory {
+    pub recipe_id: u32,
+    pub title: String,
+    pub difficulty: String,
+    pub completion_count: i64,
+    pub average_rating: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecommendedRecipe {
+    #[serde(flatten)]
+    pub recipe: Recipe,
+    pub score: f64,
+    pub reasons: Vec<String>, // Explications lisibles du score
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecommendationQuery {
+    #[serde(default = "default_recommendation_limit")]
+    pub limit: usize,
+}
+
+fn default_recommendation_limit() -> usize {
+    10
+}
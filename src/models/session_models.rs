@@ -0,0 +1,19 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Session ouverte par un utilisateur (une par connexion)
+#[derive(Debug, Clone, Serialize)]
+pub struct UserSession {
+    pub session_id: u32,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub login_time: NaiveDateTime,
+    pub last_activity: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub is_current: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
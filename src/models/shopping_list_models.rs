@@ -0,0 +1,78 @@
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShoppingListSummary {
+    pub shopping_list_id: u32,
+    pub name: String,
+    pub item_count: i64,
+    pub checked_count: i64,
+    pub estimated_total: Decimal,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShoppingListRecipe {
+    pub recipe_id: u32,
+    pub title: String,
+    pub servings: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShoppingListItem {
+    pub shopping_list_item_id: u32,
+    pub ingredient_id: u32,
+    pub ingredient_name: String,
+    pub quantity: Decimal,
+    pub measurement_unit: String,
+    pub estimated_cost: Decimal,
+    pub is_checked: bool,
+    pub checked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShoppingListCategory {
+    pub category_name: Option<String>, // Absent pour les ingrédients sans catégorie
+    pub estimated_cost: Decimal,
+    pub items: Vec<ShoppingListItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShoppingList {
+    pub shopping_list_id: u32,
+    pub user_id: u32,
+    pub name: String,
+    pub recipes: Vec<ShoppingListRecipe>,
+    pub estimated_total: Decimal,
+    pub remaining_cost: Decimal, // Coût des articles non cochés
+    pub categories: Vec<ShoppingListCategory>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Article calculé avant enregistrement de la liste
+#[derive(Debug, Serialize)]
+pub struct NewShoppingListItem {
+    pub ingredient_id: u32,
+    pub quantity: Decimal,
+    pub estimated_cost: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShoppingListRecipeRequest {
+    pub recipe_id: u32,
+    pub servings: Option<u32>, // Absent = nombre de portions de la recette
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateShoppingListRequest {
+    pub name: Option<String>,
+    pub recipes: Vec<ShoppingListRecipeRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateShoppingListItemRequest {
+    pub is_checked: bool,
+}
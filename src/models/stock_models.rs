@@ -0,0 +1,87 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StockItem {
+    pub stock_id: u32,
+    pub user_id: u32,
+    pub ingredient_id: u32,
+    pub ingredient_name: String,
+    pub quantity: Decimal,
+    pub measurement_unit: String,
+    pub expiration_date: Option<NaiveDate>,
+    pub storage_location: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddStockRequest {
+    pub ingredient_id: u32,
+    pub quantity: Decimal,
+    pub expiration_date: Option<NaiveDate>, // Format: "YYYY-MM-DD"
+    pub storage_location: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateStockRequest {
+    pub quantity: Decimal,
+    pub expiration_date: Option<NaiveDate>, // Format: "YYYY-MM-DD"
+    pub storage_location: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveStockQuery {
+    pub quantity: Option<Decimal>, // Absent = retirer tout l'élément
+    pub storage_location: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ExpiringStockItem {
+    pub stock_id: u32,
+    pub ingredient_id: u32,
+    pub ingredient_name: String,
+    pub quantity: Decimal,
+    pub measurement_unit: String,
+    pub expiration_date: NaiveDate,
+    pub storage_location: Option<String>,
+    pub days_until_expiration: i64,
+    pub expiration_status: String, // "Expired", "Critical", "Warning", "Notice", "Good"
+    pub estimated_value: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockLocationGroup {
+    pub storage_location: Option<String>,
+    pub total_value: Decimal,
+    pub items: Vec<ExpiringStockItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExpiringStockResponse {
+    pub within_days: i32,
+    pub total_value: Decimal,
+    pub locations: Vec<StockLocationGroup>,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StockSummary {
+    pub user_id: u32,
+    pub unique_ingredients: i64,
+    pub total_items: i64,
+    pub expired_items: i64,
+    pub expiring_soon: i64,
+    pub total_stock_value: Decimal,
+    pub storage_locations: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExpiringStockQuery {
+    #[serde(default = "default_within_days")]
+    pub within_days: i32,
+}
+
+fn default_within_days() -> i32 {
+    7
+}
@@ -1,6 +1,7 @@
 use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "PascalCase")]
@@ -10,20 +11,59 @@ pub enum Gender {
     Other,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "PascalCase")]
 pub enum Role {
     Administrator,
+    Moderator,
+    Nutritionist,
     Regular,
 }
 
+impl Role {
+    /// Valeur stockée en base (colonnes ENUM `role`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Administrator => "Administrator",
+            Role::Moderator => "Moderator",
+            Role::Nutritionist => "Nutritionist",
+            Role::Regular => "Regular",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Administrator" => Ok(Role::Administrator),
+            "Moderator" => Ok(Role::Moderator),
+            "Nutritionist" => Ok(Role::Nutritionist),
+            "Regular" => Ok(Role::Regular),
+            _ => Err(format!("Unknown role: {}", s)),
+        }
+    }
+}
+
 /// Structure pour les claims JWT (légère, seulement ce qui est nécessaire)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TokenClaims {
     pub sub: String, // user_id
     pub email: String,
     pub role: Role,
+    pub sid: u32,   // session_id (user_sessions)
     pub exp: usize, // expiration timestamp
+    // Présent uniquement sur les jetons d'usurpation : l'administrateur qui agit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<ActorClaim>,
+}
+
+/// Claim `act` (RFC 8693) : identité réelle derrière un jeton d'usurpation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActorClaim {
+    pub sub: String, // user_id de l'administrateur
+    pub email: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -41,6 +81,7 @@ pub struct User {
     pub birth_date: Option<NaiveDate>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub email_verified_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -63,6 +104,7 @@ pub struct UserWithStats {
     pub average_rating_given: Option<f64>,
     pub allergy_count: Option<i64>,
     pub stock_items_count: Option<i64>,
+    pub email_verified_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -94,5 +136,80 @@ pub struct ProfileResponse {
     pub birth_date: Option<NaiveDate>,
     pub country: Option<String>,
     pub city: Option<String>,
+    pub email_verified: bool,
     pub created_at: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfileRequest {
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub country: Option<String>,
+    pub city: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Connexion sans mot de passe, uniquement en mode développeur (DEV_MODE)
+#[derive(Debug, Deserialize)]
+pub struct DevLoginRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailQuery {
+    pub token: String,
+}
+
+/// Validation manuelle de l'adresse email par un administrateur
+#[derive(Debug, Deserialize)]
+pub struct UpdateEmailVerificationRequest {
+    pub verified: bool,
+}
+
+/// Filtres de la liste des utilisateurs (administration)
+#[derive(Debug, Deserialize)]
+pub struct UserSearchQuery {
+    pub q: Option<String>, // Recherche dans le nom, le prénom et l'email
+    pub role: Option<Role>,
+    pub country: Option<String>,
+    pub city: Option<String>,
+    pub is_active: Option<bool>,
+    #[serde(default = "default_user_page")]
+    pub page: i32,
+    #[serde(default = "default_user_page_size")]
+    pub page_size: i32,
+}
+
+fn default_user_page() -> i32 {
+    1
+}
+
+fn default_user_page_size() -> i32 {
+    10
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateUserRoleRequest {
+    pub role: Role,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateUserStatusRequest {
+    pub is_active: bool,
+}
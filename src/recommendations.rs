@@ -0,0 +1,8 @@
+pub mod cookable;
+pub mod meal_plan;
+pub mod personalized;
+
+// Ré-exporter les fonctions de recommandation
+pub use cookable::rank_cookable_recipes;
+pub use meal_plan::{calories_per_serving, generate_meal_plan, group_by_day};
+pub use personalized::{UserProfile, recommend_recipes};
@@ -0,0 +1,105 @@
+use crate::models::{CookableRecipe, MissingIngredient, RecipeWithIngredients, StockItem};
+use crate::utils::units::{MeasurementUnit, from_base_quantity, to_base_quantity};
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Classe les recettes selon la part de leurs ingrédients obligatoires couverte par le stock.
+///
+/// Les quantités sont comparées dans l'unité de base (grammes, millilitres ou pièces) ;
+/// le tri est déterministe : couverture décroissante, puis nombre d'ingrédients manquants,
+/// puis `recipe_id`.
+pub fn rank_cookable_recipes(
+    recipes: Vec<RecipeWithIngredients>,
+    stock: &[StockItem],
+) -> Vec<CookableRecipe> {
+    let available = available_quantities(stock);
+
+    let mut ranked: Vec<CookableRecipe> = recipes
+        .into_iter()
+        .filter_map(|recipe| match_recipe(recipe, &available))
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.coverage
+            .partial_cmp(&a.coverage)
+            .unwrap_or(Ordering::Equal)
+            .then(
+                a.missing_ingredients
+                    .len()
+                    .cmp(&b.missing_ingredients.len()),
+            )
+            .then(a.recipe.recipe_id.cmp(&b.recipe.recipe_id))
+    });
+
+    ranked
+}
+
+// Un même ingrédient peut être stocké à plusieurs emplacements
+pub fn available_quantities(stock: &[StockItem]) -> HashMap<u32, Decimal> {
+    let mut available: HashMap<u32, Decimal> = HashMap::new();
+
+    for item in stock {
+        *available.entry(item.ingredient_id).or_default() +=
+            to_base_quantity(item.quantity, &item.measurement_unit);
+    }
+
+    available
+}
+
+fn match_recipe(
+    recipe: RecipeWithIngredients,
+    available: &HashMap<u32, Decimal>,
+) -> Option<CookableRecipe> {
+    let required: Vec<_> = recipe
+        .ingredients
+        .iter()
+        .filter(|i| !i.is_optional)
+        .collect();
+
+    // Rien à comparer pour une recette sans ingrédient obligatoire
+    if required.is_empty() {
+        return None;
+    }
+
+    let mut missing_ingredients = Vec::new();
+
+    for ingredient in &required {
+        let unit = &ingredient.measurement_unit;
+        let needed = to_base_quantity(ingredient.quantity, unit);
+        let in_stock = available
+            .get(&ingredient.ingredient_id)
+            .copied()
+            .unwrap_or(Decimal::ZERO);
+
+        if in_stock >= needed {
+            continue;
+        }
+
+        let mut shortfall = from_base_quantity(needed - in_stock, unit);
+        shortfall = match MeasurementUnit::parse(unit) {
+            Some(u) if u.is_countable() => shortfall.ceil(),
+            _ => shortfall.round_dp(2),
+        };
+
+        missing_ingredients.push(MissingIngredient {
+            ingredient_id: ingredient.ingredient_id,
+            ingredient_name: ingredient.ingredient_name.clone(),
+            measurement_unit: unit.clone(),
+            required_quantity: ingredient.quantity,
+            available_quantity: from_base_quantity(in_stock, unit).round_dp(2),
+            shortfall,
+        });
+    }
+
+    let required_ingredients = required.len();
+    let covered_ingredients = required_ingredients - missing_ingredients.len();
+
+    Some(CookableRecipe {
+        recipe: recipe.recipe,
+        required_ingredients,
+        covered_ingredients,
+        coverage: covered_ingredients as f64 / required_ingredients as f64,
+        missing_ingredients,
+    })
+}
@@ -0,0 +1,288 @@
+use crate::models::{MealPlan, MealPlanDay, MealSlot, NewMealPlan, RecipeWithIngredients};
+use crate::utils::nutrition::compute_recipe_nutrition;
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+
+/// Nombre de jours couverts par un planning généré
+pub const MEAL_PLAN_DAYS: u64 = 7;
+
+/// Paramètres de génération d'un planning
+pub struct MealPlanTarget {
+    pub start_date: NaiveDate,
+    pub slots: Vec<MealSlot>,
+    pub daily_calories: Decimal,
+    pub servings: u32,
+}
+
+/// Calories par portion de chaque recette (ingrédients optionnels exclus)
+pub fn calories_per_serving(recipes: &[RecipeWithIngredients]) -> HashMap<u32, Decimal> {
+    recipes
+        .iter()
+        .map(|recipe| {
+            let nutrition = compute_recipe_nutrition(recipe, false);
+            (recipe.recipe.recipe_id, nutrition.per_serving.calories)
+        })
+        .collect()
+}
+
+/// Remplit une semaine de repas à partir des recettes candidates.
+///
+/// Les créneaux de `existing` sont conservés : leurs calories comptent dans la journée
+/// et leurs recettes sont considérées comme déjà servies. Pour chaque créneau libre,
+/// la cible est la part du créneau dans ce qu'il reste à consommer ce jour-là ; on
+/// choisit la recette la moins servie de la semaine, puis la plus proche de la cible,
+/// puis le plus petit `recipe_id`, ce qui rend la génération déterministe.
+pub fn generate_meal_plan(
+    candidates: &[u32],
+    calories: &HashMap<u32, Decimal>,
+    existing: &[MealPlan],
+    target: &MealPlanTarget,
+) -> Vec<NewMealPlan> {
+    // Une recette sans valeur nutritionnelle ne permet pas de viser un apport
+    let candidates: Vec<(u32, Decimal)> = candidates
+        .iter()
+        .filter_map(|id| calories.get(id).map(|c| (*id, *c)))
+        .filter(|(_, c)| *c > Decimal::ZERO)
+        .collect();
+
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    let mut slots = target.slots.clone();
+    slots.sort();
+    slots.dedup();
+
+    let mut served: HashMap<u32, usize> = HashMap::new();
+    for meal in existing {
+        *served.entry(meal.recipe_id).or_default() += 1;
+    }
+
+    let mut planned = Vec::new();
+
+    for offset in 0..MEAL_PLAN_DAYS {
+        let Some(plan_date) = target.start_date.checked_add_days(Days::new(offset)) else {
+            break;
+        };
+
+        let kept: Vec<&MealPlan> = existing
+            .iter()
+            .filter(|m| m.plan_date == plan_date)
+            .collect();
+        let taken: HashSet<MealSlot> = kept.iter().map(|m| m.meal_slot).collect();
+        let free: Vec<MealSlot> = slots
+            .iter()
+            .copied()
+            .filter(|slot| !taken.contains(slot))
+            .collect();
+
+        let mut remaining_calories = target.daily_calories
+            - kept
+                .iter()
+                .map(|m| calories.get(&m.recipe_id).copied().unwrap_or_default())
+                .sum::<Decimal>();
+        let mut remaining_share: Decimal = free.iter().map(calorie_share).sum();
+
+        for slot in free {
+            let share = calorie_share(&slot);
+            let slot_target = (remaining_calories * share / remaining_share).max(Decimal::ZERO);
+
+            let Some(&(recipe_id, recipe_calories)) = candidates.iter().min_by(|a, b| {
+                let served_a = served.get(&a.0).copied().unwrap_or_default();
+                let served_b = served.get(&b.0).copied().unwrap_or_default();
+
+                served_a
+                    .cmp(&served_b)
+                    .then((a.1 - slot_target).abs().cmp(&(b.1 - slot_target).abs()))
+                    .then(a.0.cmp(&b.0))
+            }) else {
+                break;
+            };
+
+            *served.entry(recipe_id).or_default() += 1;
+            remaining_calories -= recipe_calories;
+            remaining_share -= share;
+
+            planned.push(NewMealPlan {
+                plan_date,
+                meal_slot: slot,
+                recipe_id,
+                servings: target.servings,
+            });
+        }
+    }
+
+    planned
+}
+
+/// Regroupe les repas par jour (ils arrivent triés par date puis par créneau)
+pub fn group_by_day(meals: Vec<MealPlan>, calories: &HashMap<u32, Decimal>) -> Vec<MealPlanDay> {
+    let mut days: Vec<MealPlanDay> = Vec::new();
+
+    for meal in meals {
+        let meal_calories = calories.get(&meal.recipe_id).copied().unwrap_or_default();
+
+        match days.last_mut() {
+            Some(day) if day.plan_date == meal.plan_date => {
+                day.calories += meal_calories;
+                day.meals.push(meal);
+            }
+            _ => days.push(MealPlanDay {
+                plan_date: meal.plan_date,
+                calories: meal_calories,
+                meals: vec![meal],
+            }),
+        }
+    }
+
+    for day in days.iter_mut() {
+        day.calories = day.calories.round_dp(2);
+    }
+
+    days
+}
+
+// Répartition de l'apport journalier entre les repas (en pourcentage)
+fn calorie_share(slot: &MealSlot) -> Decimal {
+    match slot {
+        MealSlot::Breakfast => Decimal::from(25),
+        MealSlot::Lunch => Decimal::from(35),
+        MealSlot::Dinner => Decimal::from(30),
+        MealSlot::Snack => Decimal::from(10),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::{dec, timestamp};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn target(slots: Vec<MealSlot>) -> MealPlanTarget {
+        MealPlanTarget {
+            start_date: date(1),
+            slots,
+            daily_calories: dec("2000"),
+            servings: 2,
+        }
+    }
+
+    fn calories(values: &[(u32, &str)]) -> HashMap<u32, Decimal> {
+        values.iter().map(|(id, c)| (*id, dec(c))).collect()
+    }
+
+    fn meal(plan_date: NaiveDate, meal_slot: MealSlot, recipe_id: u32) -> MealPlan {
+        MealPlan {
+            meal_plan_id: 0,
+            user_id: 1,
+            plan_date,
+            meal_slot,
+            recipe_id,
+            recipe_title: format!("Recipe {}", recipe_id),
+            servings: 1,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    #[test]
+    fn empty_candidates_produce_no_meals() {
+        let planned = generate_meal_plan(&[], &HashMap::new(), &[], &target(vec![MealSlot::Lunch]));
+
+        assert!(planned.is_empty());
+    }
+
+    #[test]
+    fn zero_calorie_and_unknown_candidates_are_dropped() {
+        let calories = calories(&[(1, "0"), (2, "600")]);
+
+        let planned =
+            generate_meal_plan(&[1, 2, 3], &calories, &[], &target(vec![MealSlot::Lunch]));
+
+        assert_eq!(planned.len(), MEAL_PLAN_DAYS as usize);
+        assert!(planned.iter().all(|m| m.recipe_id == 2));
+
+        let only_zero = generate_meal_plan(&[1], &calories, &[], &target(vec![MealSlot::Lunch]));
+        assert!(only_zero.is_empty());
+    }
+
+    #[test]
+    fn no_recipe_repeats_while_candidates_remain() {
+        let values: Vec<(u32, String)> = (1..=14)
+            .map(|id| (id, format!("{}", 300 + id * 10)))
+            .collect();
+        let calories: HashMap<u32, Decimal> = values.iter().map(|(id, c)| (*id, dec(c))).collect();
+        let candidates: Vec<u32> = (1..=14).collect();
+
+        let planned = generate_meal_plan(
+            &candidates,
+            &calories,
+            &[],
+            &target(vec![MealSlot::Dinner, MealSlot::Lunch]),
+        );
+
+        assert_eq!(planned.len(), 14);
+        let distinct: HashSet<u32> = planned.iter().map(|m| m.recipe_id).collect();
+        assert_eq!(distinct.len(), 14);
+    }
+
+    #[test]
+    fn picks_closest_to_target_then_lowest_id() {
+        // Un seul créneau : la cible est l'apport journalier complet
+        let calories = calories(&[(3, "500"), (1, "1900"), (2, "2100")]);
+
+        let planned =
+            generate_meal_plan(&[3, 2, 1], &calories, &[], &target(vec![MealSlot::Lunch]));
+        let order: Vec<u32> = planned.iter().map(|m| m.recipe_id).collect();
+
+        // 1900 et 2100 sont à égale distance de 2000 : le plus petit recipe_id l'emporte
+        assert_eq!(order, vec![1, 2, 3, 1, 2, 3, 1]);
+        assert!(planned.iter().all(|m| m.servings == 2));
+        assert_eq!(planned[6].plan_date, date(7));
+    }
+
+    #[test]
+    fn existing_slots_are_kept_and_counted() {
+        let calories = calories(&[(1, "1500"), (2, "500"), (3, "1000")]);
+        let existing = vec![meal(date(1), MealSlot::Lunch, 1)];
+
+        let planned = generate_meal_plan(
+            &[1, 2, 3],
+            &calories,
+            &existing,
+            &target(vec![MealSlot::Lunch, MealSlot::Dinner]),
+        );
+
+        let first_day: Vec<&NewMealPlan> =
+            planned.iter().filter(|m| m.plan_date == date(1)).collect();
+        assert_eq!(first_day.len(), 1);
+        assert_eq!(first_day[0].meal_slot, MealSlot::Dinner);
+        // Il reste 500 kcal pour le dîner du premier jour
+        assert_eq!(first_day[0].recipe_id, 2);
+        // La recette déjà planifiée compte comme servie : la 3 passe avant elle le lendemain
+        assert_eq!(planned[1].plan_date, date(2));
+        assert_eq!(planned[1].recipe_id, 3);
+        assert_eq!(planned.len(), 13);
+    }
+
+    #[test]
+    fn group_by_day_sums_calories_per_date() {
+        let calories = calories(&[(1, "512.346"), (2, "100")]);
+        let meals = vec![
+            meal(date(1), MealSlot::Lunch, 1),
+            meal(date(1), MealSlot::Dinner, 2),
+            meal(date(2), MealSlot::Lunch, 3),
+        ];
+
+        let days = group_by_day(meals, &calories);
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].meals.len(), 2);
+        assert_eq!(days[0].calories, dec("612.35"));
+        assert_eq!(days[1].calories, Decimal::ZERO);
+    }
+}
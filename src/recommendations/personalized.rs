@@ -0,0 +1,418 @@
+use crate::models::{RecipeWithIngredients, RecommendedRecipe, StockItem, UserRecipeHistory};
+use crate::recommendations::cookable::available_quantities;
+use crate::utils::units::to_base_quantity;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// Poids des différents critères du score
+const PREFERRED_INGREDIENT_WEIGHT: f64 = 1.0;
+const MAX_PREFERRED_INGREDIENTS: usize = 3;
+const PREFERRED_CATEGORY_WEIGHT: f64 = 0.5;
+const MAX_PREFERRED_CATEGORY_INGREDIENTS: usize = 2;
+const PANTRY_WEIGHT: f64 = 2.0;
+const LIKED_SIMILARITY_WEIGHT: f64 = 3.0;
+const DISLIKED_SIMILARITY_WEIGHT: f64 = 2.0;
+const DIFFICULTY_WEIGHT: f64 = 1.0;
+const ALREADY_COOKED_PENALTY: f64 = 1.0;
+
+// En dessous de ce seuil, deux recettes ne sont pas présentées comme similaires
+const SIMILARITY_THRESHOLD: f64 = 0.3;
+
+/// Ce que l'on sait de l'utilisateur pour personnaliser les recommandations
+pub struct UserProfile<'a> {
+    pub history: &'a [UserRecipeHistory],
+    pub preferred_ingredients: &'a HashSet<u32>,
+    /// Ingrédients appartenant à une catégorie préférée, avec le nom de cette catégorie
+    pub preferred_category_ingredients: &'a HashMap<u32, String>,
+    pub stock: &'a [StockItem],
+    /// Recettes incompatibles avec les allergies ou exclusions de l'utilisateur
+    pub excluded_recipes: &'a HashSet<u32>,
+}
+
+// Recette notée par l'utilisateur, avec ses ingrédients
+struct RatedRecipe<'a> {
+    title: &'a str,
+    rating: Decimal,
+    ingredients: &'a HashSet<u32>,
+}
+
+/// Classe les recettes publiées pour l'utilisateur et explique chaque score.
+///
+/// Le score additionne : ingrédients préférés (directement ou via une catégorie),
+/// part des ingrédients obligatoires présents dans le stock, ressemblance (indice de
+/// Jaccard sur les ingrédients) avec les recettes notées 4 ou plus, moins la ressemblance
+/// avec celles notées 2 ou moins, et habitude de difficulté. Une recette déjà réalisée
+/// est légèrement pénalisée pour favoriser la découverte.
+///
+/// Le résultat ne dépend que des données fournies : tri par score décroissant puis
+/// par `recipe_id`.
+pub fn recommend_recipes(
+    recipes: Vec<RecipeWithIngredients>,
+    profile: &UserProfile,
+    limit: usize,
+) -> Vec<RecommendedRecipe> {
+    let ingredient_sets: HashMap<u32, HashSet<u32>> = recipes
+        .iter()
+        .map(|r| {
+            let ids = r.ingredients.iter().map(|i| i.ingredient_id).collect();
+            (r.recipe.recipe_id, ids)
+        })
+        .collect();
+
+    let rated: Vec<(u32, RatedRecipe)> = profile
+        .history
+        .iter()
+        .filter_map(|h| {
+            let rating = h.average_rating?;
+            let ingredients = ingredient_sets.get(&h.recipe_id)?;
+            Some((
+                h.recipe_id,
+                RatedRecipe {
+                    title: &h.title,
+                    rating,
+                    ingredients,
+                },
+            ))
+        })
+        .collect();
+
+    let cooked: HashMap<u32, i64> = profile
+        .history
+        .iter()
+        .map(|h| (h.recipe_id, h.completion_count))
+        .collect();
+
+    let difficulty_shares = difficulty_shares(profile.history);
+    let usual_difficulty = difficulty_shares
+        .iter()
+        .max_by(|a, b| {
+            a.1.partial_cmp(b.1)
+                .unwrap_or(Ordering::Equal)
+                .then(b.0.cmp(a.0))
+        })
+        .map(|(difficulty, _)| *difficulty);
+
+    let available = available_quantities(profile.stock);
+
+    let mut recommended: Vec<RecommendedRecipe> = recipes
+        .into_iter()
+        .filter(|r| !profile.excluded_recipes.contains(&r.recipe.recipe_id))
+        .map(|recipe| {
+            let mut score = 0.0;
+            let mut reasons = Vec::new();
+            let ingredients = &ingredient_sets[&recipe.recipe.recipe_id];
+
+            // Ingrédients préférés
+            let preferred = ingredients
+                .iter()
+                .filter(|id| profile.preferred_ingredients.contains(id))
+                .count();
+            if preferred > 0 {
+                score +=
+                    PREFERRED_INGREDIENT_WEIGHT * preferred.min(MAX_PREFERRED_INGREDIENTS) as f64;
+                reasons.push(format!(
+                    "uses {} preferred ingredient{}",
+                    preferred,
+                    if preferred > 1 { "s" } else { "" }
+                ));
+            }
+
+            // Ingrédients des catégories préférées (hors ingrédients déjà comptés)
+            let categories: Vec<&String> = ingredients
+                .iter()
+                .filter(|id| !profile.preferred_ingredients.contains(id))
+                .filter_map(|id| profile.preferred_category_ingredients.get(id))
+                .collect();
+            if !categories.is_empty() {
+                score += PREFERRED_CATEGORY_WEIGHT
+                    * categories.len().min(MAX_PREFERRED_CATEGORY_INGREDIENTS) as f64;
+                let names: BTreeSet<&str> = categories.iter().map(|c| c.as_str()).collect();
+                reasons.push(format!(
+                    "contains ingredients from preferred categories: {}",
+                    names.into_iter().collect::<Vec<_>>().join(", ")
+                ));
+            }
+
+            // Couverture par le stock
+            let (covered, required) = pantry_coverage(&recipe, &available);
+            if required > 0 {
+                let coverage = covered as f64 / required as f64;
+                score += PANTRY_WEIGHT * coverage;
+                if coverage >= 0.5 {
+                    reasons.push(format!(
+                        "you have {} of {} ingredients in your pantry",
+                        covered, required
+                    ));
+                }
+            }
+
+            // Ressemblance avec les recettes notées
+            let mut liked: Option<(f64, &RatedRecipe)> = None;
+            let mut disliked = 0.0_f64;
+
+            for (rated_id, rated_recipe) in &rated {
+                if *rated_id == recipe.recipe.recipe_id {
+                    continue;
+                }
+
+                let similarity = jaccard(ingredients, rated_recipe.ingredients);
+
+                if rated_recipe.rating >= Decimal::from(4) {
+                    let weighted = similarity * rating_ratio(rated_recipe.rating);
+                    if liked.is_none_or(|(best, _)| weighted > best) {
+                        liked = Some((weighted, rated_recipe));
+                    }
+                } else if rated_recipe.rating <= Decimal::from(2) {
+                    disliked = disliked.max(similarity);
+                }
+            }
+
+            if let Some((weighted, rated_recipe)) = liked {
+                score += LIKED_SIMILARITY_WEIGHT * weighted;
+                if jaccard(ingredients, rated_recipe.ingredients) >= SIMILARITY_THRESHOLD {
+                    reasons.push(format!(
+                        "similar to \"{}\", which you rated {}",
+                        rated_recipe.title,
+                        rated_recipe.rating.normalize()
+                    ));
+                }
+            }
+            score -= DISLIKED_SIMILARITY_WEIGHT * disliked;
+
+            // Habitudes de difficulté
+            let difficulty = recipe.recipe.difficulty.as_str();
+            if let Some(share) = difficulty_shares.get(difficulty) {
+                score += DIFFICULTY_WEIGHT * share;
+                if usual_difficulty == Some(difficulty) {
+                    reasons.push(format!(
+                        "matches the {} difficulty you usually cook",
+                        difficulty
+                    ));
+                }
+            }
+
+            if cooked.contains_key(&recipe.recipe.recipe_id) {
+                score -= ALREADY_COOKED_PENALTY;
+            }
+
+            RecommendedRecipe {
+                recipe: recipe.recipe,
+                score: (score * 100.0).round() / 100.0,
+                reasons,
+            }
+        })
+        .collect();
+
+    recommended.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then(a.recipe.recipe_id.cmp(&b.recipe.recipe_id))
+    });
+    recommended.truncate(limit);
+
+    recommended
+}
+
+// Part des complétions de l'utilisateur pour chaque difficulté
+fn difficulty_shares(history: &[UserRecipeHistory]) -> HashMap<&str, f64> {
+    let total: i64 = history.iter().map(|h| h.completion_count).sum();
+    let mut shares: HashMap<&str, f64> = HashMap::new();
+
+    if total == 0 {
+        return shares;
+    }
+
+    for entry in history {
+        *shares.entry(entry.difficulty.as_str()).or_default() +=
+            entry.completion_count as f64 / total as f64;
+    }
+
+    shares
+}
+
+// Nombre d'ingrédients obligatoires disponibles en quantité suffisante, sur le total
+fn pantry_coverage(
+    recipe: &RecipeWithIngredients,
+    available: &HashMap<u32, Decimal>,
+) -> (usize, usize) {
+    let required: Vec<_> = recipe
+        .ingredients
+        .iter()
+        .filter(|i| !i.is_optional)
+        .collect();
+
+    let covered = required
+        .iter()
+        .filter(|i| {
+            let needed = to_base_quantity(i.quantity, &i.measurement_unit);
+            available
+                .get(&i.ingredient_id)
+                .is_some_and(|in_stock| *in_stock >= needed)
+        })
+        .count();
+
+    (covered, required.len())
+}
+
+fn jaccard(a: &HashSet<u32>, b: &HashSet<u32>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn rating_ratio(rating: Decimal) -> f64 {
+    rating.to_f64().unwrap_or_default() / 5.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::{ingredient, recipe_with, stock};
+
+    // Chaque ingrédient est obligatoire et utilisé à raison de 100 g
+    fn recipe(recipe_id: u32, difficulty: &str, ingredient_ids: &[u32]) -> RecipeWithIngredients {
+        let ingredients = ingredient_ids
+            .iter()
+            .map(|id| ingredient(*id, "100", "grams"))
+            .collect();
+
+        let mut recipe = recipe_with(recipe_id, ingredients);
+        recipe.recipe.difficulty = difficulty.to_string();
+        recipe
+    }
+
+    fn history(
+        recipe_id: u32,
+        difficulty: &str,
+        completions: i64,
+        rating: Option<i64>,
+    ) -> UserRecipeHistory {
+        UserRecipeHistory {
+            recipe_id,
+            title: format!("Recipe {}", recipe_id),
+            difficulty: difficulty.to_string(),
+            completion_count: completions,
+            average_rating: rating.map(Decimal::from),
+        }
+    }
+
+    fn recipes() -> Vec<RecipeWithIngredients> {
+        vec![
+            recipe(7, "Medium", &[11]),
+            recipe(1, "Easy", &[1, 2]),
+            recipe(2, "Medium", &[1, 3]),
+            recipe(3, "Easy", &[4, 5]),
+            recipe(4, "Hard", &[1, 6]),
+            recipe(5, "Medium", &[4, 9]),
+            recipe(6, "Medium", &[8]),
+        ]
+    }
+
+    fn recommend(limit: usize) -> Vec<RecommendedRecipe> {
+        recommend_from(recipes(), limit)
+    }
+
+    fn recommend_from(recipes: Vec<RecipeWithIngredients>, limit: usize) -> Vec<RecommendedRecipe> {
+        // Recette 3 réalisée trois fois et notée 5 ; recette 10 absente du catalogue
+        let history = vec![
+            history(3, "Easy", 3, Some(5)),
+            history(10, "Medium", 1, None),
+        ];
+        let preferred_ingredients = HashSet::from([1]);
+        let preferred_category_ingredients =
+            HashMap::from([(3, "Dairy".to_string()), (5, "Vegetables".to_string())]);
+        let stock = vec![stock(2, "0.1", "kilograms")];
+        let excluded_recipes = HashSet::from([4]);
+
+        let profile = UserProfile {
+            history: &history,
+            preferred_ingredients: &preferred_ingredients,
+            preferred_category_ingredients: &preferred_category_ingredients,
+            stock: &stock,
+            excluded_recipes: &excluded_recipes,
+        };
+
+        recommend_recipes(recipes, &profile, limit)
+    }
+
+    fn find(recommended: &[RecommendedRecipe], recipe_id: u32) -> &RecommendedRecipe {
+        recommended
+            .iter()
+            .find(|r| r.recipe.recipe_id == recipe_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn orders_by_score_then_recipe_id() {
+        let recommended = recommend(10);
+
+        let order: Vec<u32> = recommended.iter().map(|r| r.recipe.recipe_id).collect();
+        let scores: Vec<f64> = recommended.iter().map(|r| r.score).collect();
+
+        // 3, 6 et 7 sont à égalité : départagés par recipe_id ; la recette 4 est exclue
+        assert_eq!(order, vec![1, 2, 5, 3, 6, 7]);
+        assert_eq!(scores, vec![2.75, 1.75, 1.25, 0.25, 0.25, 0.25]);
+    }
+
+    #[test]
+    fn output_does_not_depend_on_input_order() {
+        let mut reversed = recipes();
+        reversed.reverse();
+
+        let first: Vec<u32> = recommend(10).iter().map(|r| r.recipe.recipe_id).collect();
+        let second: Vec<u32> = recommend_from(reversed, 10)
+            .iter()
+            .map(|r| r.recipe.recipe_id)
+            .collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn explains_each_score() {
+        let recommended = recommend(10);
+
+        assert_eq!(
+            find(&recommended, 1).reasons,
+            vec![
+                "uses 1 preferred ingredient",
+                "you have 1 of 2 ingredients in your pantry",
+                "matches the Easy difficulty you usually cook",
+            ]
+        );
+        assert_eq!(
+            find(&recommended, 2).reasons,
+            vec![
+                "uses 1 preferred ingredient",
+                "contains ingredients from preferred categories: Dairy",
+            ]
+        );
+        assert_eq!(
+            find(&recommended, 5).reasons,
+            vec!["similar to \"Recipe 3\", which you rated 5"]
+        );
+        // Recette déjà réalisée : pénalisée mais toujours expliquée
+        assert_eq!(
+            find(&recommended, 3).reasons,
+            vec![
+                "contains ingredients from preferred categories: Vegetables",
+                "matches the Easy difficulty you usually cook",
+            ]
+        );
+        assert!(find(&recommended, 6).reasons.is_empty());
+    }
+
+    #[test]
+    fn truncates_to_limit() {
+        let order: Vec<u32> = recommend(2).iter().map(|r| r.recipe.recipe_id).collect();
+        assert_eq!(order, vec![1, 2]);
+
+        assert!(recommend(0).is_empty());
+    }
+}
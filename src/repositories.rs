@@ -1,14 +1,36 @@
+pub mod allergy_repository;
+pub mod audit_repository;
+pub mod email_verification_repository;
+pub mod error_log_repository;
 pub mod image_repository;
 pub mod ingredient_categories_repository;
 pub mod ingredient_repository;
+pub mod login_throttle_repository;
+pub mod meal_plan_repository;
+pub mod password_reset_repository;
+pub mod permission_repository;
 pub mod recipe_repository;
+pub mod session_repository;
+pub mod shopping_list_repository;
+pub mod stock_repository;
 pub mod user_preferences_repository;
 pub mod user_repository;
 
 // Ré-exporter les structs pour simplifier les imports
+pub use allergy_repository::AllergyRepository;
+pub use audit_repository::AuditRepository;
+pub use email_verification_repository::EmailVerificationRepository;
+pub use error_log_repository::ErrorLogRepository;
 pub use image_repository::ImageRepository;
 pub use ingredient_categories_repository::IngredientCategoryRepository;
 pub use ingredient_repository::IngredientRepository;
+pub use login_throttle_repository::LoginThrottleRepository;
+pub use meal_plan_repository::MealPlanRepository;
+pub use password_reset_repository::PasswordResetRepository;
+pub use permission_repository::PermissionRepository;
 pub use recipe_repository::RecipeRepository;
+pub use session_repository::SessionRepository;
+pub use shopping_list_repository::ShoppingListRepository;
+pub use stock_repository::StockRepository;
 pub use user_preferences_repository::UserPreferencesRepository;
 pub use user_repository::UserRepository;
@@ -0,0 +1,270 @@
+use crate::{
+    models::{Allergy, AllergyWithIngredients, Ingredient, Severity, UserAllergyDetail},
+    repositories::IngredientRepository,
+};
+use chrono::Utc;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct AllergyRepository {
+    pool: MySqlPool,
+}
+
+impl AllergyRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_allergy(row: &MySqlRow) -> Allergy {
+        // Convertir TIMESTAMP en NaiveDateTime
+        let created_at: chrono::DateTime<Utc> = row.get(3);
+        let updated_at: chrono::DateTime<Utc> = row.get(4);
+
+        Allergy {
+            allergy_id: row.get(0),
+            name: row.get(1),
+            description: row.get(2),
+            created_at: created_at.naive_utc(),
+            updated_at: updated_at.naive_utc(),
+        }
+    }
+
+    pub fn get_severity(severity: &str) -> Severity {
+        match severity {
+            "Mild" => Severity::Mild,
+            "Moderate" => Severity::Moderate,
+            "Severe" => Severity::Severe,
+            "Life-threatening" => Severity::LifeThreatening,
+            _ => Severity::Moderate,
+        }
+    }
+
+    fn get_user_allergy_detail(row: &MySqlRow) -> UserAllergyDetail {
+        let severity_str: String = row.get(2);
+        let added_date: chrono::DateTime<Utc> = row.get(3);
+
+        UserAllergyDetail {
+            allergy_id: row.get(0),
+            allergy_name: row.get(1),
+            severity: Self::get_severity(&severity_str),
+            allergy_added_date: added_date.naive_utc(),
+        }
+    }
+
+    async fn read_error_message(
+        conn: &mut sqlx::pool::PoolConnection<sqlx::MySql>,
+    ) -> Result<(), Error> {
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut **conn)
+            .await?;
+
+        match error_message {
+            None => Ok(()),
+            Some(error_msg) => Err(Error::Protocol(error_msg)),
+        }
+    }
+
+    // =====================================================
+    // CATALOGUE D'ALLERGIES
+    // =====================================================
+
+    pub async fn get_all(&self) -> Result<Vec<Allergy>, Error> {
+        let allergies = sqlx::query("CALL sp_get_all_allergies(@p_error_message)")
+            .map(|row: MySqlRow| Self::get_allergy(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(allergies)
+    }
+
+    pub async fn find_by_id(
+        &self,
+        allergy_id: u32,
+    ) -> Result<Option<AllergyWithIngredients>, Error> {
+        let results = sqlx::query("CALL sp_get_allergy_by_id(?, @p_error_message)")
+            .bind(allergy_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        if results.is_empty() {
+            return Ok(None);
+        }
+
+        // Le premier résultat contient l'allergie, les suivants les ingrédients
+        let allergy = Self::get_allergy(&results[0]);
+        let ingredients: Vec<Ingredient> = results
+            .iter()
+            .skip(1)
+            .map(IngredientRepository::get_ingredient)
+            .collect();
+
+        Ok(Some(AllergyWithIngredients {
+            allergy,
+            ingredients,
+        }))
+    }
+
+    pub async fn create(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        created_by_user_id: u32,
+    ) -> Result<u32, Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_create_allergy(?, ?, ?, @p_allergy_id, @p_error_message)")
+            .bind(name)
+            .bind(description)
+            .bind(created_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_allergy_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(allergy_id), None) => Ok(allergy_id as u32),
+            (None, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (Some(_), Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error during allergy creation".to_string(),
+            )),
+        }
+    }
+
+    pub async fn update(
+        &self,
+        allergy_id: u32,
+        name: &str,
+        description: Option<&str>,
+        updated_by_user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_update_allergy(?, ?, ?, ?, @p_error_message)")
+            .bind(allergy_id)
+            .bind(name)
+            .bind(description)
+            .bind(updated_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+
+    pub async fn delete(&self, allergy_id: u32, deleted_by_user_id: u32) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_delete_allergy(?, ?, @p_error_message)")
+            .bind(allergy_id)
+            .bind(deleted_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+
+    // =====================================================
+    // ALLERGÈNES DES INGRÉDIENTS
+    // =====================================================
+
+    pub async fn add_ingredient(
+        &self,
+        allergy_id: u32,
+        ingredient_id: u32,
+        user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_add_ingredient_allergy(?, ?, ?, @p_error_message)")
+            .bind(allergy_id)
+            .bind(ingredient_id)
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+
+    pub async fn remove_ingredient(
+        &self,
+        allergy_id: u32,
+        ingredient_id: u32,
+        user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_remove_ingredient_allergy(?, ?, ?, @p_error_message)")
+            .bind(allergy_id)
+            .bind(ingredient_id)
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+
+    pub async fn get_ingredients(&self, allergy_id: u32) -> Result<Vec<Ingredient>, Error> {
+        let ingredients = sqlx::query("CALL sp_get_allergy_ingredients(?, @p_error_message)")
+            .bind(allergy_id)
+            .map(|row: MySqlRow| IngredientRepository::get_ingredient(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ingredients)
+    }
+
+    // =====================================================
+    // ALLERGIES DES UTILISATEURS
+    // =====================================================
+
+    pub async fn get_user_allergies(&self, user_id: u32) -> Result<Vec<UserAllergyDetail>, Error> {
+        let allergies = sqlx::query("CALL sp_get_user_allergies(?, @p_error_message)")
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_user_allergy_detail(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(allergies)
+    }
+
+    pub async fn set_user_allergy(
+        &self,
+        user_id: u32,
+        allergy_id: u32,
+        severity: &Severity,
+    ) -> Result<(), Error> {
+        let severity_str = match severity {
+            Severity::Mild => "Mild",
+            Severity::Moderate => "Moderate",
+            Severity::Severe => "Severe",
+            Severity::LifeThreatening => "Life-threatening",
+        };
+
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_set_user_allergy(?, ?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(allergy_id)
+            .bind(severity_str)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+
+    pub async fn remove_user_allergy(&self, user_id: u32, allergy_id: u32) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_remove_user_allergy(?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(allergy_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+}
@@ -0,0 +1,301 @@
+use crate::models::{
+    AuditDeletion, AuditFilter, AuditImpersonation, ChangeType, Gender, HistoryIngredient,
+    HistoryRecipe, HistoryUser, Role,
+};
+use chrono::Utc;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct AuditRepository {
+    pool: MySqlPool,
+}
+
+impl AuditRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn change_type_str(change_type: &ChangeType) -> &'static str {
+        match change_type {
+            ChangeType::Insert => "INSERT",
+            ChangeType::Update => "UPDATE",
+            ChangeType::Delete => "DELETE",
+        }
+    }
+
+    fn get_audit_deletion(row: &MySqlRow) -> AuditDeletion {
+        // Convertir TIMESTAMP en NaiveDateTime
+        let deleted_at: chrono::DateTime<Utc> = row.get(5);
+
+        AuditDeletion {
+            audit_id: row.get(0),
+            table_name: row.get(1),
+            record_id: row.get(2),
+            deleted_data: row.get(3),
+            deleted_by_user_id: row.get(4),
+            deleted_at: deleted_at.naive_utc(),
+            ip_address: row.get(6),
+            user_agent: row.get(7),
+        }
+    }
+
+    fn get_history_user(row: &MySqlRow) -> HistoryUser {
+        let gender_str: Option<String> = row.get(4);
+        let role_str: Option<String> = row.get(6);
+        let changed_at: chrono::DateTime<Utc> = row.get(13);
+
+        HistoryUser {
+            history_id: row.get(0),
+            user_id: row.get(1),
+            first_name: row.get(2),
+            last_name: row.get(3),
+            gender: gender_str.map(|g| match g.as_str() {
+                "Male" => Gender::Male,
+                "Female" => Gender::Female,
+                _ => Gender::Other,
+            }),
+            email: row.get(5),
+            role: role_str.map(|r| r.parse().unwrap_or(Role::Regular)),
+            country: row.get(7),
+            city: row.get(8),
+            is_active: row.get(9),
+            birth_date: row.get(10),
+            change_type: row.get(11),
+            changed_by_user_id: row.get(12),
+            changed_at: changed_at.naive_utc(),
+            change_details: row.get(14),
+        }
+    }
+
+    fn get_history_recipe(row: &MySqlRow) -> HistoryRecipe {
+        let changed_at: chrono::DateTime<Utc> = row.get(10);
+
+        HistoryRecipe {
+            history_id: row.get(0),
+            recipe_id: row.get(1),
+            title: row.get(2),
+            description: row.get(3),
+            servings: row.get(4),
+            is_published: row.get(5),
+            difficulty: row.get(6),
+            author_user_id: row.get(7),
+            change_type: row.get(8),
+            changed_by_user_id: row.get(9),
+            changed_at: changed_at.naive_utc(),
+            change_details: row.get(11),
+        }
+    }
+
+    fn get_history_ingredient(row: &MySqlRow) -> HistoryIngredient {
+        let changed_at: chrono::DateTime<Utc> = row.get(11);
+
+        HistoryIngredient {
+            history_id: row.get(0),
+            ingredient_id: row.get(1),
+            name: row.get(2),
+            carbohydrates: row.get(3),
+            proteins: row.get(4),
+            fats: row.get(5),
+            fibers: row.get(6),
+            calories: row.get(7),
+            price: row.get(8),
+            change_type: row.get(9),
+            changed_by_user_id: row.get(10),
+            changed_at: changed_at.naive_utc(),
+            change_details: row.get(12),
+        }
+    }
+
+    fn get_audit_impersonation(row: &MySqlRow) -> AuditImpersonation {
+        let requested_at: chrono::DateTime<Utc> = row.get(7);
+
+        AuditImpersonation {
+            log_id: row.get(0),
+            impersonator_user_id: row.get(1),
+            impersonated_user_id: row.get(2),
+            session_id: row.get(3),
+            http_method: row.get(4),
+            request_path: row.get(5),
+            ip_address: row.get(6),
+            requested_at: requested_at.naive_utc(),
+        }
+    }
+
+    pub async fn get_deletions(
+        &self,
+        filter: &AuditFilter,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<AuditDeletion>, i64), Error> {
+        let results =
+            sqlx::query("CALL sp_get_audit_deletions(?, ?, ?, ?, ?, ?, ?, @p_error_message)")
+                .bind(filter.table.as_deref())
+                .bind(filter.entity_id)
+                .bind(filter.user_id)
+                .bind(filter.from)
+                .bind(filter.to)
+                .bind(page)
+                .bind(page_size)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(Self::paginated(&results, Self::get_audit_deletion))
+    }
+
+    pub async fn get_user_history(
+        &self,
+        filter: &AuditFilter,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<HistoryUser>, i64), Error> {
+        let results = self
+            .call_history("sp_get_user_history", filter, page, page_size)
+            .await?;
+
+        Ok(Self::paginated(&results, Self::get_history_user))
+    }
+
+    pub async fn get_recipe_history(
+        &self,
+        filter: &AuditFilter,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<HistoryRecipe>, i64), Error> {
+        let results = self
+            .call_history("sp_get_recipe_history", filter, page, page_size)
+            .await?;
+
+        Ok(Self::paginated(&results, Self::get_history_recipe))
+    }
+
+    pub async fn get_ingredient_history(
+        &self,
+        filter: &AuditFilter,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<HistoryIngredient>, i64), Error> {
+        let results = self
+            .call_history("sp_get_ingredient_history", filter, page, page_size)
+            .await?;
+
+        Ok(Self::paginated(&results, Self::get_history_ingredient))
+    }
+
+    /// Filtres : entity_id = utilisateur usurpé, user_id = administrateur
+    pub async fn get_impersonations(
+        &self,
+        filter: &AuditFilter,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<AuditImpersonation>, i64), Error> {
+        let results =
+            sqlx::query("CALL sp_get_audit_impersonations(?, ?, ?, ?, ?, ?, @p_error_message)")
+                .bind(filter.entity_id)
+                .bind(filter.user_id)
+                .bind(filter.from)
+                .bind(filter.to)
+                .bind(page)
+                .bind(page_size)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(Self::paginated(&results, Self::get_audit_impersonation))
+    }
+
+    pub async fn record_impersonation(
+        &self,
+        impersonator_user_id: u32,
+        impersonated_user_id: u32,
+        session_id: Option<u32>,
+        http_method: &str,
+        request_path: &str,
+        ip_address: Option<&str>,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_record_impersonation(?, ?, ?, ?, ?, ?, @p_error_message)")
+            .bind(impersonator_user_id)
+            .bind(impersonated_user_id)
+            .bind(session_id)
+            .bind(http_method)
+            .bind(request_path)
+            .bind(ip_address)
+            .execute(&mut *conn)
+            .await?;
+
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut *conn)
+            .await?;
+
+        match error_message {
+            None => Ok(()),
+            Some(error_msg) => Err(Error::Protocol(error_msg)),
+        }
+    }
+
+    /// Recrée l'enregistrement archivé ; renvoie (table_name, record_id)
+    pub async fn restore(
+        &self,
+        audit_id: u32,
+        restored_by_user_id: u32,
+    ) -> Result<(String, u32), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            "CALL sp_restore_deletion(?, ?, @p_table_name, @p_record_id, @p_error_message)",
+        )
+        .bind(audit_id)
+        .bind(restored_by_user_id)
+        .execute(&mut *conn)
+        .await?;
+
+        let result: (Option<String>, Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_table_name, @p_record_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1), row.get(2)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(table_name), Some(record_id), None) => Ok((table_name, record_id as u32)),
+            (_, _, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            _ => Err(Error::Protocol(
+                "Unknown error during restoration".to_string(),
+            )),
+        }
+    }
+
+    // Les trois procédures d'historique partagent la même signature
+    async fn call_history(
+        &self,
+        procedure: &str,
+        filter: &AuditFilter,
+        page: i32,
+        page_size: i32,
+    ) -> Result<Vec<MySqlRow>, Error> {
+        let sql = format!("CALL {}(?, ?, ?, ?, ?, ?, ?, @p_error_message)", procedure);
+
+        sqlx::query(&sql)
+            .bind(filter.entity_id)
+            .bind(filter.user_id)
+            .bind(filter.change_type.as_ref().map(Self::change_type_str))
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(page)
+            .bind(page_size)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    // Le premier résultat contient le count total, les suivants les lignes
+    fn paginated<T>(results: &[MySqlRow], map: fn(&MySqlRow) -> T) -> (Vec<T>, i64) {
+        let total_count: i64 = if !results.is_empty() {
+            results[0].get(0)
+        } else {
+            0
+        };
+
+        let rows = results.iter().skip(1).map(map).collect();
+
+        (rows, total_count)
+    }
+}
@@ -0,0 +1,130 @@
+use crate::models::{ErrorLog, ErrorLogFilter, ErrorSummary};
+use chrono::Utc;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct ErrorLogRepository {
+    pool: MySqlPool,
+}
+
+impl ErrorLogRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_error_log(row: &MySqlRow) -> ErrorLog {
+        // Convertir TIMESTAMP en NaiveDateTime
+        let occurred_at: chrono::DateTime<Utc> = row.get(6);
+        let resolved_at: Option<chrono::DateTime<Utc>> = row.get(9);
+
+        ErrorLog {
+            error_id: row.get(0),
+            error_type: row.get(1),
+            error_message: row.get(2),
+            error_details: row.get(3),
+            procedure_name: row.get(4),
+            user_id: row.get(5),
+            occurred_at: occurred_at.naive_utc(),
+            ip_address: row.get(7),
+            resolved: row.get(8),
+            resolved_at: resolved_at.map(|d| d.naive_utc()),
+            resolved_by_user_id: row.get(10),
+            resolution_notes: row.get(11),
+        }
+    }
+
+    fn get_error_summary(row: &MySqlRow) -> ErrorSummary {
+        let first_occurrence: chrono::DateTime<Utc> = row.get(6);
+        let last_occurrence: chrono::DateTime<Utc> = row.get(7);
+
+        ErrorSummary {
+            error_date: row.get(0),
+            error_type: row.get(1),
+            procedure_name: row.get(2),
+            error_count: row.get(3),
+            affected_users: row.get(4),
+            unresolved_count: row.get(5),
+            first_occurrence: first_occurrence.naive_utc(),
+            last_occurrence: last_occurrence.naive_utc(),
+        }
+    }
+
+    pub async fn get_all(
+        &self,
+        filter: &ErrorLogFilter,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<ErrorLog>, i64), Error> {
+        let results =
+            sqlx::query("CALL sp_get_error_logs(?, ?, ?, ?, ?, ?, ?, ?, @p_error_message)")
+                .bind(filter.error_type.as_deref())
+                .bind(filter.procedure_name.as_deref())
+                .bind(filter.user_id)
+                .bind(filter.resolved)
+                .bind(filter.from)
+                .bind(filter.to)
+                .bind(page)
+                .bind(page_size)
+                .fetch_all(&self.pool)
+                .await?;
+
+        // Le premier résultat contient le count total, les suivants les lignes
+        let total_count: i64 = if !results.is_empty() {
+            results[0].get(0)
+        } else {
+            0
+        };
+
+        let errors = results.iter().skip(1).map(Self::get_error_log).collect();
+
+        Ok((errors, total_count))
+    }
+
+    pub async fn find_by_id(&self, error_id: u32) -> Result<Option<ErrorLog>, Error> {
+        let error_log = sqlx::query("CALL sp_get_error_log_by_id(?, @p_error_message)")
+            .bind(error_id)
+            .map(|row: MySqlRow| Self::get_error_log(&row))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(error_log)
+    }
+
+    pub async fn get_summary(&self) -> Result<Vec<ErrorSummary>, Error> {
+        let summary = sqlx::query("CALL sp_get_error_summary(@p_error_message)")
+            .map(|row: MySqlRow| Self::get_error_summary(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(summary)
+    }
+
+    pub async fn resolve(
+        &self,
+        error_id: u32,
+        resolved_by_user_id: u32,
+        resolution_notes: Option<&str>,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_resolve_error(?, ?, ?, @p_success, @p_error_message)")
+            .bind(error_id)
+            .bind(resolved_by_user_id)
+            .bind(resolution_notes)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<bool>, Option<String>) =
+            sqlx::query("SELECT @p_success, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(true), None) => Ok(()),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            _ => Err(Error::Protocol(
+                "Unknown error during error log resolution".to_string(),
+            )),
+        }
+    }
+}
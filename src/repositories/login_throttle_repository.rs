@@ -0,0 +1,88 @@
+use crate::utils::auth::LoginThrottlePolicy;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct LoginThrottleRepository {
+    pool: MySqlPool,
+}
+
+impl LoginThrottleRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// Réserve une tentative avant la vérification du mot de passe : elle est comptée comme
+    /// un échec tant que `reset` n'est pas appelé. Renvoie le nombre de secondes à attendre
+    /// si le compte ou l'IP était déjà verrouillé (0 si la tentative peut continuer)
+    pub async fn reserve_attempt(
+        &self,
+        email: &str,
+        ip_address: Option<&str>,
+        user_id: Option<u32>,
+        policy: &LoginThrottlePolicy,
+    ) -> Result<i64, Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            "CALL sp_reserve_login_attempt(?, ?, ?, ?, ?, ?, ?, ?, @p_retry_after, @p_error_message)",
+        )
+        .bind(email)
+        .bind(ip_address)
+        .bind(user_id)
+        .bind(policy.account_threshold)
+        .bind(policy.ip_threshold)
+        .bind(policy.base_lockout_seconds)
+        .bind(policy.max_lockout_seconds)
+        .bind(policy.window_seconds)
+        .execute(&mut *conn)
+        .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_retry_after, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(retry_after), None) => Ok(retry_after.max(0)),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error reserving login attempt".to_string(),
+            )),
+        }
+    }
+
+    /// Connexion réussie : efface les échecs du compte et rend la tentative réservée à l'IP
+    pub async fn reset(&self, email: &str, ip_address: Option<&str>) -> Result<(), Error> {
+        sqlx::query("CALL sp_reset_login_failures(?, ?, @p_error_message)")
+            .bind(email)
+            .bind(ip_address)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn unlock_user(&self, user_id: u32, unlocked_by_user_id: u32) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_unlock_user_login(?, ?, @p_success, @p_error_message)")
+            .bind(user_id)
+            .bind(unlocked_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<bool>, Option<String>) =
+            sqlx::query("SELECT @p_success, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(true), None) => Ok(()),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            _ => Err(Error::Protocol(
+                "Unknown error during account unlock".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::models::{MealPlan, MealSlot, NewMealPlan};
+use chrono::{NaiveDate, Utc};
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct MealPlanRepository {
+    pool: MySqlPool,
+}
+
+impl MealPlanRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn get_meal_slot(meal_slot: &str) -> MealSlot {
+        match meal_slot {
+            "breakfast" => MealSlot::Breakfast,
+            "lunch" => MealSlot::Lunch,
+            "snack" => MealSlot::Snack,
+            _ => MealSlot::Dinner,
+        }
+    }
+
+    fn meal_slot_str(meal_slot: &MealSlot) -> &'static str {
+        match meal_slot {
+            MealSlot::Breakfast => "breakfast",
+            MealSlot::Lunch => "lunch",
+            MealSlot::Dinner => "dinner",
+            MealSlot::Snack => "snack",
+        }
+    }
+
+    fn get_meal_plan(row: &MySqlRow) -> MealPlan {
+        let meal_slot_str: String = row.get(3);
+
+        // Convertir TIMESTAMP en NaiveDateTime
+        let created_at: chrono::DateTime<Utc> = row.get(7);
+        let updated_at: chrono::DateTime<Utc> = row.get(8);
+
+        MealPlan {
+            meal_plan_id: row.get(0),
+            user_id: row.get(1),
+            plan_date: row.get(2),
+            meal_slot: Self::get_meal_slot(&meal_slot_str),
+            recipe_id: row.get(4),
+            recipe_title: row.get(5),
+            servings: row.get(6),
+            created_at: created_at.naive_utc(),
+            updated_at: updated_at.naive_utc(),
+        }
+    }
+
+    // Lire le message d'erreur renvoyé par la procédure sur la même connexion
+    async fn read_error_message(
+        conn: &mut sqlx::pool::PoolConnection<sqlx::MySql>,
+    ) -> Result<(), Error> {
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut **conn)
+            .await?;
+
+        match error_message {
+            None => Ok(()),
+            Some(error_msg) => Err(Error::Protocol(error_msg)),
+        }
+    }
+
+    pub async fn get_user_meal_plans(
+        &self,
+        user_id: u32,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MealPlan>, Error> {
+        let meal_plans = sqlx::query("CALL sp_get_user_meal_plans(?, ?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(start_date)
+            .bind(end_date)
+            .map(|row: MySqlRow| Self::get_meal_plan(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(meal_plans)
+    }
+
+    pub async fn find_by_id(
+        &self,
+        meal_plan_id: u32,
+        user_id: u32,
+    ) -> Result<Option<MealPlan>, Error> {
+        let meal_plan = sqlx::query("CALL sp_get_meal_plan_by_id(?, ?, @p_error_message)")
+            .bind(meal_plan_id)
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_meal_plan(&row))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(meal_plan)
+    }
+
+    pub async fn create(
+        &self,
+        user_id: u32,
+        plan_date: NaiveDate,
+        meal_slot: &MealSlot,
+        recipe_id: u32,
+        servings: u32,
+    ) -> Result<u32, Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_create_meal_plan(?, ?, ?, ?, ?, @p_meal_plan_id, @p_error_message)")
+            .bind(user_id)
+            .bind(plan_date)
+            .bind(Self::meal_slot_str(meal_slot))
+            .bind(recipe_id)
+            .bind(servings)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_meal_plan_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(meal_plan_id), None) => Ok(meal_plan_id as u32),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error during meal plan creation".to_string(),
+            )),
+        }
+    }
+
+    pub async fn update(
+        &self,
+        meal_plan_id: u32,
+        user_id: u32,
+        plan_date: Option<NaiveDate>,
+        meal_slot: Option<&MealSlot>,
+        recipe_id: Option<u32>,
+        servings: Option<u32>,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_update_meal_plan(?, ?, ?, ?, ?, ?, @p_error_message)")
+            .bind(meal_plan_id)
+            .bind(user_id)
+            .bind(plan_date)
+            .bind(meal_slot.map(Self::meal_slot_str))
+            .bind(recipe_id)
+            .bind(servings)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+
+    pub async fn delete(&self, meal_plan_id: u32, user_id: u32) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_delete_meal_plan(?, ?, @p_error_message)")
+            .bind(meal_plan_id)
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+
+    /// Enregistrer les repas générés ; un créneau déjà planifié est remplacé
+    pub async fn save_all(&self, user_id: u32, meals: &[NewMealPlan]) -> Result<(), Error> {
+        let meals_json =
+            serde_json::to_string(meals).map_err(|e| Error::Protocol(e.to_string()))?;
+
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_save_meal_plans(?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(meals_json)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_error_message(&mut conn).await
+    }
+}
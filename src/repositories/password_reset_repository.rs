@@ -0,0 +1,65 @@
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct PasswordResetRepository {
+    pool: MySqlPool,
+}
+
+impl PasswordResetRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// Enregistre un jeton pour l'utilisateur ; les jetons précédents ne sont plus valides
+    pub async fn create(
+        &self,
+        user_id: u32,
+        token: &str,
+        expires_in_seconds: i64,
+        requested_ip: Option<&str>,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_create_password_reset(?, ?, ?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(token)
+            .bind(expires_in_seconds)
+            .bind(requested_ip)
+            .execute(&mut *conn)
+            .await?;
+
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut *conn)
+            .await?;
+
+        match error_message {
+            None => Ok(()),
+            Some(error_msg) => Err(Error::Protocol(error_msg)),
+        }
+    }
+
+    /// Consomme le jeton et remplace le mot de passe ; renvoie l'user_id concerné
+    pub async fn reset_password(&self, token: &str, password_hash: &str) -> Result<u32, Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_reset_password(?, ?, @p_user_id, @p_error_message)")
+            .bind(token)
+            .bind(password_hash)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_user_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(user_id), None) => Ok(user_id as u32),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error during password reset".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::models::{Permission, Role, RolePermission};
+use chrono::Utc;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct PermissionRepository {
+    pool: MySqlPool,
+}
+
+impl PermissionRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_role_permission(row: &MySqlRow) -> RolePermission {
+        let role_str: String = row.get(0);
+        let granted_at: chrono::DateTime<Utc> = row.get(4);
+
+        RolePermission {
+            role: role_str.parse().unwrap_or(Role::Regular),
+            permission_code: row.get(1),
+            description: row.get(2),
+            granted_by_user_id: row.get(3),
+            granted_at: granted_at.naive_utc(),
+        }
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<Permission>, Error> {
+        let permissions = sqlx::query("CALL sp_get_permissions(@p_error_message)")
+            .map(|row: MySqlRow| Permission {
+                permission_code: row.get(0),
+                description: row.get(1),
+            })
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(permissions)
+    }
+
+    /// Permissions accordées à un rôle (à tous les rôles si `role` est None)
+    pub async fn get_role_permissions(
+        &self,
+        role: Option<&Role>,
+    ) -> Result<Vec<RolePermission>, Error> {
+        let permissions = sqlx::query("CALL sp_get_role_permissions(?, @p_error_message)")
+            .bind(role.map(Role::as_str))
+            .map(|row: MySqlRow| Self::get_role_permission(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(permissions)
+    }
+
+    pub async fn has_permission(&self, role: &Role, permission_code: &str) -> Result<bool, Error> {
+        let is_allowed: Option<bool> = sqlx::query("CALL sp_check_permission(?, ?)")
+            .bind(role.as_str())
+            .bind(permission_code)
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(is_allowed.unwrap_or(false))
+    }
+
+    /// Indique si `role` possède toutes les permissions accordées à `other`
+    pub async fn covers_role(&self, role: &Role, other: &Role) -> Result<bool, Error> {
+        let granted = self.get_role_permissions(Some(role)).await?;
+        let required = self.get_role_permissions(Some(other)).await?;
+
+        Ok(required.iter().all(|required| {
+            granted
+                .iter()
+                .any(|granted| granted.permission_code == required.permission_code)
+        }))
+    }
+
+    pub async fn grant(
+        &self,
+        role: &Role,
+        permission_code: &str,
+        granted_by_user_id: u32,
+    ) -> Result<(), Error> {
+        self.update_mapping(
+            "CALL sp_grant_role_permission(?, ?, ?, @p_success, @p_error_message)",
+            role,
+            permission_code,
+            granted_by_user_id,
+        )
+        .await
+    }
+
+    pub async fn revoke(
+        &self,
+        role: &Role,
+        permission_code: &str,
+        revoked_by_user_id: u32,
+    ) -> Result<(), Error> {
+        self.update_mapping(
+            "CALL sp_revoke_role_permission(?, ?, ?, @p_success, @p_error_message)",
+            role,
+            permission_code,
+            revoked_by_user_id,
+        )
+        .await
+    }
+
+    // Les deux procédures partagent la même signature et les mêmes variables de sortie
+    async fn update_mapping(
+        &self,
+        call: &str,
+        role: &Role,
+        permission_code: &str,
+        changed_by_user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(call)
+            .bind(role.as_str())
+            .bind(permission_code)
+            .bind(changed_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<bool>, Option<String>) =
+            sqlx::query("SELECT @p_success, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(true), None) => Ok(()),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            _ => Err(Error::Protocol(
+                "Unknown error updating role permissions".to_string(),
+            )),
+        }
+    }
+}
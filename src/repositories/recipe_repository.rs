@@ -1,7 +1,13 @@
-use crate::models::{Recipe, RecipeIngredientDetail, RecipeStep, RecipeWithIngredients};
+use crate::models::{
+    ExcludedRecipe, ExclusionReason, ExclusionTrigger, PopularRecipe, Recipe, RecipeCompletion,
+    RecipeComplexity, RecipeIngredientDetail, RecipeRating, RecipeReview, RecipeSearchQuery,
+    RecipeStep, RecipeWithIngredients, TrendingRecipe, UserRecipeHistory,
+};
+use crate::repositories::AllergyRepository;
 use chrono::{NaiveDateTime, Utc};
 use rust_decimal::Decimal;
 use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+use std::collections::HashMap;
 
 pub struct RecipeRepository {
     pool: MySqlPool,
@@ -28,6 +34,81 @@ impl RecipeRepository {
             updated_at: updated_at.naive_utc(),
             author_first_name: row.try_get(9).ok(),
             author_last_name: row.try_get(10).ok(),
+            rating: None,
+        }
+    }
+
+    fn get_recipe_rating(row: &MySqlRow) -> RecipeRating {
+        let histogram = (1..=5u8)
+            .zip(4..=8)
+            .map(|(stars, column)| (stars, row.get(column)))
+            .collect();
+
+        RecipeRating {
+            completion_count: row.get(1),
+            rating_count: row.get(2),
+            average_rating: row.get(3),
+            histogram,
+        }
+    }
+
+    // Les indicateurs de complexité terminent les lignes populaires et tendance
+    fn get_recipe_complexity(row: &MySqlRow, offset: usize) -> RecipeComplexity {
+        RecipeComplexity {
+            ingredient_count: row.get(offset),
+            step_count: row.get(offset + 1),
+            total_minutes: row.get(offset + 2),
+            optional_ingredients: row.get(offset + 3),
+            cooking_steps: row.get(offset + 4),
+            action_steps: row.get(offset + 5),
+            longest_step_minutes: row.get(offset + 6),
+        }
+    }
+
+    fn get_popular_recipe(row: &MySqlRow) -> PopularRecipe {
+        PopularRecipe {
+            recipe: Self::get_recipe(row),
+            completion_count: row.get(11),
+            average_rating: row.get(12),
+            unique_users: row.get(13),
+            last_completed: row.get(14),
+            five_star_count: row.get(15),
+            four_plus_star_count: row.get(16),
+            complexity: Self::get_recipe_complexity(row, 17),
+        }
+    }
+
+    fn get_trending_recipe(row: &MySqlRow) -> TrendingRecipe {
+        TrendingRecipe {
+            recipe: Self::get_recipe(row),
+            completion_count: row.get(11),
+            average_rating: row.get(12),
+            unique_users: row.get(13),
+            last_completed: row.get(14),
+            trend_score: row.get(15),
+            complexity: Self::get_recipe_complexity(row, 16),
+        }
+    }
+
+    fn get_recipe_review(row: &MySqlRow) -> RecipeReview {
+        RecipeReview {
+            completion_id: row.get(0),
+            user_id: row.get(1),
+            reviewer_name: row.get(2),
+            rating: row.get(3),
+            comment: row.get(4),
+            completion_date: row.get(5),
+        }
+    }
+
+    fn get_recipe_completion(row: &MySqlRow) -> RecipeCompletion {
+        RecipeCompletion {
+            completion_id: row.get(0),
+            recipe_id: row.get(1),
+            recipe_title: row.get(2),
+            rating: row.get(3),
+            comment: row.get(4),
+            completion_date: row.get(5),
         }
     }
 
@@ -49,10 +130,16 @@ impl RecipeRepository {
         }
     }
 
-    pub async fn get_all(&self, page: i32, page_size: i32) -> Result<(Vec<Recipe>, i64), Error> {
-        let results = sqlx::query("CALL sp_get_all_recipes(?, ?)")
+    pub async fn get_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        max_cost_per_serving: Option<Decimal>,
+    ) -> Result<(Vec<Recipe>, i64), Error> {
+        let results = sqlx::query("CALL sp_get_all_recipes(?, ?, ?)")
             .bind(page)
             .bind(page_size)
+            .bind(max_cost_per_serving)
             .fetch_all(&self.pool)
             .await?;
 
@@ -71,6 +158,111 @@ impl RecipeRepository {
         Ok((recipes, total_count))
     }
 
+    /// Recherche de recettes publiées ; les listes d'ingrédients sont déjà normalisées ("1,4,7")
+    pub async fn search(
+        &self,
+        query: &RecipeSearchQuery,
+        include_ingredients: Option<&str>,
+        exclude_ingredients: Option<&str>,
+    ) -> Result<(Vec<Recipe>, i64), Error> {
+        let results = sqlx::query(
+            "CALL sp_search_recipes(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, @p_error_message)",
+        )
+        .bind(query.q.as_deref())
+        .bind(query.difficulty.as_deref())
+        .bind(query.author_id)
+        .bind(query.max_total_minutes)
+        .bind(include_ingredients)
+        .bind(exclude_ingredients)
+        .bind(query.min_rating)
+        .bind(query.max_cost_per_serving)
+        .bind(query.effective_sort().as_str())
+        .bind(query.page)
+        .bind(query.page_size)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total_count: i64 = if !results.is_empty() {
+            results[0].get(0)
+        } else {
+            0
+        };
+
+        let recipes: Vec<Recipe> = results.iter().skip(1).map(Self::get_recipe).collect();
+
+        Ok((recipes, total_count))
+    }
+
+    /// Recettes publiées compatibles avec les allergies et exclusions de l'utilisateur
+    pub async fn get_for_user(
+        &self,
+        user_id: u32,
+        page: i32,
+        page_size: i32,
+        max_cost_per_serving: Option<Decimal>,
+    ) -> Result<(Vec<Recipe>, i64), Error> {
+        let results = sqlx::query("CALL sp_get_recipes_for_user(?, ?, ?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(page)
+            .bind(page_size)
+            .bind(max_cost_per_serving)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let total_count: i64 = if !results.is_empty() {
+            results[0].get(0)
+        } else {
+            0
+        };
+
+        let recipes: Vec<Recipe> = results.iter().skip(1).map(Self::get_recipe).collect();
+
+        Ok((recipes, total_count))
+    }
+
+    /// Recettes publiées écartées pour l'utilisateur, avec les ingrédients responsables
+    pub async fn get_exclusions_for_user(
+        &self,
+        user_id: u32,
+    ) -> Result<Vec<ExcludedRecipe>, Error> {
+        let results = sqlx::query("CALL sp_get_recipe_exclusions_for_user(?, @p_error_message)")
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        // Les lignes arrivent triées par recette
+        let mut excluded: Vec<ExcludedRecipe> = Vec::new();
+
+        for row in results.iter() {
+            let recipe_id: u32 = row.get(0);
+            let reason_str: String = row.get(4);
+            let severity_str: Option<String> = row.get(6);
+
+            let trigger = ExclusionTrigger {
+                ingredient_id: row.get(2),
+                ingredient_name: row.get(3),
+                reason: match reason_str.as_str() {
+                    "allergy" => ExclusionReason::Allergy,
+                    "excluded_category" => ExclusionReason::ExcludedCategory,
+                    _ => ExclusionReason::ExcludedIngredient,
+                },
+                detail: row.get(5),
+                severity: severity_str.as_deref().map(AllergyRepository::get_severity),
+            };
+
+            match excluded.last_mut() {
+                Some(recipe) if recipe.recipe_id == recipe_id => recipe.triggers.push(trigger),
+                _ => excluded.push(ExcludedRecipe {
+                    recipe_id,
+                    title: row.get(1),
+                    triggers: vec![trigger],
+                }),
+            }
+        }
+
+        Ok(excluded)
+    }
+
     pub async fn find_by_id(&self, recipe_id: u32) -> Result<Option<RecipeWithIngredients>, Error> {
         let results = sqlx::query("CALL sp_get_recipe_by_id(?, @p_error_message)")
             .bind(recipe_id)
@@ -95,6 +287,42 @@ impl RecipeRepository {
         }))
     }
 
+    /// Toutes les recettes publiées avec leurs ingrédients, en un seul appel
+    pub async fn get_published_with_ingredients(
+        &self,
+    ) -> Result<Vec<RecipeWithIngredients>, Error> {
+        let results =
+            sqlx::query("CALL sp_get_published_recipes_with_ingredients(@p_error_message)")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut recipes: Vec<Recipe> = Vec::new();
+        let mut ingredients_by_recipe: HashMap<u32, Vec<RecipeIngredientDetail>> = HashMap::new();
+
+        for row in results.iter() {
+            // Seul le jeu de résultats des recettes a une colonne `title`
+            if row.try_column("title").is_ok() {
+                recipes.push(Self::get_recipe(row));
+            } else {
+                let ingredient = Self::get_recipe_ingredient(row);
+                ingredients_by_recipe
+                    .entry(ingredient.recipe_id)
+                    .or_default()
+                    .push(ingredient);
+            }
+        }
+
+        Ok(recipes
+            .into_iter()
+            .map(|recipe| RecipeWithIngredients {
+                ingredients: ingredients_by_recipe
+                    .remove(&recipe.recipe_id)
+                    .unwrap_or_default(),
+                recipe,
+            })
+            .collect())
+    }
+
     pub async fn get_user_recipes(
         &self,
         user_id: u32,
@@ -334,6 +562,154 @@ impl RecipeRepository {
         }
     }
 
+    /// Rattache à chaque recette ses notes agrégées (moyenne, nombre et histogramme)
+    pub async fn attach_ratings(&self, recipes: &mut [Recipe]) -> Result<(), Error> {
+        if recipes.is_empty() {
+            return Ok(());
+        }
+
+        let recipe_ids = recipes
+            .iter()
+            .map(|r| r.recipe_id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let mut ratings: HashMap<u32, RecipeRating> =
+            sqlx::query("CALL sp_get_recipe_ratings(?, @p_error_message)")
+                .bind(recipe_ids)
+                .map(|row: MySqlRow| (row.get(0), Self::get_recipe_rating(&row)))
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .collect();
+
+        // Une recette jamais réalisée a des compteurs à zéro
+        for recipe in recipes.iter_mut() {
+            let rating = ratings
+                .remove(&recipe.recipe_id)
+                .unwrap_or_else(|| RecipeRating {
+                    histogram: (1..=5u8).map(|stars| (stars, 0)).collect(),
+                    ..Default::default()
+                });
+
+            recipe.rating = Some(rating);
+        }
+
+        Ok(())
+    }
+
+    /// Recettes populaires selon v_popular_recipes (au moins 5 complétions et 3.5 de moyenne)
+    pub async fn get_popular(&self, limit: i32) -> Result<Vec<PopularRecipe>, Error> {
+        let recipes = sqlx::query("CALL sp_get_popular_recipes(?, @p_error_message)")
+            .bind(limit)
+            .map(|row: MySqlRow| Self::get_popular_recipe(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(recipes)
+    }
+
+    /// Recettes les plus réalisées et les mieux notées sur les `days` derniers jours
+    pub async fn get_trending(&self, days: i32, limit: i32) -> Result<Vec<TrendingRecipe>, Error> {
+        let recipes = sqlx::query("CALL sp_get_trending_recipes(?, ?, @p_error_message)")
+            .bind(days)
+            .bind(limit)
+            .map(|row: MySqlRow| Self::get_trending_recipe(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(recipes)
+    }
+
+    /// Complétions de l'utilisateur regroupées par recette, avec sa note moyenne
+    pub async fn get_user_recipe_history(
+        &self,
+        user_id: u32,
+    ) -> Result<Vec<UserRecipeHistory>, Error> {
+        let history = sqlx::query("CALL sp_get_user_recipe_history(?, @p_error_message)")
+            .bind(user_id)
+            .map(|row: MySqlRow| UserRecipeHistory {
+                recipe_id: row.get(0),
+                title: row.get(1),
+                difficulty: row.get(2),
+                completion_count: row.get(3),
+                average_rating: row.get(4),
+            })
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(history)
+    }
+
+    /// Avis (note ou commentaire) laissés sur une recette, du plus récent au plus ancien
+    pub async fn get_reviews(
+        &self,
+        recipe_id: u32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<RecipeReview>, i64), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        let results = sqlx::query("CALL sp_get_recipe_reviews(?, ?, ?, @p_error_message)")
+            .bind(recipe_id)
+            .bind(page)
+            .bind(page_size)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut *conn)
+            .await?;
+
+        if let Some(error_msg) = error_message {
+            return Err(Error::Protocol(error_msg));
+        }
+
+        let total_count: i64 = if !results.is_empty() {
+            results[0].get(0)
+        } else {
+            0
+        };
+
+        let reviews: Vec<RecipeReview> = results
+            .iter()
+            .skip(1)
+            .map(Self::get_recipe_review)
+            .collect();
+
+        Ok((reviews, total_count))
+    }
+
+    /// Historique des recettes réalisées par un utilisateur
+    pub async fn get_user_completions(
+        &self,
+        user_id: u32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<RecipeCompletion>, i64), Error> {
+        let results = sqlx::query("CALL sp_get_user_completions(?, ?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(page)
+            .bind(page_size)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let total_count: i64 = if !results.is_empty() {
+            results[0].get(0)
+        } else {
+            0
+        };
+
+        let completions: Vec<RecipeCompletion> = results
+            .iter()
+            .skip(1)
+            .map(Self::get_recipe_completion)
+            .collect();
+
+        Ok((completions, total_count))
+    }
+
     pub async fn get_recipe_steps(&self, recipe_id: u32) -> Result<Vec<RecipeStep>, Error> {
         let results = sqlx::query("CALL sp_get_recipe_steps(?, @p_error_message)")
             .bind(recipe_id)
@@ -0,0 +1,174 @@
+use crate::models::{Role, UserSession};
+use chrono::Utc;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct SessionRepository {
+    pool: MySqlPool,
+}
+
+impl SessionRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_session(row: &MySqlRow, current_session_id: u32) -> UserSession {
+        // Convertir TIMESTAMP en NaiveDateTime
+        let login_time: chrono::DateTime<Utc> = row.get(3);
+        let last_activity: chrono::DateTime<Utc> = row.get(4);
+        let expires_at: Option<chrono::DateTime<Utc>> = row.get(5);
+        let session_id: u32 = row.get(0);
+
+        UserSession {
+            session_id,
+            ip_address: row.get(1),
+            user_agent: row.get(2),
+            login_time: login_time.naive_utc(),
+            last_activity: last_activity.naive_utc(),
+            expires_at: expires_at.map(|d| d.naive_utc()),
+            is_current: session_id == current_session_id,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: u32,
+        session_token: &str,
+        ip_address: Option<&str>,
+        user_agent: Option<&str>,
+        expires_in_seconds: i64,
+    ) -> Result<u32, Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_create_session(?, ?, ?, ?, ?, @p_session_id, @p_error_message)")
+            .bind(user_id)
+            .bind(session_token)
+            .bind(ip_address)
+            .bind(user_agent)
+            .bind(expires_in_seconds)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_session_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(session_id), None) => Ok(session_id as u32),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error during session creation".to_string(),
+            )),
+        }
+    }
+
+    /// Remplace le refresh token de la session ; renvoie (session_id, user_id)
+    pub async fn refresh(
+        &self,
+        session_token: &str,
+        new_session_token: &str,
+        expires_in_seconds: i64,
+    ) -> Result<(u32, u32), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            "CALL sp_refresh_session(?, ?, ?, @p_session_id, @p_user_id, @p_error_message)",
+        )
+        .bind(session_token)
+        .bind(new_session_token)
+        .bind(expires_in_seconds)
+        .execute(&mut *conn)
+        .await?;
+
+        let result: (Option<i64>, Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_session_id, @p_user_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1), row.get(2)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(session_id), Some(user_id), None) => Ok((session_id as u32, user_id as u32)),
+            (_, _, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            _ => Err(Error::Protocol(
+                "Unknown error during session refresh".to_string(),
+            )),
+        }
+    }
+
+    /// Rôle actuel de l'utilisateur si la session est toujours active (et note l'activité),
+    /// `None` si elle a été révoquée ou a expiré
+    pub async fn touch(&self, session_id: u32, user_id: u32) -> Result<Option<Role>, Error> {
+        let role: Option<Option<String>> = sqlx::query("CALL sp_touch_session(?, ?)")
+            .bind(session_id)
+            .bind(user_id)
+            .map(|row: MySqlRow| row.get(1))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(role.flatten().map(|r| r.parse().unwrap_or(Role::Regular)))
+    }
+
+    pub async fn get_user_sessions(
+        &self,
+        user_id: u32,
+        current_session_id: u32,
+    ) -> Result<Vec<UserSession>, Error> {
+        let sessions = sqlx::query("CALL sp_get_user_sessions(?, @p_error_message)")
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_session(&row, current_session_id))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(sessions)
+    }
+
+    pub async fn revoke(&self, session_id: u32, user_id: u32) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_revoke_session(?, ?, @p_error_message)")
+            .bind(session_id)
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut *conn)
+            .await?;
+
+        match error_message {
+            None => Ok(()),
+            Some(error_msg) => Err(Error::Protocol(error_msg)),
+        }
+    }
+
+    /// Révoque toutes les sessions de l'utilisateur sauf `except_session_id`
+    pub async fn revoke_all(
+        &self,
+        user_id: u32,
+        except_session_id: Option<u32>,
+    ) -> Result<u32, Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_revoke_user_sessions(?, ?, @p_revoked_count, @p_error_message)")
+            .bind(user_id)
+            .bind(except_session_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_revoked_count, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(count), None) => Ok(count as u32),
+            (_, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error during session revocation".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::models::{
+    NewShoppingListItem, ShoppingList, ShoppingListCategory, ShoppingListItem, ShoppingListRecipe,
+    ShoppingListRecipeRequest, ShoppingListSummary,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct ShoppingListRepository {
+    pool: MySqlPool,
+}
+
+impl ShoppingListRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_shopping_list_summary(row: &MySqlRow) -> ShoppingListSummary {
+        // Convertir TIMESTAMP en NaiveDateTime
+        let created_at: chrono::DateTime<Utc> = row.get(5);
+        let updated_at: chrono::DateTime<Utc> = row.get(6);
+
+        ShoppingListSummary {
+            shopping_list_id: row.get(0),
+            name: row.get(1),
+            item_count: row.get(2),
+            checked_count: row.get(3),
+            estimated_total: row.get(4),
+            created_at: created_at.naive_utc(),
+            updated_at: updated_at.naive_utc(),
+        }
+    }
+
+    fn get_shopping_list_item(row: &MySqlRow) -> (Option<String>, ShoppingListItem) {
+        let checked_at: Option<chrono::DateTime<Utc>> = row.get(8);
+
+        let item = ShoppingListItem {
+            shopping_list_item_id: row.get(0),
+            ingredient_id: row.get(1),
+            ingredient_name: row.get(2),
+            quantity: row.get(4),
+            measurement_unit: row.get(5),
+            estimated_cost: row.get(6),
+            is_checked: row.get(7),
+            checked_at: checked_at.map(|d| d.naive_utc()),
+        };
+
+        (row.get(3), item)
+    }
+
+    pub async fn get_user_lists(&self, user_id: u32) -> Result<Vec<ShoppingListSummary>, Error> {
+        let lists = sqlx::query("CALL sp_get_user_shopping_lists(?, @p_error_message)")
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_shopping_list_summary(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(lists)
+    }
+
+    pub async fn find_by_id(
+        &self,
+        shopping_list_id: u32,
+        user_id: u32,
+    ) -> Result<Option<ShoppingList>, Error> {
+        let results = sqlx::query("CALL sp_get_shopping_list(?, ?, @p_error_message)")
+            .bind(shopping_list_id)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        if results.is_empty() {
+            return Ok(None);
+        }
+
+        // En-tête, puis recettes sélectionnées, puis articles
+        let header = &results[0];
+        let created_at: chrono::DateTime<Utc> = header.get(3);
+        let updated_at: chrono::DateTime<Utc> = header.get(4);
+
+        let mut recipes: Vec<ShoppingListRecipe> = Vec::new();
+        let mut categories: Vec<ShoppingListCategory> = Vec::new();
+        let mut estimated_total = Decimal::ZERO;
+        let mut remaining_cost = Decimal::ZERO;
+
+        for row in results.iter().skip(1) {
+            // Seul le jeu de résultats des recettes a une colonne `title`
+            if row.try_column("title").is_ok() {
+                recipes.push(ShoppingListRecipe {
+                    recipe_id: row.get(0),
+                    title: row.get(1),
+                    servings: row.get(2),
+                });
+                continue;
+            }
+
+            let (category_name, item) = Self::get_shopping_list_item(row);
+
+            estimated_total += item.estimated_cost;
+            if !item.is_checked {
+                remaining_cost += item.estimated_cost;
+            }
+
+            // Les articles arrivent triés par catégorie
+            match categories.last_mut() {
+                Some(category) if category.category_name == category_name => {
+                    category.estimated_cost += item.estimated_cost;
+                    category.items.push(item);
+                }
+                _ => categories.push(ShoppingListCategory {
+                    category_name,
+                    estimated_cost: item.estimated_cost,
+                    items: vec![item],
+                }),
+            }
+        }
+
+        Ok(Some(ShoppingList {
+            shopping_list_id: header.get(0),
+            user_id: header.get(1),
+            name: header.get(2),
+            recipes,
+            estimated_total,
+            remaining_cost,
+            categories,
+            created_at: created_at.naive_utc(),
+            updated_at: updated_at.naive_utc(),
+        }))
+    }
+
+    pub async fn create(
+        &self,
+        user_id: u32,
+        name: &str,
+        recipes: &[ShoppingListRecipeRequest],
+        items: &[NewShoppingListItem],
+    ) -> Result<u32, Error> {
+        let recipes_json =
+            serde_json::to_string(recipes).map_err(|e| Error::Protocol(e.to_string()))?;
+        let items_json =
+            serde_json::to_string(items).map_err(|e| Error::Protocol(e.to_string()))?;
+
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            "CALL sp_create_shopping_list(?, ?, ?, ?, @p_shopping_list_id, @p_error_message)",
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(recipes_json)
+        .bind(items_json)
+        .execute(&mut *conn)
+        .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_shopping_list_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(shopping_list_id), None) => Ok(shopping_list_id as u32),
+            (None, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (Some(_), Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error during shopping list creation".to_string(),
+            )),
+        }
+    }
+
+    pub async fn set_item_checked(
+        &self,
+        shopping_list_id: u32,
+        shopping_list_item_id: u32,
+        user_id: u32,
+        is_checked: bool,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_set_shopping_list_item_checked(?, ?, ?, ?, @p_error_message)")
+            .bind(shopping_list_id)
+            .bind(shopping_list_item_id)
+            .bind(user_id)
+            .bind(is_checked)
+            .execute(&mut *conn)
+            .await?;
+
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut *conn)
+            .await?;
+
+        match error_message {
+            None => Ok(()),
+            Some(error_msg) => Err(Error::Protocol(error_msg)),
+        }
+    }
+
+    pub async fn delete(&self, shopping_list_id: u32, user_id: u32) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_delete_shopping_list(?, ?, @p_error_message)")
+            .bind(shopping_list_id)
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let error_message: Option<String> = sqlx::query("SELECT @p_error_message")
+            .map(|row: MySqlRow| row.get(0))
+            .fetch_one(&mut *conn)
+            .await?;
+
+        match error_message {
+            None => Ok(()),
+            Some(error_msg) => Err(Error::Protocol(error_msg)),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::models::{ExpiringStockItem, StockItem, StockSummary};
+use crate::utils::cost::estimate_cost;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
+
+pub struct StockRepository {
+    pool: MySqlPool,
+}
+
+impl StockRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn get_stock_item(row: &MySqlRow) -> StockItem {
+        // Convertir TIMESTAMP en NaiveDateTime
+        let created_at: chrono::DateTime<Utc> = row.get(8);
+        let updated_at: chrono::DateTime<Utc> = row.get(9);
+
+        StockItem {
+            stock_id: row.get(0),
+            user_id: row.get(1),
+            ingredient_id: row.get(2),
+            ingredient_name: row.get(3),
+            quantity: row.get(4),
+            measurement_unit: row.get(5),
+            expiration_date: row.get(6),
+            storage_location: row.get(7),
+            created_at: created_at.naive_utc(),
+            updated_at: updated_at.naive_utc(),
+        }
+    }
+
+    fn get_expiring_stock_item(row: &MySqlRow) -> ExpiringStockItem {
+        let quantity: Decimal = row.get(3);
+        let measurement_unit: String = row.get(8);
+        let price: Option<Decimal> = row.get(9);
+        let weight: Option<Decimal> = row.get(10);
+
+        // Même calcul que le coût des recettes ; sans prix ni poids de référence, valeur nulle
+        let estimated_value = match (price, weight) {
+            (Some(price), Some(weight)) => {
+                estimate_cost(price, weight, quantity, &measurement_unit).round_dp(2)
+            }
+            _ => Decimal::ZERO,
+        };
+
+        ExpiringStockItem {
+            stock_id: row.get(0),
+            ingredient_id: row.get(1),
+            ingredient_name: row.get(2),
+            quantity,
+            measurement_unit,
+            expiration_date: row.get(4),
+            storage_location: row.get(5),
+            days_until_expiration: row.get(6),
+            expiration_status: row.get(7),
+            estimated_value,
+        }
+    }
+
+    fn get_stock_summary(row: &MySqlRow) -> StockSummary {
+        StockSummary {
+            user_id: row.get(0),
+            unique_ingredients: row.get(1),
+            total_items: row.get(2),
+            expired_items: row.get(3),
+            expiring_soon: row.get(4),
+            total_stock_value: row.get(5),
+            storage_locations: row.get(6),
+        }
+    }
+
+    pub async fn get_user_stock(&self, user_id: u32) -> Result<Vec<StockItem>, Error> {
+        let items = sqlx::query("CALL sp_get_user_stock(?, @p_error_message)")
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_stock_item(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(items)
+    }
+
+    pub async fn find_by_id(
+        &self,
+        stock_id: u32,
+        user_id: u32,
+    ) -> Result<Option<StockItem>, Error> {
+        let item = sqlx::query("CALL sp_get_stock_item(?, ?, @p_error_message)")
+            .bind(stock_id)
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_stock_item(&row))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(item)
+    }
+
+    pub async fn get_expiring_stock(
+        &self,
+        user_id: u32,
+        within_days: i32,
+    ) -> Result<Vec<ExpiringStockItem>, Error> {
+        let items = sqlx::query("CALL sp_get_expiring_stock(?, ?, @p_error_message)")
+            .bind(user_id)
+            .bind(within_days)
+            .map(|row: MySqlRow| Self::get_expiring_stock_item(&row))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(items)
+    }
+
+    pub async fn get_summary(&self, user_id: u32) -> Result<StockSummary, Error> {
+        let summary = sqlx::query("CALL sp_get_stock_summary(?, @p_error_message)")
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_stock_summary(&row))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        // La vue ne renvoie aucune ligne pour un stock vide
+        Ok(summary.unwrap_or(StockSummary {
+            user_id,
+            unique_ingredients: 0,
+            total_items: 0,
+            expired_items: 0,
+            expiring_soon: 0,
+            total_stock_value: Decimal::ZERO,
+            storage_locations: 0,
+        }))
+    }
+
+    pub async fn add(
+        &self,
+        user_id: u32,
+        ingredient_id: u32,
+        quantity: Decimal,
+        expiration_date: Option<NaiveDate>,
+        storage_location: Option<&str>,
+    ) -> Result<u32, Error> {
+        self.manage_stock(
+            user_id,
+            ingredient_id,
+            Some(quantity),
+            expiration_date,
+            storage_location,
+            "add",
+        )
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        user_id: u32,
+        ingredient_id: u32,
+        quantity: Decimal,
+        expiration_date: Option<NaiveDate>,
+        storage_location: Option<&str>,
+    ) -> Result<u32, Error> {
+        self.manage_stock(
+            user_id,
+            ingredient_id,
+            Some(quantity),
+            expiration_date,
+            storage_location,
+            "update",
+        )
+        .await
+    }
+
+    /// Retire `quantity` du stock, ou l'élément entier si `quantity` est absent
+    pub async fn remove(
+        &self,
+        user_id: u32,
+        ingredient_id: u32,
+        quantity: Option<Decimal>,
+        storage_location: Option<&str>,
+    ) -> Result<u32, Error> {
+        self.manage_stock(
+            user_id,
+            ingredient_id,
+            quantity,
+            None,
+            storage_location,
+            "remove",
+        )
+        .await
+    }
+
+    async fn manage_stock(
+        &self,
+        user_id: u32,
+        ingredient_id: u32,
+        quantity: Option<Decimal>,
+        expiration_date: Option<NaiveDate>,
+        storage_location: Option<&str>,
+        operation: &str,
+    ) -> Result<u32, Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_manage_user_stock(?, ?, ?, ?, ?, ?, @p_stock_id, @p_error_message)")
+            .bind(user_id)
+            .bind(ingredient_id)
+            .bind(quantity)
+            .bind(expiration_date)
+            .bind(storage_location)
+            .bind(operation)
+            .execute(&mut *conn)
+            .await?;
+
+        let result: (Option<i64>, Option<String>) =
+            sqlx::query("SELECT @p_stock_id, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        match result {
+            (Some(stock_id), None) => Ok(stock_id as u32),
+            (None, Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (Some(_), Some(error_msg)) => Err(Error::Protocol(error_msg)),
+            (None, None) => Err(Error::Protocol(
+                "Unknown error during stock operation".to_string(),
+            )),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::{Gender, Role, User};
+use crate::models::{Gender, Role, User, UserSearchQuery, UserWithStats};
 use chrono::{NaiveDate, Utc};
 use sqlx::{Error, MySqlPool, Row, mysql::MySqlRow};
 
@@ -11,6 +11,15 @@ impl UserRepository {
         Self { pool }
     }
 
+    fn get_gender(gender_str: &str) -> Gender {
+        match gender_str {
+            "Male" => Gender::Male,
+            "Female" => Gender::Female,
+            "Other" => Gender::Other,
+            _ => Gender::Other,
+        }
+    }
+
     fn get_user(row: &MySqlRow) -> User {
         let gender_str: String = row.get(3);
         let role_str: String = row.get(6);
@@ -18,30 +27,56 @@ impl UserRepository {
         // Convertir TIMESTAMP en NaiveDateTime
         let created_at: chrono::DateTime<Utc> = row.get(11);
         let updated_at: chrono::DateTime<Utc> = row.get(12);
+        let email_verified_at: Option<chrono::DateTime<Utc>> = row.get(13);
 
         User {
             user_id: row.get(0),
             first_name: row.get(1),
             last_name: row.get(2),
-            gender: match gender_str.as_str() {
-                "Male" => Gender::Male,
-                "Female" => Gender::Female,
-                "Other" => Gender::Other,
-                _ => Gender::Other,
-            },
+            gender: Self::get_gender(&gender_str),
             password_hash: row.get(4),
             email: row.get(5),
-            role: match role_str.as_str() {
-                "Administrator" => Role::Administrator,
-                "Regular" => Role::Regular,
-                _ => Role::Regular,
-            },
+            role: role_str.parse().unwrap_or(Role::Regular),
             country: row.get(7),
             city: row.get(8),
             is_active: row.get(9),
             birth_date: row.get(10),
             created_at: created_at.naive_utc(),
             updated_at: updated_at.naive_utc(),
+            email_verified_at: email_verified_at.map(|d| d.naive_utc()),
+        }
+    }
+
+    fn get_user_with_stats(row: &MySqlRow) -> UserWithStats {
+        let gender_str: String = row.get(3);
+        let role_str: String = row.get(5);
+
+        // Convertir TIMESTAMP en NaiveDateTime
+        let created_at: chrono::DateTime<Utc> = row.get(10);
+        let updated_at: chrono::DateTime<Utc> = row.get(11);
+        let age: Option<i64> = row.get(12);
+        let email_verified_at: Option<chrono::DateTime<Utc>> = row.get(18);
+
+        UserWithStats {
+            user_id: row.get(0),
+            first_name: row.get(1),
+            last_name: row.get(2),
+            gender: Self::get_gender(&gender_str),
+            email: row.get(4),
+            role: role_str.parse().unwrap_or(Role::Regular),
+            country: row.get(6),
+            city: row.get(7),
+            is_active: row.get(8),
+            birth_date: row.get(9),
+            created_at: created_at.naive_utc(),
+            updated_at: updated_at.naive_utc(),
+            age: age.map(|a| a as i32),
+            recipes_created: row.get(13),
+            recipes_completed: row.get(14),
+            average_rating_given: row.get(15),
+            allergy_count: row.get(16),
+            stock_items_count: row.get(17),
+            email_verified_at: email_verified_at.map(|d| d.naive_utc()),
         }
     }
 
@@ -65,15 +100,16 @@ impl UserRepository {
         Ok(user)
     }
 
-    pub async fn get_all(
-        &self,
-        page: i32,
-        page_size: i32,
-    ) -> Result<(Vec<User>, i64), sqlx::Error> {
-        // Appel de la procédure stockée avec pagination
-        let mut results = sqlx::query("CALL sp_get_all_user(?, ?)")
-            .bind(page)
-            .bind(page_size)
+    pub async fn get_all(&self, query: &UserSearchQuery) -> Result<(Vec<User>, i64), sqlx::Error> {
+        // Appel de la procédure stockée avec filtres et pagination
+        let results = sqlx::query("CALL sp_get_all_user(?, ?, ?, ?, ?, ?, ?)")
+            .bind(query.page)
+            .bind(query.page_size)
+            .bind(query.q.as_deref())
+            .bind(query.role.as_ref().map(Role::as_str))
+            .bind(query.country.as_deref())
+            .bind(query.city.as_deref())
+            .bind(query.is_active)
             .fetch_all(&self.pool)
             .await?;
 
@@ -138,4 +174,124 @@ impl UserRepository {
             )),
         }
     }
+
+    pub async fn update(
+        &self,
+        user_id: u32,
+        first_name: Option<&str>,
+        last_name: Option<&str>,
+        country: Option<&str>,
+        city: Option<&str>,
+        changed_by_user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_update_user(?, ?, ?, ?, ?, ?, @p_success, @p_error_message)")
+            .bind(user_id)
+            .bind(first_name)
+            .bind(last_name)
+            .bind(country)
+            .bind(city)
+            .bind(changed_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_success(&mut conn, "Unknown error during user update").await
+    }
+
+    pub async fn update_password(&self, user_id: u32, password_hash: &str) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_update_user_password(?, ?, @p_success, @p_error_message)")
+            .bind(user_id)
+            .bind(password_hash)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_success(&mut conn, "Unknown error during password update").await
+    }
+
+    pub async fn find_with_stats(&self, user_id: u32) -> Result<Option<UserWithStats>, Error> {
+        let user = sqlx::query("CALL sp_get_user_with_stats(?)")
+            .bind(user_id)
+            .map(|row: MySqlRow| Self::get_user_with_stats(&row))
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    pub async fn update_role(
+        &self,
+        user_id: u32,
+        role: &Role,
+        changed_by_user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_update_user_role(?, ?, ?, @p_success, @p_error_message)")
+            .bind(user_id)
+            .bind(role.as_str())
+            .bind(changed_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_success(&mut conn, "Unknown error during role update").await
+    }
+
+    pub async fn set_active(
+        &self,
+        user_id: u32,
+        is_active: bool,
+        changed_by_user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_set_user_active(?, ?, ?, @p_success, @p_error_message)")
+            .bind(user_id)
+            .bind(is_active)
+            .bind(changed_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_success(&mut conn, "Unknown error during user status update").await
+    }
+
+    pub async fn set_email_verified(
+        &self,
+        user_id: u32,
+        verified: bool,
+        changed_by_user_id: u32,
+    ) -> Result<(), Error> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("CALL sp_set_email_verified(?, ?, ?, @p_success, @p_error_message)")
+            .bind(user_id)
+            .bind(verified)
+            .bind(changed_by_user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Self::read_success(&mut conn, "Unknown error during email verification update").await
+    }
+
+    // Lit les variables de sortie sur la connexion qui a appelé la procédure
+    async fn read_success(
+        conn: &mut sqlx::pool::PoolConnection<sqlx::MySql>,
+        unknown_error: &str,
+    ) -> Result<(), Error> {
+        let result: (Option<bool>, Option<String>) =
+            sqlx::query("SELECT @p_success, @p_error_message")
+                .map(|row: MySqlRow| (row.get(0), row.get(1)))
+                .fetch_one(&mut **conn)
+                .await?;
+
+        match result {
+            (Some(true), None) => Ok(()),
+            (Some(false), Some(error_msg)) | (None, Some(error_msg)) => {
+                Err(Error::Protocol(error_msg))
+            }
+            _ => Err(Error::Protocol(unknown_error.to_string())),
+        }
+    }
 }
@@ -1,4 +1,11 @@
 pub mod auth;
+pub mod cost;
+pub mod nutrition;
+pub mod scaling;
+pub mod shopping;
+#[cfg(test)]
+pub mod test_fixtures;
+pub mod units;
 
 // Ré-exporter les fonctions d'auth
 pub use auth::validator;
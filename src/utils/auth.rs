@@ -1,11 +1,14 @@
-use crate::models::{Role, TokenClaims, User};
-use actix_web::{Error, HttpMessage, HttpResponse, dev::ServiceRequest};
+use crate::models::{ActorClaim, TokenClaims, User};
+use crate::repositories::{AuditRepository, SessionRepository, UserRepository};
+use actix_web::{Error, HttpMessage, HttpRequest, HttpResponse, dev::ServiceRequest, web};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use chrono::Utc;
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use sqlx::MySqlPool;
+use std::net::IpAddr;
 impl TokenClaims {
-    /// Crée des claims à partir d'un User
-    pub fn from_user(user: &User, expiration_seconds: i64) -> Self {
+    /// Crée des claims à partir d'un User et de sa session
+    pub fn from_user(user: &User, session_id: u32, expiration_seconds: i64) -> Self {
         let expiration_time = Utc::now()
             .checked_add_signed(chrono::Duration::seconds(expiration_seconds))
             .expect("valid timestamp")
@@ -15,23 +18,41 @@ impl TokenClaims {
             sub: user.user_id.to_string(),
             email: user.email.clone(),
             role: user.role.clone(),
+            sid: session_id,
             exp: expiration_time as usize,
+            act: None,
         }
     }
-
-    /// Vérifie si l'utilisateur est administrateur
-    pub fn is_admin(&self) -> bool {
-        matches!(self.role, Role::Administrator)
-    }
 }
 
 /// Crée un token JWT pour un utilisateur
 pub fn create_jwt(
     user: &User,
+    session_id: u32,
+    secret: &str,
+    expiration_seconds: i64,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = TokenClaims::from_user(user, session_id, expiration_seconds);
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_ref()),
+    )
+}
+
+/// Crée un token JWT pour `user` au nom de l'administrateur `actor`
+pub fn create_impersonation_jwt(
+    user: &User,
+    session_id: u32,
+    actor: ActorClaim,
     secret: &str,
     expiration_seconds: i64,
 ) -> Result<String, jsonwebtoken::errors::Error> {
-    let claims = TokenClaims::from_user(user, expiration_seconds);
+    let claims = TokenClaims {
+        act: Some(actor),
+        ..TokenClaims::from_user(user, session_id, expiration_seconds)
+    };
 
     encode(
         &Header::default(),
@@ -58,15 +79,228 @@ pub async fn validator(
 ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
     let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
 
-    match decode_jwt(credentials.token(), &secret) {
-        Ok(claims) => {
+    let mut claims = match decode_jwt(credentials.token(), &secret) {
+        Ok(claims) => claims,
+        Err(_) => return Err((actix_web::error::ErrorUnauthorized("Invalid token"), req)),
+    };
+
+    let user_id = match claims.sub.parse::<u32>() {
+        Ok(id) => id,
+        Err(_) => return Err((actix_web::error::ErrorUnauthorized("Invalid token"), req)),
+    };
+
+    // Un claim `act` illisible invalide le token : la requête ne pourrait pas être tracée
+    let impersonator_id = match claims.act.as_ref().map(|actor| actor.sub.parse::<u32>()) {
+        None => None,
+        Some(Ok(id)) => Some(id),
+        Some(Err(_)) => return Err((actix_web::error::ErrorUnauthorized("Invalid token"), req)),
+    };
+
+    // Le token n'est accepté que si sa session n'a pas été révoquée
+    let Some(pool) = req.app_data::<web::Data<MySqlPool>>() else {
+        log::error!("Database pool missing from application data");
+        return Err((
+            actix_web::error::ErrorInternalServerError("Internal server error"),
+            req,
+        ));
+    };
+
+    match SessionRepository::new(pool.get_ref().clone())
+        .touch(claims.sid, user_id)
+        .await
+    {
+        Ok(Some(role)) => {
+            // Le rôle du JWT peut être périmé : les contrôles utilisent celui en base
+            claims.role = role;
+
+            // Toute requête faite sous usurpation est tracée ; sans trace, elle est refusée
+            if let Some(impersonator_id) = impersonator_id {
+                let ip_address = client_ip(req.request());
+                let request_path = req
+                    .uri()
+                    .path_and_query()
+                    .map(|pq| pq.as_str().to_string())
+                    .unwrap_or_else(|| req.path().to_string());
+
+                if let Err(e) = AuditRepository::new(pool.get_ref().clone())
+                    .record_impersonation(
+                        impersonator_id,
+                        user_id,
+                        Some(claims.sid),
+                        req.method().as_str(),
+                        &request_path,
+                        ip_address.as_deref(),
+                    )
+                    .await
+                {
+                    log::error!("Failed to record impersonated request: {:?}", e);
+                    return Err((
+                        actix_web::error::ErrorInternalServerError("Internal server error"),
+                        req,
+                    ));
+                }
+            }
+
             req.extensions_mut().insert(claims);
             Ok(req)
         }
-        Err(_) => Err((actix_web::error::ErrorUnauthorized("Invalid token"), req)),
+        Ok(None) => Err((actix_web::error::ErrorUnauthorized("Session revoked"), req)),
+        Err(e) => {
+            log::error!("Failed to check session: {:?}", e);
+            Err((
+                actix_web::error::ErrorInternalServerError("Internal server error"),
+                req,
+            ))
+        }
     }
 }
 
+/// Génère un jeton opaque aléatoire : refresh token ou jeton à usage unique envoyé par email
+/// (seule son empreinte est stockée en base)
+pub fn generate_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Durée de vie des refresh tokens (et donc des sessions), en secondes
+pub fn refresh_token_expiration() -> i64 {
+    std::env::var("REFRESH_TOKEN_EXPIRATION")
+        .unwrap_or_else(|_| "2592000".to_string())
+        .parse::<i64>()
+        .expect("REFRESH_TOKEN_EXPIRATION must be a valid number")
+}
+
+/// Durée de validité des liens de réinitialisation, en secondes
+pub fn password_reset_expiration() -> i64 {
+    std::env::var("PASSWORD_RESET_EXPIRATION")
+        .unwrap_or_else(|_| "3600".to_string())
+        .parse::<i64>()
+        .expect("PASSWORD_RESET_EXPIRATION must be a valid number")
+}
+
+/// Durée de validité des liens de vérification d'email, en secondes
+pub fn email_verification_expiration() -> i64 {
+    std::env::var("EMAIL_VERIFICATION_EXPIRATION")
+        .unwrap_or_else(|_| "86400".to_string())
+        .parse::<i64>()
+        .expect("EMAIL_VERIFICATION_EXPIRATION must be a valid number")
+}
+
+/// Refuse l'action tant que l'adresse email n'est pas vérifiée (aucun rôle n'en est dispensé)
+pub async fn require_verified_email(
+    pool: &MySqlPool,
+    claims: &TokenClaims,
+) -> Result<(), HttpResponse> {
+    let (user_id, _) = extract_user_info(claims)?;
+
+    match UserRepository::new(pool.clone()).find_by_id(user_id).await {
+        Ok(Some(user)) if user.email_verified_at.is_some() => Ok(()),
+        Ok(_) => Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Email address must be verified first"
+        }))),
+        Err(e) => {
+            log::error!("Failed to check email verification: {:?}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to check email verification"
+            })))
+        }
+    }
+}
+
+/// Durée de vie des jetons d'usurpation, en secondes
+pub fn impersonation_expiration() -> i64 {
+    std::env::var("IMPERSONATION_EXPIRATION")
+        .unwrap_or_else(|_| "900".to_string())
+        .parse::<i64>()
+        .expect("IMPERSONATION_EXPIRATION must be a valid number")
+}
+
+/// Mode développeur (DEV_MODE=true) : active /auth/dev-login, à ne jamais activer en production
+pub fn dev_mode_enabled() -> bool {
+    std::env::var("DEV_MODE")
+        .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+        .unwrap_or(false)
+}
+
+/// Seuils de protection contre la force brute sur /auth/login
+pub struct LoginThrottlePolicy {
+    /// Échecs avant verrouillage du compte
+    pub account_threshold: i32,
+    /// Échecs avant verrouillage de l'IP, tous comptes confondus
+    pub ip_threshold: i32,
+    /// Durée du premier verrou, doublée à chaque nouvel échec
+    pub base_lockout_seconds: i32,
+    pub max_lockout_seconds: i32,
+    /// Période sans échec après laquelle les compteurs repartent de zéro
+    pub window_seconds: i32,
+}
+
+impl LoginThrottlePolicy {
+    pub fn from_env() -> Self {
+        let env_or = |name: &str, default: &str| {
+            std::env::var(name)
+                .unwrap_or_else(|_| default.to_string())
+                .parse::<i32>()
+                .unwrap_or_else(|_| panic!("{} must be a valid number", name))
+        };
+
+        Self {
+            account_threshold: env_or("LOGIN_MAX_FAILURES", "5"),
+            ip_threshold: env_or("LOGIN_MAX_FAILURES_PER_IP", "20"),
+            base_lockout_seconds: env_or("LOGIN_LOCKOUT_SECONDS", "60"),
+            max_lockout_seconds: env_or("LOGIN_MAX_LOCKOUT_SECONDS", "86400"),
+            window_seconds: env_or("LOGIN_FAILURE_WINDOW", "900"),
+        }
+    }
+}
+
+/// Adresse IP du client (tronquée à la taille des colonnes ip_address).
+///
+/// X-Forwarded-For n'est lu que si la connexion vient d'un proxy listé dans TRUSTED_PROXIES
+/// (IP séparées par des virgules) : la chaîne est alors remontée depuis la droite jusqu'à
+/// la première adresse qui n'est pas un proxy de confiance. Sinon l'adresse du pair est utilisée,
+/// pour qu'un client ne puisse pas choisir l'IP comptée par le verrouillage des connexions.
+pub fn client_ip(http_req: &HttpRequest) -> Option<String> {
+    let peer_ip = http_req.peer_addr()?.ip();
+    let trusted_proxies = trusted_proxies();
+    let mut ip_address = peer_ip;
+
+    if trusted_proxies.contains(&peer_ip) {
+        let forwarded: Vec<&str> = http_req
+            .headers()
+            .get_all("x-forwarded-for")
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect();
+
+        for hop in forwarded.iter().rev() {
+            let Ok(hop_ip) = hop.parse::<IpAddr>() else {
+                break;
+            };
+
+            ip_address = hop_ip;
+            if !trusted_proxies.contains(&hop_ip) {
+                break;
+            }
+        }
+    }
+
+    Some(ip_address.to_string().chars().take(45).collect())
+}
+
+// Adresses des reverse proxies autorisés à transmettre l'IP du client
+fn trusted_proxies() -> Vec<IpAddr> {
+    std::env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+        .collect()
+}
+
 // Helper pour extraire user_id et role des claims
 pub fn extract_user_info(claims: &TokenClaims) -> Result<(u32, String), HttpResponse> {
     let user_id = match claims.sub.parse::<u32>() {
@@ -79,10 +313,16 @@ pub fn extract_user_info(claims: &TokenClaims) -> Result<(u32, String), HttpResp
         }
     };
 
-    let user_role = match &claims.role {
-        crate::models::Role::Administrator => "Administrator".to_string(),
-        crate::models::Role::Regular => "Regular".to_string(),
-    };
+    Ok((user_id, claims.role.as_str().to_string()))
+}
+
+// Helper pour refuser, sous usurpation, les actions réservées au titulaire du compte
+pub fn reject_impersonation(claims: &TokenClaims) -> Result<(), HttpResponse> {
+    if claims.act.is_some() {
+        return Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Not allowed while impersonating"
+        })));
+    }
 
-    Ok((user_id, user_role))
+    Ok(())
 }
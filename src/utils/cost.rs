@@ -0,0 +1,63 @@
+use crate::models::{IngredientCost, RecipeCost, RecipeIngredientDetail, RecipeWithIngredients};
+use crate::utils::units::to_grams;
+use rust_decimal::Decimal;
+
+/// Calcule le coût d'une recette : `price` est le prix de `weight` grammes d'ingrédient.
+///
+/// Même règle que la vue `v_recipe_cost` ; les ingrédients optionnels ne sont comptés dans
+/// les totaux que si `include_optional` est vrai.
+pub fn compute_recipe_cost(recipe: &RecipeWithIngredients, include_optional: bool) -> RecipeCost {
+    let mut total_cost = Decimal::ZERO;
+    let mut ingredients = Vec::with_capacity(recipe.ingredients.len());
+
+    for ingredient in &recipe.ingredients {
+        let cost = ingredient_cost(ingredient);
+
+        if include_optional || !ingredient.is_optional {
+            total_cost += cost;
+        }
+
+        ingredients.push(IngredientCost {
+            ingredient_id: ingredient.ingredient_id,
+            ingredient_name: ingredient.ingredient_name.clone(),
+            quantity: ingredient.quantity,
+            measurement_unit: ingredient.measurement_unit.clone(),
+            is_optional: ingredient.is_optional,
+            price: ingredient.price,
+            weight: ingredient.weight,
+            cost: cost.round_dp(2),
+        });
+    }
+
+    let servings = Decimal::from(recipe.recipe.servings.max(1));
+
+    RecipeCost {
+        recipe_id: recipe.recipe.recipe_id,
+        title: recipe.recipe.title.clone(),
+        servings: recipe.recipe.servings,
+        include_optional,
+        total_cost: total_cost.round_dp(2),
+        cost_per_serving: (total_cost / servings).round_dp(2),
+        ingredients,
+    }
+}
+
+/// Coût de la quantité d'un ingrédient utilisée dans une recette
+pub fn ingredient_cost(ingredient: &RecipeIngredientDetail) -> Decimal {
+    estimate_cost(
+        ingredient.price,
+        ingredient.weight,
+        ingredient.quantity,
+        &ingredient.measurement_unit,
+    )
+}
+
+/// Coût de `quantity` (exprimée dans `unit`) d'un ingrédient valant `price` pour `weight` grammes
+pub fn estimate_cost(price: Decimal, weight: Decimal, quantity: Decimal, unit: &str) -> Decimal {
+    // Un poids de référence nul ne permet pas de calculer de prix
+    if weight.is_zero() {
+        return Decimal::ZERO;
+    }
+
+    price * to_grams(quantity, unit, weight) / weight
+}
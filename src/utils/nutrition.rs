@@ -0,0 +1,193 @@
+use crate::models::{
+    IngredientNutrition, NutritionValues, RecipeIngredientDetail, RecipeNutrition,
+    RecipeWithIngredients,
+};
+use crate::utils::units::to_grams;
+use rust_decimal::Decimal;
+
+/// Calcule les apports d'une recette à partir des valeurs pour 100g de chaque ingrédient.
+///
+/// Les ingrédients optionnels apparaissent dans le détail mais ne sont comptés dans les
+/// totaux que si `include_optional` est vrai.
+pub fn compute_recipe_nutrition(
+    recipe: &RecipeWithIngredients,
+    include_optional: bool,
+) -> RecipeNutrition {
+    let mut total = NutritionValues::default();
+    let mut ingredients = Vec::with_capacity(recipe.ingredients.len());
+
+    for ingredient in &recipe.ingredients {
+        let grams = to_grams(
+            ingredient.quantity,
+            &ingredient.measurement_unit,
+            ingredient.weight,
+        );
+        let nutrition = ingredient_nutrition(ingredient, grams);
+
+        if include_optional || !ingredient.is_optional {
+            add(&mut total, &nutrition);
+        }
+
+        ingredients.push(IngredientNutrition {
+            ingredient_id: ingredient.ingredient_id,
+            ingredient_name: ingredient.ingredient_name.clone(),
+            quantity: ingredient.quantity,
+            measurement_unit: ingredient.measurement_unit.clone(),
+            grams: grams.round_dp(2),
+            is_optional: ingredient.is_optional,
+            nutrition: round(&nutrition),
+        });
+    }
+
+    // Une recette sans portion renseignée compte pour une portion
+    let servings = Decimal::from(recipe.recipe.servings.max(1));
+    let per_serving = NutritionValues {
+        calories: total.calories / servings,
+        proteins: total.proteins / servings,
+        carbohydrates: total.carbohydrates / servings,
+        fats: total.fats / servings,
+        fibers: total.fibers / servings,
+    };
+
+    RecipeNutrition {
+        recipe_id: recipe.recipe.recipe_id,
+        title: recipe.recipe.title.clone(),
+        servings: recipe.recipe.servings,
+        include_optional,
+        total: round(&total),
+        per_serving: round(&per_serving),
+        ingredients,
+    }
+}
+
+fn ingredient_nutrition(ingredient: &RecipeIngredientDetail, grams: Decimal) -> NutritionValues {
+    let ratio = grams / Decimal::from(100);
+
+    NutritionValues {
+        calories: ingredient.calories * ratio,
+        proteins: ingredient.proteins * ratio,
+        carbohydrates: ingredient.carbohydrates * ratio,
+        fats: ingredient.fats * ratio,
+        fibers: ingredient.fibers * ratio,
+    }
+}
+
+fn add(total: &mut NutritionValues, values: &NutritionValues) {
+    total.calories += values.calories;
+    total.proteins += values.proteins;
+    total.carbohydrates += values.carbohydrates;
+    total.fats += values.fats;
+    total.fibers += values.fibers;
+}
+
+fn round(values: &NutritionValues) -> NutritionValues {
+    NutritionValues {
+        calories: values.calories.round_dp(2),
+        proteins: values.proteins.round_dp(2),
+        carbohydrates: values.carbohydrates.round_dp(2),
+        fats: values.fats.round_dp(2),
+        fibers: values.fibers.round_dp(2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::{dec, ingredient, recipe_with};
+
+    // 100 kcal pour 100 g : les calories valent le poids en grammes
+    fn calorie_ingredient(
+        ingredient_id: u32,
+        quantity: &str,
+        unit: &str,
+    ) -> RecipeIngredientDetail {
+        RecipeIngredientDetail {
+            calories: dec("100"),
+            ..ingredient(ingredient_id, quantity, unit)
+        }
+    }
+
+    #[test]
+    fn converts_masses_to_grams() {
+        let recipe = recipe_with(
+            1,
+            vec![
+                calorie_ingredient(1, "250", "grams"),
+                calorie_ingredient(2, "1.5", "kilograms"),
+            ],
+        );
+
+        let nutrition = compute_recipe_nutrition(&recipe, false);
+
+        assert_eq!(nutrition.ingredients[0].grams, dec("250"));
+        assert_eq!(nutrition.ingredients[1].grams, dec("1500"));
+        assert_eq!(nutrition.total.calories, dec("1750"));
+    }
+
+    #[test]
+    fn treats_volumes_as_water() {
+        let recipe = recipe_with(
+            1,
+            vec![
+                calorie_ingredient(1, "200", "milliliters"),
+                calorie_ingredient(2, "0.5", "liters"),
+            ],
+        );
+
+        let nutrition = compute_recipe_nutrition(&recipe, false);
+
+        assert_eq!(nutrition.ingredients[0].grams, dec("200"));
+        assert_eq!(nutrition.ingredients[1].grams, dec("500"));
+        assert_eq!(nutrition.total.calories, dec("700"));
+    }
+
+    #[test]
+    fn weighs_pieces_with_ingredient_weight() {
+        let egg = RecipeIngredientDetail {
+            proteins: dec("12.5"),
+            weight: dec("60"),
+            ..ingredient(1, "3", "pieces")
+        };
+
+        let nutrition = compute_recipe_nutrition(&recipe_with(1, vec![egg]), false);
+
+        assert_eq!(nutrition.ingredients[0].grams, dec("180"));
+        assert_eq!(nutrition.total.proteins, dec("22.5"));
+    }
+
+    #[test]
+    fn pieces_without_weight_count_for_nothing() {
+        let unweighed = RecipeIngredientDetail {
+            weight: Decimal::ZERO,
+            ..calorie_ingredient(1, "4", "pieces")
+        };
+
+        let nutrition = compute_recipe_nutrition(
+            &recipe_with(1, vec![unweighed, calorie_ingredient(2, "100", "grams")]),
+            false,
+        );
+
+        assert_eq!(nutrition.ingredients[0].grams, Decimal::ZERO);
+        assert_eq!(nutrition.ingredients[0].nutrition.calories, Decimal::ZERO);
+        assert_eq!(nutrition.total.calories, dec("100"));
+    }
+
+    #[test]
+    fn optional_ingredients_only_count_when_included() {
+        let optional = RecipeIngredientDetail {
+            is_optional: true,
+            ..calorie_ingredient(2, "50", "grams")
+        };
+        let mut recipe = recipe_with(1, vec![calorie_ingredient(1, "300", "grams"), optional]);
+        recipe.recipe.servings = 2;
+
+        let without = compute_recipe_nutrition(&recipe, false);
+        let with = compute_recipe_nutrition(&recipe, true);
+
+        assert_eq!(without.total.calories, dec("300"));
+        assert_eq!(without.per_serving.calories, dec("150"));
+        assert_eq!(without.ingredients[1].nutrition.calories, dec("50"));
+        assert_eq!(with.total.calories, dec("350"));
+        assert_eq!(with.per_serving.calories, dec("175"));
+    }
+}
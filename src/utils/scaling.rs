@@ -0,0 +1,108 @@
+use crate::models::RecipeWithIngredients;
+use crate::utils::units::round_for_unit;
+use rust_decimal::Decimal;
+
+/// Nombre de portions maximal accepté pour adapter une recette
+pub const MAX_SERVINGS: u32 = 100;
+
+/// Vérifie qu'un nombre de portions demandé est utilisable pour adapter une recette
+pub fn validate_servings(servings: u32) -> Result<u32, String> {
+    if servings == 0 || servings > MAX_SERVINGS {
+        return Err(format!("Servings must be between 1 and {}", MAX_SERVINGS));
+    }
+
+    Ok(servings)
+}
+
+/// Adapte une recette à `servings` portions : chaque quantité est multipliée
+/// proportionnellement puis arrondie selon son unité de mesure
+pub fn scale_recipe(mut recipe: RecipeWithIngredients, servings: u32) -> RecipeWithIngredients {
+    // Une recette sans portion renseignée compte pour une portion
+    let original_servings = recipe.recipe.servings.max(1);
+    let factor = Decimal::from(servings) / Decimal::from(original_servings);
+
+    for ingredient in recipe.ingredients.iter_mut() {
+        ingredient.quantity =
+            round_for_unit(ingredient.quantity * factor, &ingredient.measurement_unit);
+    }
+
+    recipe.recipe.servings = servings;
+    recipe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::{dec, ingredient, recipe_with};
+
+    fn quantities(recipe: &RecipeWithIngredients) -> Vec<Decimal> {
+        recipe.ingredients.iter().map(|i| i.quantity).collect()
+    }
+
+    #[test]
+    fn scales_up_proportionally() {
+        let mut recipe = recipe_with(
+            1,
+            vec![
+                ingredient(1, "200", "grams"),
+                ingredient(2, "0.5", "liters"),
+            ],
+        );
+        recipe.recipe.servings = 2;
+
+        let scaled = scale_recipe(recipe, 6);
+
+        assert_eq!(scaled.recipe.servings, 6);
+        assert_eq!(quantities(&scaled), vec![dec("600"), dec("1.5")]);
+    }
+
+    #[test]
+    fn scales_down_proportionally() {
+        let mut recipe = recipe_with(
+            1,
+            vec![ingredient(1, "500", "grams"), ingredient(2, "3", "cups")],
+        );
+        recipe.recipe.servings = 4;
+
+        let scaled = scale_recipe(recipe, 2);
+
+        assert_eq!(scaled.recipe.servings, 2);
+        assert_eq!(quantities(&scaled), vec![dec("250"), dec("1.5")]);
+    }
+
+    #[test]
+    fn rounds_per_measurement_unit() {
+        let mut recipe = recipe_with(
+            1,
+            vec![
+                ingredient(1, "3", "pieces"),
+                ingredient(2, "1", "teaspoon"),
+                ingredient(3, "1", "pieces"),
+            ],
+        );
+        recipe.recipe.servings = 4;
+
+        // Facteur 1/4 : 0,75 pièce, 0,25 cuillère, 0,25 pièce
+        let scaled = scale_recipe(recipe, 1);
+
+        assert_eq!(quantities(&scaled), vec![dec("1"), dec("0.5"), dec("1")]);
+    }
+
+    #[test]
+    fn recipe_without_servings_counts_as_one() {
+        let mut recipe = recipe_with(1, vec![ingredient(1, "100", "grams")]);
+        recipe.recipe.servings = 0;
+
+        let scaled = scale_recipe(recipe, 3);
+
+        assert_eq!(quantities(&scaled), vec![dec("300")]);
+    }
+
+    #[test]
+    fn rejects_zero_and_out_of_range_servings() {
+        assert!(validate_servings(0).is_err());
+        assert!(validate_servings(MAX_SERVINGS + 1).is_err());
+        assert_eq!(validate_servings(1), Ok(1));
+        assert_eq!(validate_servings(MAX_SERVINGS), Ok(MAX_SERVINGS));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::models::{NewShoppingListItem, RecipeWithIngredients, StockItem};
+use crate::utils::cost::estimate_cost;
+use crate::utils::units::{MeasurementUnit, from_base_quantity, to_base_quantity};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+struct RequiredIngredient<'a> {
+    measurement_unit: &'a str,
+    price: Decimal,
+    weight: Decimal,
+    base_quantity: Decimal,
+}
+
+/// Calcule les articles à acheter pour cuisiner `recipes` (recette, portions visées) :
+/// besoins cumulés des ingrédients obligatoires, moins le stock de l'utilisateur.
+///
+/// Les quantités sont additionnées dans l'unité de base puis reconverties dans l'unité
+/// de l'ingrédient ; les articles sont triés par `ingredient_id`.
+pub fn build_shopping_items(
+    recipes: &[(RecipeWithIngredients, u32)],
+    stock: &[StockItem],
+) -> Vec<NewShoppingListItem> {
+    let mut required: BTreeMap<u32, RequiredIngredient> = BTreeMap::new();
+
+    for (recipe, servings) in recipes {
+        let factor = Decimal::from(*servings) / Decimal::from(recipe.recipe.servings.max(1));
+
+        for ingredient in recipe.ingredients.iter().filter(|i| !i.is_optional) {
+            let entry = required
+                .entry(ingredient.ingredient_id)
+                .or_insert(RequiredIngredient {
+                    measurement_unit: &ingredient.measurement_unit,
+                    price: ingredient.price,
+                    weight: ingredient.weight,
+                    base_quantity: Decimal::ZERO,
+                });
+
+            entry.base_quantity +=
+                to_base_quantity(ingredient.quantity * factor, &ingredient.measurement_unit);
+        }
+    }
+
+    // Un même ingrédient peut être stocké à plusieurs emplacements
+    let mut available: HashMap<u32, Decimal> = HashMap::new();
+    for item in stock {
+        *available.entry(item.ingredient_id).or_default() +=
+            to_base_quantity(item.quantity, &item.measurement_unit);
+    }
+
+    required
+        .into_iter()
+        .filter_map(|(ingredient_id, needed)| {
+            let missing =
+                needed.base_quantity - available.get(&ingredient_id).copied().unwrap_or_default();
+
+            if missing <= Decimal::ZERO {
+                return None;
+            }
+
+            let quantity = purchase_quantity(
+                from_base_quantity(missing, needed.measurement_unit),
+                needed.measurement_unit,
+            );
+
+            Some(NewShoppingListItem {
+                ingredient_id,
+                quantity,
+                estimated_cost: estimate_cost(
+                    needed.price,
+                    needed.weight,
+                    quantity,
+                    needed.measurement_unit,
+                )
+                .round_dp(2),
+            })
+        })
+        .collect()
+}
+
+// On n'achète pas de fraction de pièce ; les autres quantités restent au centième
+fn purchase_quantity(quantity: Decimal, unit: &str) -> Decimal {
+    match MeasurementUnit::parse(unit) {
+        Some(u) if u.is_countable() => quantity.ceil(),
+        _ => quantity.round_dp(2).max(Decimal::new(1, 2)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecipeIngredientDetail;
+    use crate::utils::test_fixtures::{dec, ingredient, recipe_with, stock};
+
+    // Prix de 1.00 pour `weight` grammes
+    fn priced(
+        ingredient_id: u32,
+        quantity: &str,
+        unit: &str,
+        weight: &str,
+    ) -> RecipeIngredientDetail {
+        RecipeIngredientDetail {
+            price: Decimal::ONE,
+            weight: dec(weight),
+            ..ingredient(ingredient_id, quantity, unit)
+        }
+    }
+
+    #[test]
+    fn aggregates_recipes_and_scales_servings() {
+        let mut first = recipe_with(1, vec![priced(10, "200", "grams", "1000")]);
+        first.recipe.servings = 2;
+        let mut second = recipe_with(2, vec![priced(10, "100", "grams", "1000")]);
+        second.recipe.servings = 4;
+
+        let items = build_shopping_items(&[(first, 4), (second, 2)], &[]);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].ingredient_id, 10);
+        assert_eq!(items[0].quantity, dec("450"));
+        assert_eq!(items[0].estimated_cost, dec("0.45"));
+    }
+
+    #[test]
+    fn subtracts_stock_split_across_locations() {
+        let recipes = [(recipe_with(1, vec![ingredient(10, "1000", "grams")]), 1)];
+        let stock = vec![
+            StockItem {
+                storage_location: Some("Fridge".to_string()),
+                ..stock(10, "300", "grams")
+            },
+            StockItem {
+                storage_location: Some("Pantry".to_string()),
+                ..stock(10, "200", "grams")
+            },
+        ];
+
+        let items = build_shopping_items(&recipes, &stock);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].quantity, dec("500"));
+    }
+
+    #[test]
+    fn converts_mixed_units_through_base_unit() {
+        // Recette en kilogrammes, stock en grammes
+        let recipes = [(
+            recipe_with(1, vec![priced(10, "1.5", "kilograms", "1000")]),
+            1,
+        )];
+
+        let items = build_shopping_items(&recipes, &[stock(10, "250", "grams")]);
+
+        assert_eq!(items[0].quantity, dec("1.25"));
+        assert_eq!(items[0].estimated_cost, dec("1.25"));
+    }
+
+    #[test]
+    fn countable_ingredients_are_rounded_up() {
+        let mut recipe = recipe_with(1, vec![priced(20, "3", "pieces", "50")]);
+        recipe.recipe.servings = 4;
+
+        let items = build_shopping_items(&[(recipe, 3)], &[stock(20, "1", "pieces")]);
+
+        // 2.25 pièces nécessaires, 1 en stock : on achète 2 pièces entières
+        assert_eq!(items[0].quantity, dec("2"));
+        assert_eq!(items[0].estimated_cost, dec("2"));
+    }
+
+    #[test]
+    fn omits_covered_and_optional_ingredients() {
+        let optional = RecipeIngredientDetail {
+            is_optional: true,
+            ..ingredient(40, "5", "grams")
+        };
+        let recipes = [(
+            recipe_with(
+                1,
+                vec![
+                    ingredient(30, "100", "grams"),
+                    ingredient(10, "2", "pieces"),
+                    optional,
+                ],
+            ),
+            1,
+        )];
+
+        let items = build_shopping_items(&recipes, &[stock(30, "0.1", "kilograms")]);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].ingredient_id, 10);
+    }
+
+    #[test]
+    fn purchase_quantity_keeps_a_minimal_amount() {
+        assert_eq!(purchase_quantity(dec("0.001"), "grams"), dec("0.01"));
+        assert_eq!(purchase_quantity(dec("1.234"), "liters"), dec("1.23"));
+        assert_eq!(purchase_quantity(dec("1.01"), "pieces"), dec("2"));
+    }
+}
@@ -0,0 +1,89 @@
+//! Données partagées par les tests unitaires des calculs sur les recettes et le stock.
+//!
+//! Les fixtures ont des valeurs neutres (une portion, ni prix ni apports) : chaque test
+//! ne renseigne que les champs qu'il vérifie, via la syntaxe `..fixture()`.
+
+use crate::models::{Recipe, RecipeIngredientDetail, RecipeWithIngredients, StockItem};
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+
+pub fn dec(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+pub fn timestamp() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
+
+/// Recette publiée d'une portion
+pub fn recipe(recipe_id: u32) -> Recipe {
+    Recipe {
+        recipe_id,
+        title: format!("Recipe {}", recipe_id),
+        description: None,
+        servings: 1,
+        difficulty: "Easy".to_string(),
+        author_user_id: 1,
+        is_published: true,
+        created_at: timestamp(),
+        updated_at: timestamp(),
+        author_first_name: None,
+        author_last_name: None,
+        rating: None,
+    }
+}
+
+/// Recette `recipe_id` composée de `ingredients`
+pub fn recipe_with(
+    recipe_id: u32,
+    ingredients: Vec<RecipeIngredientDetail>,
+) -> RecipeWithIngredients {
+    RecipeWithIngredients {
+        recipe: recipe(recipe_id),
+        ingredients: ingredients
+            .into_iter()
+            .map(|ingredient| RecipeIngredientDetail {
+                recipe_id,
+                ..ingredient
+            })
+            .collect(),
+    }
+}
+
+/// Ingrédient obligatoire sans prix ni apports ; une pièce pèse 100 g
+pub fn ingredient(ingredient_id: u32, quantity: &str, unit: &str) -> RecipeIngredientDetail {
+    RecipeIngredientDetail {
+        recipe_id: 0,
+        ingredient_id,
+        ingredient_name: format!("Ingredient {}", ingredient_id),
+        quantity: dec(quantity),
+        measurement_unit: unit.to_string(),
+        is_optional: false,
+        carbohydrates: Decimal::ZERO,
+        proteins: Decimal::ZERO,
+        fats: Decimal::ZERO,
+        fibers: Decimal::ZERO,
+        calories: Decimal::ZERO,
+        price: Decimal::ZERO,
+        weight: Decimal::from(100),
+    }
+}
+
+/// Stock de l'utilisateur 1, sans date d'expiration ni emplacement
+pub fn stock(ingredient_id: u32, quantity: &str, unit: &str) -> StockItem {
+    StockItem {
+        stock_id: 0,
+        user_id: 1,
+        ingredient_id,
+        ingredient_name: format!("Ingredient {}", ingredient_id),
+        quantity: dec(quantity),
+        measurement_unit: unit.to_string(),
+        expiration_date: None,
+        storage_location: None,
+        created_at: timestamp(),
+        updated_at: timestamp(),
+    }
+}
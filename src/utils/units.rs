@@ -0,0 +1,159 @@
+use rust_decimal::Decimal;
+
+/// Unités de mesure de la colonne `ingredients.measurement_unit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeasurementUnit {
+    Grams,
+    Kilograms,
+    Milliliters,
+    Liters,
+    Teaspoon,
+    Tablespoon,
+    Cups,
+    Pieces,
+}
+
+impl MeasurementUnit {
+    pub fn parse(unit: &str) -> Option<Self> {
+        match unit {
+            "grams" => Some(Self::Grams),
+            "kilograms" => Some(Self::Kilograms),
+            "milliliters" => Some(Self::Milliliters),
+            "liters" => Some(Self::Liters),
+            "teaspoon" => Some(Self::Teaspoon),
+            "tablespoon" => Some(Self::Tablespoon),
+            "cups" => Some(Self::Cups),
+            "pieces" => Some(Self::Pieces),
+            _ => None,
+        }
+    }
+
+    /// Facteur vers l'unité de base : grammes pour les masses, millilitres pour les volumes,
+    /// nombre de pièces pour `pieces`
+    pub fn base_factor(&self) -> Decimal {
+        match self {
+            Self::Grams | Self::Milliliters | Self::Pieces => Decimal::ONE,
+            Self::Kilograms | Self::Liters => Decimal::from(1000),
+            Self::Teaspoon => Decimal::from(5),
+            Self::Tablespoon => Decimal::from(15),
+            Self::Cups => Decimal::from(240),
+        }
+    }
+
+    /// Les pièces ne se comptent qu'en nombres entiers
+    pub fn is_countable(&self) -> bool {
+        matches!(self, Self::Pieces)
+    }
+}
+
+/// Arrondit une quantité à une précision utilisable en cuisine pour son unité :
+/// pièces entières, demi-cuillères, quarts de tasse
+pub fn round_for_unit(quantity: Decimal, unit: &str) -> Decimal {
+    let rounded = match MeasurementUnit::parse(unit) {
+        Some(MeasurementUnit::Pieces) => quantity.round(),
+        Some(MeasurementUnit::Teaspoon | MeasurementUnit::Tablespoon) => {
+            (quantity * Decimal::TWO).round() / Decimal::TWO
+        }
+        Some(MeasurementUnit::Cups) => (quantity * Decimal::from(4)).round() / Decimal::from(4),
+        Some(MeasurementUnit::Kilograms | MeasurementUnit::Liters) => quantity.round_dp(2),
+        Some(MeasurementUnit::Grams | MeasurementUnit::Milliliters) | None => {
+            if quantity >= Decimal::TEN {
+                quantity.round()
+            } else {
+                quantity.round_dp(1)
+            }
+        }
+    };
+
+    // Une quantité non nulle ne doit pas disparaître à l'arrondi
+    if rounded.is_zero() && !quantity.is_zero() {
+        match MeasurementUnit::parse(unit) {
+            Some(MeasurementUnit::Pieces) => Decimal::ONE,
+            Some(MeasurementUnit::Teaspoon | MeasurementUnit::Tablespoon) => Decimal::new(5, 1),
+            Some(MeasurementUnit::Cups) => Decimal::new(25, 2),
+            _ => quantity.round_dp(2).max(Decimal::new(1, 2)),
+        }
+    } else {
+        rounded.normalize()
+    }
+}
+
+/// Convertit une quantité vers l'unité de base (une unité inconnue est traitée comme des grammes)
+pub fn to_base_quantity(quantity: Decimal, unit: &str) -> Decimal {
+    match MeasurementUnit::parse(unit) {
+        Some(u) => quantity * u.base_factor(),
+        None => quantity,
+    }
+}
+
+/// Convertit une quantité exprimée dans l'unité de base vers `unit`
+pub fn from_base_quantity(quantity: Decimal, unit: &str) -> Decimal {
+    match MeasurementUnit::parse(unit) {
+        Some(u) => quantity / u.base_factor(),
+        None => quantity,
+    }
+}
+
+/// Convertit une quantité en grammes : les volumes sont assimilés à de l'eau (1 ml = 1 g)
+/// et une pièce pèse `piece_weight` grammes (colonne `ingredients.weight`)
+pub fn to_grams(quantity: Decimal, unit: &str, piece_weight: Decimal) -> Decimal {
+    match MeasurementUnit::parse(unit) {
+        Some(MeasurementUnit::Pieces) => quantity * piece_weight,
+        Some(u) => quantity * u.base_factor(),
+        None => quantity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::dec;
+
+    #[test]
+    fn parse_rejects_unknown_units() {
+        assert_eq!(MeasurementUnit::parse("cups"), Some(MeasurementUnit::Cups));
+        assert_eq!(MeasurementUnit::parse("Cups"), None);
+        assert_eq!(MeasurementUnit::parse("pinch"), None);
+    }
+
+    #[test]
+    fn base_conversion_round_trips() {
+        assert_eq!(to_base_quantity(dec("1.5"), "kilograms"), dec("1500"));
+        assert_eq!(to_base_quantity(dec("2"), "tablespoon"), dec("30"));
+        assert_eq!(to_base_quantity(dec("0.5"), "cups"), dec("120"));
+        assert_eq!(from_base_quantity(dec("750"), "liters"), dec("0.75"));
+        assert_eq!(from_base_quantity(dec("15"), "teaspoon"), dec("3"));
+    }
+
+    #[test]
+    fn unknown_units_are_left_unchanged() {
+        assert_eq!(to_base_quantity(dec("42"), "pinch"), dec("42"));
+        assert_eq!(from_base_quantity(dec("42"), "pinch"), dec("42"));
+        assert_eq!(to_grams(dec("42"), "pinch", dec("100")), dec("42"));
+    }
+
+    #[test]
+    fn to_grams_uses_piece_weight() {
+        assert_eq!(to_grams(dec("3"), "pieces", dec("60")), dec("180"));
+        assert_eq!(to_grams(dec("2"), "liters", dec("60")), dec("2000"));
+    }
+
+    #[test]
+    fn round_for_unit_uses_kitchen_precision() {
+        assert_eq!(round_for_unit(dec("2.4"), "pieces"), dec("2"));
+        assert_eq!(round_for_unit(dec("1.3"), "teaspoon"), dec("1.5"));
+        assert_eq!(round_for_unit(dec("0.6"), "cups"), dec("0.5"));
+        assert_eq!(round_for_unit(dec("1.234"), "kilograms"), dec("1.23"));
+        assert_eq!(round_for_unit(dec("12.6"), "grams"), dec("13"));
+        assert_eq!(round_for_unit(dec("2.34"), "milliliters"), dec("2.3"));
+    }
+
+    #[test]
+    fn round_for_unit_keeps_small_quantities() {
+        assert_eq!(round_for_unit(dec("0.2"), "pieces"), dec("1"));
+        assert_eq!(round_for_unit(dec("0.1"), "tablespoon"), dec("0.5"));
+        assert_eq!(round_for_unit(dec("0.05"), "cups"), dec("0.25"));
+        assert_eq!(round_for_unit(dec("0.001"), "grams"), dec("0.01"));
+        assert_eq!(round_for_unit(Decimal::ZERO, "pieces"), Decimal::ZERO);
+    }
+}